@@ -24,22 +24,79 @@ ARGS:
 
 OPTIONS:
         --an                      Sorts variables in an alphanumeric manner
+        --asp-semantics <ASP_SEMANTICS>
+                                  Choose the semantics of the ASP export [default: Complete]
+                                  [possible values: Grounded, Admissible, Complete, TwoValued,
+                                  Stable]
+        --cnf-formulae            Encode the acceptance conditions of the CNF export from the parsed
+                                  formulae instead of the BDD (only two valued semantics)
+        --cnf-semantics <CNF_SEMANTICS>
+                                  Choose the semantics of the CNF export, where stable candidates
+                                  extend the grounded model but are not checked for stability
+                                  [default: TwoValued] [possible values: TwoValued,
+                                  StableCandidate, Admissible, Complete]
         --com                     Compute the complete models
+        --count                   Print the number of models of each kind, prefixed by its
+                                  semantics, instead of the models themselves (two valued models
+                                  are counted symbolically, and so are single-formula stable
+                                  models in hybrid and biodivine mode)
         --counter <COUNTER>       Set if the (counter-)models shall be computed and printed,
                                   possible values are 'nai' and 'mem' for naive and memoization
                                   repectively (only works in hybrid and naive mode)
+        --decompose               Compute the complete and stable models by decomposing the
+                                  statement dependency graph into its strongly connected
+                                  components (only works in hybrid and naive mode)
+        --dot <DOT>               Write a graphviz DOT diagram of each acceptance condition into
+                                  the given directory, named after the index and the name of its
+                                  statement, together with the conditions restricted by the
+                                  grounded model if it is computed (only works in hybrid and naive
+                                  mode)
+        --explain                 Explain the values of the grounded model, by their derivation or
+                                  by two completions on which the acceptance condition disagrees,
+                                  and why no stable model exists, if none is found, from the
+                                  conflicts of the nogood-learning based approach, which then
+                                  computes the stable models (only works in hybrid and naive mode)
         --export <EXPORT>         Export the adf-bdd state after parsing and BDD instantiation to
                                   the given filename
+        --export-asp <EXPORT_ASP>
+                                  Export an answer set program, whose answer sets are the models of
+                                  the chosen semantics, to the given filename (only works in hybrid
+                                  and naive mode)
+        --export-cnf <EXPORT_CNF>
+                                  Export the models of the chosen semantics as DIMACS CNF to the
+                                  given filename (only works in hybrid and naive mode)
+        --export-qbf <EXPORT_QBF>
+                                  Export the chosen preferred reasoning task for the given
+                                  statement as QDIMACS QBF to the given filename (only works in
+                                  hybrid and naive mode)
         --grd                     Compute the grounded model
     -h, --help                    Print help information
         --heu <HEU>               Choose which heuristics shall be used by the nogood-learning
                                   approach [possible values: Simple, MinModMinPathsMaxVarImp,
                                   MinModMaxVarImpMinPaths]
         --import                  Import an adf- bdd state instead of an adf
-        --lib <IMPLEMENTATION>    Choose the bdd implementation of either 'biodivine', 'naive', or
-                                  hybrid [default: hybrid]
+        --lib <IMPLEMENTATION>    Choose the bdd implementation of either 'biodivine', 'naive',
+                                  'sat', or hybrid, where 'sat' computes the two valued and stable
+                                  models with an embedded SAT solver [default: hybrid]
+        --limit <LIMIT>           Stop the computation of each kind of models after the given
+                                  number of models
         --lx                      Sorts variables in an lexicographic manner
+        --order <ORDER>           Sorts variables with respect to the given structural ordering of
+                                  the statement dependency graph [possible values: Lexi, Alphanum,
+                                  Dfs, Bfs, Force, MinDegree, Scc]
+        --prf                     Compute the preferred models by decomposing the statement
+                                  dependency graph into its strongly connected components (only
+                                  works in hybrid and naive mode)
+        --qbf-statement <QBF_STATEMENT>
+                                  The statement, whose acceptance is decided by the QBF export
+        --qbf-task <QBF_TASK>     Choose the reasoning task of the QBF export, i.e. whether the
+                                  statement is true in some or all preferred interpretations
+                                  [default: SkepticalPreferred] [possible values:
+                                  CredulousPreferred, SkepticalPreferred]
     -q                            Sets log verbosity to only errors
+        --rewrite <REWRITE>       Rewrite the acceptance conditions into the given normal form
+                                  before instantiating the BDDs [possible values: Simplified,
+                                  Eliminated, Nnf, Cnf, Dnf]
         --rust_log <RUST_LOG>     Sets the verbosity to 'warn', 'info', 'debug' or 'trace' if -v and
                                   -q are not use [env: RUST_LOG=debug]
         --stm                     Compute the stable models
@@ -53,6 +110,10 @@ OPTIONS:
                                   hybrid lib-mode)
         --stmrew2                 Compute the stable models with a single-formula rewriting on
                                   internal representation(only hybrid lib-mode)
+        --split <SPLIT>           Number of heuristic choices, which are used to split the search
+                                  space for the worker threads [default: 4]
+        --threads <THREADS>       Number of worker threads for the nogood-learning based approach
+                                  (only works in hybrid and naive mode)
         --twoval                  Compute the two valued models with the nogood-learning based
                                   approach
     -v                            Sets log verbosity (multiple times means more verbose)
@@ -75,13 +136,16 @@ OPTIONS:
     variant_size_differences
 )]
 
-use std::{fs::File, path::PathBuf};
+use std::{fs::File, num::NonZeroUsize, path::PathBuf};
 
-use adf_bdd::adf::Adf;
+use adf_bdd::datatypes::{adf::PrintDictionary, Term, Var};
+
+use adf_bdd::adf::{asp::AspSemantics, cnf::CnfSemantics, qbf::QbfTask, Adf};
 use adf_bdd::adfbiodivine::Adf as BdAdf;
+use adf_bdd::obdd::CountOverflow;
 
-use adf_bdd::parser::AdfParser;
-use clap::Parser;
+use adf_bdd::parser::{rewriting::NormalForm, AdfParser, VarOrdering};
+use clap::{builder::TypedValueParser, Parser};
 use crossbeam_channel::unbounded;
 use strum::VariantNames;
 
@@ -94,7 +158,7 @@ struct App {
     /// Sets the verbosity to 'warn', 'info', 'debug' or 'trace' if -v and -q are not use
     #[arg(long = "rust_log", env)]
     rust_log: Option<String>,
-    /// Choose the bdd implementation of either 'biodivine', 'naive', or hybrid
+    /// Choose the bdd implementation of either 'biodivine', 'naive', 'sat', or hybrid, where 'sat' computes the two valued and stable models with an embedded SAT solver
     #[arg(long = "lib", default_value = "hybrid")]
     implementation: String,
     /// Sets log verbosity (multiple times means more verbose)
@@ -109,6 +173,12 @@ struct App {
     /// Sorts variables in an alphanumeric manner
     #[arg(long = "an", group = "sorting")]
     sort_alphan: bool,
+    /// Sorts variables with respect to the given structural ordering of the statement dependency graph
+    #[arg(long, group = "sorting", value_parser = clap::builder::PossibleValuesParser::new(VarOrdering::VARIANTS).map(|s| s.parse::<VarOrdering>().expect("value has been checked by clap")))]
+    order: Option<VarOrdering>,
+    /// Rewrite the acceptance conditions into the given normal form before instantiating the BDDs
+    #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(NormalForm::VARIANTS).map(|s| s.parse::<NormalForm>().expect("value has been checked by clap")))]
+    rewrite: Option<NormalForm>,
     /// Compute the grounded model
     #[arg(long = "grd")]
     grounded: bool,
@@ -136,21 +206,69 @@ struct App {
     /// Choose which heuristics shall be used by the nogood-learning approach
     #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(adf_bdd::adf::heuristics::Heuristic::VARIANTS.iter().filter(|&v| v != &"Custom").collect::<Vec<_>>()))]
     heu: Option<adf_bdd::adf::heuristics::Heuristic<'static>>,
+    /// Number of worker threads for the nogood-learning based approach (only works in hybrid and naive mode)
+    #[arg(long)]
+    threads: Option<NonZeroUsize>,
+    /// Number of heuristic choices, which are used to split the search space for the worker threads
+    #[arg(long, default_value_t = 4, requires = "threads")]
+    split: usize,
     /// Compute the two valued models with the nogood-learning based approach
     #[arg(long = "twoval")]
     two_val: bool,
     /// Compute the complete models
     #[arg(long = "com")]
     complete: bool,
+    /// Compute the preferred models by decomposing the statement dependency graph into its strongly connected components (only works in hybrid and naive mode)
+    #[arg(long = "prf")]
+    preferred: bool,
+    /// Compute the complete and stable models by decomposing the statement dependency graph into its strongly connected components (only works in hybrid and naive mode)
+    #[arg(long)]
+    decompose: bool,
     /// Import an adf- bdd state instead of an adf
     #[arg(long)]
     import: bool,
     /// Export the adf-bdd state after parsing and BDD instantiation to the given filename
     #[arg(long)]
     export: Option<PathBuf>,
+    /// Export the models of the chosen semantics as DIMACS CNF to the given filename (only works in hybrid and naive mode)
+    #[arg(long = "export-cnf")]
+    export_cnf: Option<PathBuf>,
+    /// Choose the semantics of the CNF export, where stable candidates extend the grounded model but are not checked for stability
+    #[arg(long = "cnf-semantics", default_value = "TwoValued", requires = "export_cnf", value_parser = clap::builder::PossibleValuesParser::new(CnfSemantics::VARIANTS).map(|s| s.parse::<CnfSemantics>().expect("value has been checked by clap")))]
+    cnf_semantics: CnfSemantics,
+    /// Encode the acceptance conditions of the CNF export from the parsed formulae instead of the BDD (only two valued semantics)
+    #[arg(long = "cnf-formulae", requires = "export_cnf")]
+    cnf_formulae: bool,
+    /// Export an answer set program, whose answer sets are the models of the chosen semantics, to the given filename (only works in hybrid and naive mode)
+    #[arg(long = "export-asp")]
+    export_asp: Option<PathBuf>,
+    /// Choose the semantics of the ASP export
+    #[arg(long = "asp-semantics", default_value = "Complete", requires = "export_asp", value_parser = clap::builder::PossibleValuesParser::new(AspSemantics::VARIANTS).map(|s| s.parse::<AspSemantics>().expect("value has been checked by clap")))]
+    asp_semantics: AspSemantics,
+    /// Export the chosen preferred reasoning task for the given statement as QDIMACS QBF to the given filename (only works in hybrid and naive mode)
+    #[arg(long = "export-qbf", requires = "qbf_statement")]
+    export_qbf: Option<PathBuf>,
+    /// Choose the reasoning task of the QBF export, i.e. whether the statement is true in some or all preferred interpretations
+    #[arg(long = "qbf-task", default_value = "SkepticalPreferred", requires = "export_qbf", value_parser = clap::builder::PossibleValuesParser::new(QbfTask::VARIANTS).map(|s| s.parse::<QbfTask>().expect("value has been checked by clap")))]
+    qbf_task: QbfTask,
+    /// The statement, whose acceptance is decided by the QBF export
+    #[arg(long = "qbf-statement", requires = "export_qbf")]
+    qbf_statement: Option<String>,
     /// Set if the (counter-)models shall be computed and printed, possible values are 'nai' and 'mem' for naive and memoization repectively (only works in hybrid and naive mode)
     #[arg(long)]
     counter: Option<String>,
+    /// Stop the computation of each kind of models after the given number of models
+    #[arg(long)]
+    limit: Option<usize>,
+    /// Print the number of models of each kind, prefixed by its semantics, instead of the models themselves (two valued models are counted symbolically, and so are single-formula stable models in hybrid and biodivine mode)
+    #[arg(long)]
+    count: bool,
+    /// Explain the values of the grounded model, by their derivation or by two completions on which the acceptance condition disagrees, and why no stable model exists, if none is found, from the conflicts of the nogood-learning based approach, which then computes the stable models (only works in hybrid and naive mode)
+    #[arg(long)]
+    explain: bool,
+    /// Write a graphviz DOT diagram of each acceptance condition into the given directory, named after the index and the name of its statement, together with the conditions restricted by the grounded model if it is computed (only works in hybrid and naive mode)
+    #[arg(long)]
+    dot: Option<PathBuf>,
 }
 
 impl App {
@@ -194,6 +312,12 @@ impl App {
                 if self.sort_alphan {
                     parser.varsort_alphanum();
                 }
+                if let Some(order) = self.order {
+                    parser.varsort(order);
+                }
+                if let Some(form) = self.rewrite {
+                    parser.rewrite(form);
+                }
                 let adf = if !self.stable_rew {
                     BdAdf::from_parser(&parser)
                 } else {
@@ -221,67 +345,202 @@ impl App {
                 log::info!("[Start] translate into naive representation");
                 let mut naive_adf = adf.hybrid_step();
                 log::info!("[Done] translate into naive representation");
+                self.write_cnf(&mut naive_adf, Some(&parser));
+                self.write_asp(&naive_adf);
+                self.write_qbf(&naive_adf);
+                self.write_dot_statements(&naive_adf);
                 if self.grounded {
                     let grounded = naive_adf.grounded();
                     print!("{}", naive_adf.print_interpretation(&grounded));
+                    self.write_dot_model(&mut naive_adf, "grounded", &grounded);
+                    if self.explain {
+                        // the hybrid step already decides the grounded model, which hides its derivation
+                        self.explain_grounded(&mut adf.hybrid_step_opt(false));
+                    }
                 }
 
                 let printer = naive_adf.print_dictionary();
 
-                if self.complete {
-                    for model in naive_adf.complete() {
-                        print!("{}", printer.print_interpretation(&model));
-                    }
+                if self.complete && self.decompose {
+                    self.print_models("complete", naive_adf.complete_decomposed(), &printer);
+                } else if self.complete {
+                    self.print_models("complete", naive_adf.complete(), &printer);
                 }
 
-                if self.two_val {
+                if self.preferred {
+                    self.print_models("preferred", naive_adf.preferred_decomposed(), &printer);
+                }
+
+                if self.two_val && self.count && self.limit.is_none() {
+                    Self::print_two_valued_count(adf.two_valued_count());
+                } else if self.two_val {
                     let (sender, receiver) = unbounded();
-                    naive_adf.two_val_nogood_channel(self.heu.unwrap_or_default(), sender);
-                    for model in receiver.into_iter() {
-                        print!("{}", printer.print_interpretation(&model));
+                    match self.threads {
+                        Some(threads) => {
+                            let workers = naive_adf.two_val_nogood_channel_parallel(
+                                self.heu.unwrap_or_default(),
+                                self.split,
+                                threads,
+                                sender,
+                            );
+                            self.print_models("two valued", receiver, &printer);
+                            workers.join().expect("Worker threads should not panic");
+                        }
+                        None => {
+                            naive_adf.two_val_nogood_channel(self.heu.unwrap_or_default(), sender);
+                            self.print_models("two valued", receiver, &printer);
+                        }
                     }
                 }
 
                 if self.stable {
-                    for model in naive_adf.stable() {
-                        print!("{}", printer.print_interpretation(&model));
+                    if self.explain {
+                        self.print_explained_stable(&mut naive_adf, &printer);
+                    } else if self.decompose {
+                        self.print_models("stable", naive_adf.stable_decomposed(), &printer);
+                    } else {
+                        self.print_models("stable", naive_adf.stable(), &printer);
                     }
                 }
 
                 if self.stable_counting_a {
-                    for model in naive_adf.stable_count_optimisation_heu_a() {
-                        print!("{}", printer.print_interpretation(&model));
-                    }
+                    self.print_models(
+                        "stable",
+                        naive_adf.stable_count_optimisation_heu_a(),
+                        &printer,
+                    );
                 }
 
                 if self.stable_counting_b {
-                    for model in naive_adf.stable_count_optimisation_heu_b() {
-                        print!("{}", printer.print_interpretation(&model));
-                    }
+                    self.print_models(
+                        "stable",
+                        naive_adf.stable_count_optimisation_heu_b(),
+                        &printer,
+                    );
                 }
 
                 if self.stable_pre {
-                    for model in naive_adf.stable_with_prefilter() {
-                        print!("{}", printer.print_interpretation(&model));
-                    }
+                    self.print_models("stable", naive_adf.stable_with_prefilter(), &printer);
                 }
 
-                if self.stable_rew || self.stable_rew2 {
-                    for model in naive_adf.stable_bdd_representation(&adf) {
-                        print!("{}", printer.print_interpretation(&model));
-                    }
+                if (self.stable_rew || self.stable_rew2) && self.count && self.limit.is_none() {
+                    println!("stable: {}", adf.stable_count());
+                } else if self.stable_rew || self.stable_rew2 {
+                    self.print_models(
+                        "stable",
+                        naive_adf.stable_bdd_representation(&adf),
+                        &printer,
+                    );
                 }
 
                 if self.stable_ng {
-                    for model in naive_adf.stable_nogood(self.heu.unwrap_or_default()) {
-                        print!("{}", printer.print_interpretation(&model));
+                    if self.explain {
+                        if self.threads.is_some() {
+                            log::error!("--threads is not supported together with --explain, the models are computed sequentially");
+                        }
+                        self.print_explained_stable(&mut naive_adf, &printer);
+                    } else if let Some(threads) = self.threads {
+                        let (sender, receiver) = unbounded();
+                        let workers = naive_adf.stable_nogood_channel_parallel(
+                            self.heu.unwrap_or_default(),
+                            self.split,
+                            threads,
+                            sender,
+                        );
+                        self.print_models("stable", receiver, &printer);
+                        workers.join().expect("Worker threads should not panic");
+                    } else {
+                        self.print_models(
+                            "stable",
+                            naive_adf.stable_nogood(self.heu.unwrap_or_default()),
+                            &printer,
+                        );
                     }
                 }
             }
+            "sat" => {
+                if self.counter.is_some()
+                    || self.stable_counting_a
+                    || self.stable_counting_b
+                    || self.stable_pre
+                    || self.stable_rew
+                    || self.stable_rew2
+                    || self.stable_ng
+                    || self.preferred
+                    || self.decompose
+                {
+                    log::error!("Only --grd, --com, --twoval, and --stm are supported in sat mode");
+                }
+                if self.threads.is_some() {
+                    log::error!("--threads is not supported in sat mode, the models are computed sequentially");
+                }
+                let parser = AdfParser::default();
+                match parser.parse()(&input) {
+                    Ok(_) => log::info!("[Done] parsing"),
+                    Err(e) => {
+                        log::error!("Error during parsing:\n{} \n\n cannot continue, panic!", e);
+                        panic!("Parsing failed, see log for further details")
+                    }
+                }
+                if self.sort_lex {
+                    parser.varsort_lexi();
+                }
+                if self.sort_alphan {
+                    parser.varsort_alphanum();
+                }
+                if let Some(order) = self.order {
+                    parser.varsort(order);
+                }
+                if let Some(form) = self.rewrite {
+                    parser.rewrite(form);
+                }
+                let mut adf = BdAdf::from_parser(&parser).hybrid_step();
+                self.write_cnf(&mut adf, Some(&parser));
+                self.write_asp(&adf);
+                self.write_qbf(&adf);
+                self.write_dot_statements(&adf);
+                if self.grounded {
+                    let grounded = adf.grounded();
+                    print!("{}", adf.print_interpretation(&grounded));
+                    self.write_dot_model(&mut adf, "grounded", &grounded);
+                }
+
+                let printer = adf.print_dictionary();
+
+                if self.complete {
+                    self.print_models("complete", adf.complete(), &printer);
+                }
+
+                if self.two_val {
+                    self.print_models("two valued", adf.two_valued_sat(), &printer);
+                }
+
+                if self.stable {
+                    let found = self.print_models("stable", adf.stable_sat(), &printer);
+                    self.explain_no_stable(&mut adf, found);
+                }
+            }
             "biodivine" => {
                 if self.counter.is_some() {
                     log::error!("Modelcounting not supported in biodivine mode");
                 }
+                if self.dot.is_some() {
+                    log::error!("DOT export not supported in biodivine mode");
+                }
+                if self.explain {
+                    log::error!("Explanations not supported in biodivine mode");
+                }
+                if self.preferred || self.decompose {
+                    log::error!(
+                        "Preferred models and the decomposition not supported in biodivine mode"
+                    );
+                }
+                if self.export_cnf.is_some()
+                    || self.export_asp.is_some()
+                    || self.export_qbf.is_some()
+                {
+                    log::error!("CNF, ASP, and QBF export not supported in biodivine mode");
+                }
                 let parser = AdfParser::default();
                 match parser.parse()(&input) {
                     Ok(_) => log::info!("[Done] parsing"),
@@ -297,6 +556,12 @@ impl App {
                 if self.sort_alphan {
                     parser.varsort_alphanum();
                 }
+                if let Some(order) = self.order {
+                    parser.varsort(order);
+                }
+                if let Some(form) = self.rewrite {
+                    parser.rewrite(form);
+                }
                 let adf = if !self.stable_rew {
                     BdAdf::from_parser(&parser)
                 } else {
@@ -308,25 +573,30 @@ impl App {
                     print!("{}", adf.print_interpretation(&grounded));
                 }
 
+                let printer = adf.print_dictionary();
+
                 if self.complete {
-                    for model in adf.complete() {
-                        print!("{}", adf.print_interpretation(&model));
-                    }
+                    self.print_models("complete", adf.complete(), &printer);
+                }
+
+                if self.two_val && self.count && self.limit.is_none() {
+                    Self::print_two_valued_count(adf.two_valued_count());
+                } else if self.two_val {
+                    self.print_models("two valued", adf.two_valued(), &printer);
                 }
 
                 if self.stable {
-                    for model in adf.stable() {
-                        print!("{}", adf.print_interpretation(&model));
-                    }
+                    self.print_models("stable", adf.stable(), &printer);
                 }
 
-                if self.stable_rew || self.stable_rew2 {
-                    for model in adf.stable_bdd_representation() {
-                        print!("{}", adf.print_interpretation(&model));
-                    }
+                if (self.stable_rew || self.stable_rew2) && self.count && self.limit.is_none() {
+                    println!("stable: {}", adf.stable_count());
+                } else if self.stable_rew || self.stable_rew2 {
+                    self.print_models("stable", adf.stable_bdd_representation(), &printer);
                 }
             }
             _ => {
+                let parser = AdfParser::default();
                 let mut adf = if self.import {
                     #[cfg(not(feature = "adhoccounting"))]
                     {
@@ -341,7 +611,6 @@ impl App {
                         result
                     }
                 } else {
-                    let parser = AdfParser::default();
                     match parser.parse()(&input) {
                         Ok(_) => log::info!("[Done] parsing"),
                         Err(e) => {
@@ -359,6 +628,12 @@ impl App {
                     if self.sort_alphan {
                         parser.varsort_alphanum();
                     }
+                    if let Some(order) = self.order {
+                        parser.varsort(order);
+                    }
+                    if let Some(form) = self.rewrite {
+                        parser.rewrite(form);
+                    }
                     Adf::from_parser(&parser)
                 };
                 if let Some(export) = &self.export {
@@ -397,32 +672,274 @@ impl App {
                     None => {}
                 }
 
+                self.write_cnf(&mut adf, (!self.import).then_some(&parser));
+                self.write_asp(&adf);
+                self.write_qbf(&adf);
+                self.write_dot_statements(&adf);
                 if self.grounded {
                     let grounded = adf.grounded();
                     print!("{}", adf.print_interpretation(&grounded));
+                    self.write_dot_model(&mut adf, "grounded", &grounded);
+                    if self.explain {
+                        self.explain_grounded(&mut adf);
+                    }
                 }
                 if self.complete {
                     let printer = adf.print_dictionary();
-                    for model in adf.complete() {
-                        print!("{}", printer.print_interpretation(&model));
+                    if self.decompose {
+                        self.print_models("complete", adf.complete_decomposed(), &printer);
+                    } else {
+                        self.print_models("complete", adf.complete(), &printer);
+                    }
+                }
+                if self.preferred {
+                    let printer = adf.print_dictionary();
+                    self.print_models("preferred", adf.preferred_decomposed(), &printer);
+                }
+                if self.two_val && self.count && self.limit.is_none() {
+                    Self::print_two_valued_count(adf.two_valued_count());
+                } else if self.two_val {
+                    let printer = adf.print_dictionary();
+                    let (sender, receiver) = unbounded();
+                    match self.threads {
+                        Some(threads) => {
+                            let workers = adf.two_val_nogood_channel_parallel(
+                                self.heu.unwrap_or_default(),
+                                self.split,
+                                threads,
+                                sender,
+                            );
+                            self.print_models("two valued", receiver, &printer);
+                            workers.join().expect("Worker threads should not panic");
+                        }
+                        None => {
+                            adf.two_val_nogood_channel(self.heu.unwrap_or_default(), sender);
+                            self.print_models("two valued", receiver, &printer);
+                        }
                     }
                 }
                 if self.stable {
                     let printer = adf.print_dictionary();
-                    for model in adf.stable() {
-                        print!("{}", printer.print_interpretation(&model));
+                    if self.explain {
+                        self.print_explained_stable(&mut adf, &printer);
+                    } else if self.decompose {
+                        self.print_models("stable", adf.stable_decomposed(), &printer);
+                    } else {
+                        self.print_models("stable", adf.stable(), &printer);
                     }
                 }
 
                 if self.stable_ng {
                     let printer = adf.print_dictionary();
-                    for model in adf.stable_nogood(self.heu.unwrap_or_default()) {
-                        print!("{}", printer.print_interpretation(&model));
+                    if self.explain {
+                        if self.threads.is_some() {
+                            log::error!("--threads is not supported together with --explain, the models are computed sequentially");
+                        }
+                        self.print_explained_stable(&mut adf, &printer);
+                    } else if let Some(threads) = self.threads {
+                        let (sender, receiver) = unbounded();
+                        let workers = adf.stable_nogood_channel_parallel(
+                            self.heu.unwrap_or_default(),
+                            self.split,
+                            threads,
+                            sender,
+                        );
+                        self.print_models("stable", receiver, &printer);
+                        workers.join().expect("Worker threads should not panic");
+                    } else {
+                        self.print_models(
+                            "stable",
+                            adf.stable_nogood(self.heu.unwrap_or_default()),
+                            &printer,
+                        );
                     }
                 }
             }
         }
     }
+
+    /// Prints the given models, or only their number if requested, up to the given limit.
+    /// Returns the number of models.
+    fn print_models<I>(&self, semantics: &str, models: I, printer: &PrintDictionary) -> usize
+    where
+        I: IntoIterator<Item = Vec<Term>>,
+    {
+        let models = models.into_iter().take(self.limit.unwrap_or(usize::MAX));
+        if self.count {
+            let count = models.count();
+            println!("{semantics}: {count}");
+            count
+        } else {
+            let mut count = 0;
+            for model in models {
+                print!("{}", printer.print_interpretation(&model));
+                count += 1;
+            }
+            count
+        }
+    }
+
+    /// Prints the symbolically computed number of two valued models, or reports that it is too large to be counted exactly.
+    fn print_two_valued_count(count: Result<u128, CountOverflow>) {
+        match count {
+            Ok(count) => println!("two valued: {count}"),
+            Err(e) => log::error!("Cannot count the two valued models: {e}"),
+        }
+    }
+
+    /// Prints why no stable model exists, if explanations are requested and no stable model has been found.
+    fn explain_no_stable(&self, adf: &mut Adf, found: usize) {
+        if self.explain && found == 0 && self.limit != Some(0) {
+            if let Some(conflict) = adf.explain_no_stable() {
+                print!("{}", conflict.to_text(&adf.print_dictionary()));
+            }
+        }
+    }
+
+    /// Prints the stable models of the nogood-learner and, if none is found, why no stable model exists.
+    /// The explanation is derived from the conflicts of the same search.
+    fn print_explained_stable(&self, adf: &mut Adf, printer: &PrintDictionary) {
+        let mut models = adf.stable_nogood_explained(self.heu.unwrap_or_default());
+        let found = self.print_models("stable", &mut models, printer);
+        if found == 0 && self.limit != Some(0) {
+            if let Some(conflict) = models.explanation() {
+                print!("{}", conflict.to_text(printer));
+            }
+        }
+    }
+
+    /// Prints the explanation of the grounded model and writes its DOT diagram, if a target directory is given.
+    fn explain_grounded(&self, adf: &mut Adf) {
+        let explanation = adf.explain_grounded();
+        let printer = adf.print_dictionary();
+        print!("{}", explanation.to_text(&printer));
+        if let Some(dir) = &self.dot {
+            Self::write_dot_file(
+                &dir.join("grounded_explanation.dot"),
+                &explanation.to_dot(&printer),
+            );
+        }
+    }
+
+    /// Writes the CNF encoding of the chosen semantics, if a target file is given.
+    /// The acceptance conditions are encoded from the formulae of the parser if requested and available, and from the BDD otherwise.
+    fn write_cnf(&self, adf: &mut Adf, parser: Option<&AdfParser>) {
+        if let Some(path) = &self.export_cnf {
+            if path.exists() {
+                log::error!(
+                    "Cannot write CNF file <{}>, as it already exists",
+                    path.to_string_lossy()
+                );
+                return;
+            }
+            let cnf = match parser.filter(|_| self.cnf_formulae) {
+                Some(parser) => adf
+                    .formulae_to_cnf(parser, self.cnf_semantics)
+                    .unwrap_or_else(|reason| {
+                        log::error!("{reason}, encoding the BDD instead");
+                        adf.to_cnf(self.cnf_semantics)
+                    }),
+                None => {
+                    if self.cnf_formulae {
+                        log::error!("No formulae available, encoding the BDD instead");
+                    }
+                    adf.to_cnf(self.cnf_semantics)
+                }
+            };
+            std::fs::write(path, cnf.to_string()).unwrap_or_else(|reason| {
+                panic!("couldn't write {}: {}", path.to_string_lossy(), reason)
+            });
+        }
+    }
+
+    /// Writes the answer set program of the chosen semantics, if a target file is given.
+    fn write_asp(&self, adf: &Adf) {
+        if let Some(path) = &self.export_asp {
+            if path.exists() {
+                log::error!(
+                    "Cannot write ASP file <{}>, as it already exists",
+                    path.to_string_lossy()
+                );
+                return;
+            }
+            std::fs::write(path, adf.to_asp(self.asp_semantics)).unwrap_or_else(|reason| {
+                panic!("couldn't write {}: {}", path.to_string_lossy(), reason)
+            });
+        }
+    }
+
+    /// Writes the QBF of the chosen reasoning task, if a target file is given.
+    fn write_qbf(&self, adf: &Adf) {
+        if let (Some(path), Some(name)) = (&self.export_qbf, &self.qbf_statement) {
+            if path.exists() {
+                log::error!(
+                    "Cannot write QBF file <{}>, as it already exists",
+                    path.to_string_lossy()
+                );
+                return;
+            }
+            match adf.ordering.variable(name) {
+                Some(statement) => {
+                    std::fs::write(path, adf.to_qbf(self.qbf_task, statement).to_string())
+                        .unwrap_or_else(|reason| {
+                            panic!("couldn't write {}: {}", path.to_string_lossy(), reason)
+                        })
+                }
+                None => log::error!("Cannot write QBF file, as statement {name} does not exist"),
+            }
+        }
+    }
+
+    /// Writes one DOT diagram for each acceptance condition, if a target directory is given.
+    fn write_dot_statements(&self, adf: &Adf) {
+        if let Some(dir) = &self.dot {
+            for idx in 0..adf.ac.len() {
+                let name = adf
+                    .ordering
+                    .name(Var(idx))
+                    .expect("Each statement has a name");
+                Self::write_dot_file(
+                    &dir.join(format!("ac_{idx}_{}.dot", sanitize_filename(&name))),
+                    &adf.ac_to_dot(&[Var(idx)]),
+                );
+            }
+        }
+    }
+
+    /// Writes one DOT diagram of all acceptance conditions, restricted by the given model, if a target directory is given.
+    fn write_dot_model(&self, adf: &mut Adf, name: &str, model: &[Term]) {
+        if let Some(dir) = &self.dot {
+            Self::write_dot_file(
+                &dir.join(format!("{}.dot", name)),
+                &adf.restricted_ac_to_dot(model),
+            );
+        }
+    }
+
+    fn write_dot_file(path: &std::path::Path, content: &str) {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).unwrap_or_else(|reason| {
+                panic!("couldn't create {}: {}", dir.to_string_lossy(), reason)
+            });
+        }
+        std::fs::write(path, content).unwrap_or_else(|reason| {
+            panic!("couldn't write {}: {}", path.to_string_lossy(), reason)
+        });
+    }
+}
+
+/// Replaces each character of a statement name, which might not be valid in a filename.
+/// Different names might be mapped to the same result, so the filenames need to contain the index of the statement as well.
+fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
 }
 
 fn main() {
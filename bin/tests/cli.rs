@@ -17,6 +17,18 @@ fn arguments() -> Result<(), Box<dyn std::error::Error>> {
         "argument '--lx' cannot be used with '--an'",
     ));
 
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg("--lx").arg("--order").arg("Scc").arg("file.txt");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "argument '--lx' cannot be used with '--order <ORDER>'",
+    ));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg("--order").arg("Unknown").arg("file.txt");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'Unknown'"));
+
     cmd = Command::cargo_bin("adf-bdd")?;
     cmd.arg("-h");
     cmd.assert()
@@ -76,6 +88,21 @@ fn runs_naive() -> Result<(), Box<dyn std::error::Error>> {
         "u(1) u(10) u(2) u(3) F(4) F(5) u(6) u(7) u(8) u(9)",
     ));
 
+    for order in ["Dfs", "Bfs", "Force", "MinDegree", "Scc"] {
+        cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--order")
+            .arg(order)
+            .arg("--grd")
+            .arg("--lib")
+            .arg("naive");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("F(4)"))
+            .stdout(predicate::str::contains("F(5)"))
+            .stdout(predicate::str::contains("u(7)"));
+    }
+
     cmd = Command::cargo_bin("adf-bdd")?;
     cmd.arg(file.path())
         .arg("--an")
@@ -148,6 +175,57 @@ fn runs_naive() -> Result<(), Box<dyn std::error::Error>> {
         "u(1) u(2) u(3) F(4) F(5) u(6) u(7) u(8) u(9) u(10) \n",
     ));
 
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--an")
+        .arg("--grd")
+        .arg("--lib")
+        .arg("naive")
+        .arg("--dot")
+        .arg(tempdir.path().join("dot"));
+    cmd.assert().success().stdout(predicate::str::contains(
+        "u(1) u(2) u(3) F(4) F(5) u(6) u(7) u(8) u(9) u(10) \n",
+    ));
+    tempdir
+        .child("dot/ac_0_1.dot")
+        .assert(predicate::str::starts_with("digraph {"));
+    tempdir
+        .child("dot/ac_9_10.dot")
+        .assert(predicate::str::contains("[label=\"10\", shape=plaintext]"));
+    tempdir
+        .child("dot/grounded.dot")
+        .assert(predicate::str::contains("[label=\"5\", shape=plaintext]"));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--grd")
+        .arg("--dot")
+        .arg(tempdir.path().join("dot_hybrid"));
+    cmd.assert().success();
+    tempdir
+        .child("dot_hybrid/ac_0_7.dot")
+        .assert(predicate::str::contains("[style=dashed]"));
+    tempdir
+        .child("dot_hybrid/grounded.dot")
+        .assert(predicate::path::exists());
+
+    // names, which are sanitized to the same filename, are kept apart by their index
+    let colliding = assert_fs::NamedTempFile::new("colliding.adf")?;
+    colliding.write_str("s(\"a_b\").s(\"a.b\").ac(\"a_b\",c(v)).ac(\"a.b\",c(f)).")?;
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(colliding.path())
+        .arg("--lib")
+        .arg("naive")
+        .arg("--dot")
+        .arg(tempdir.path().join("dot_colliding"));
+    cmd.assert().success();
+    tempdir
+        .child("dot_colliding/ac_0_a_b.dot")
+        .assert(predicate::str::contains("[label=\"a_b\", shape=plaintext]"));
+    tempdir
+        .child("dot_colliding/ac_1_a_b.dot")
+        .assert(predicate::str::contains("[label=\"a.b\", shape=plaintext]"));
+
     #[cfg(feature = "importexport")]
     {
         cmd = Command::cargo_bin("adf-bdd")?;
@@ -355,3 +433,478 @@ fn runs_biodivine_hybrid() -> Result<(), Box<dyn std::error::Error>> {
     ));
     Ok(())
 }
+
+#[test]
+fn runs_parallel_nogood() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).s(c).s(d).s(e).ac(a,neg(b)).ac(b,neg(a)).ac(c,neg(d)).ac(d,neg(c)).ac(e,or(a,c)).")?;
+
+    for lib in ["hybrid", "naive"] {
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path()).arg("--stmng").arg("--lib").arg(lib);
+        let sequential = cmd.output()?;
+        assert!(sequential.status.success());
+        let mut sequential = String::from_utf8(sequential.stdout)?
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        sequential.sort();
+        assert_eq!(sequential.len(), 4);
+
+        cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--stmng")
+            .arg("--threads")
+            .arg("3")
+            .arg("--split")
+            .arg("2")
+            .arg("--lib")
+            .arg(lib);
+        let parallel = cmd.output()?;
+        assert!(parallel.status.success());
+        let mut parallel = String::from_utf8(parallel.stdout)?
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        parallel.sort();
+        assert_eq!(sequential, parallel);
+    }
+
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--twoval")
+        .arg("--threads")
+        .arg("2");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("T(a) F(b) T(c) F(d) T(e)"));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path()).arg("--split").arg("2");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "the following required arguments were not provided",
+    ));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--twoval")
+        .arg("--threads")
+        .arg("0");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value '0'"));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--twoval")
+        .arg("--threads")
+        .arg("2")
+        .arg("--limit")
+        .arg("1");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("T(a)").count(1));
+    Ok(())
+}
+
+#[test]
+fn rewrites_acceptance_conditions() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str(
+        "s(a).s(b).s(c).ac(a,and(c(v),neg(neg(b)))).ac(b,iff(a,or(a,c))).ac(c,xor(c,c(f))).",
+    )?;
+
+    for lib in ["hybrid", "naive", "biodivine"] {
+        for form in ["Simplified", "Eliminated", "Nnf", "Cnf", "Dnf"] {
+            let mut cmd = Command::cargo_bin("adf-bdd")?;
+            cmd.arg(file.path())
+                .arg("--grd")
+                .arg("--stm")
+                .arg("--rewrite")
+                .arg(form)
+                .arg("--lib")
+                .arg(lib);
+            cmd.assert()
+                .success()
+                .stdout("u(a) u(b) u(c) \nT(a) T(b) F(c) \n");
+        }
+    }
+
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path()).arg("--rewrite").arg("Foo");
+    cmd.assert().failure();
+    Ok(())
+}
+
+#[test]
+fn limits_and_counts_models() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).s(c).s(d).s(e).ac(a,neg(b)).ac(b,neg(a)).ac(c,neg(d)).ac(d,neg(c)).ac(e,or(a,c)).")?;
+
+    for lib in ["hybrid", "naive"] {
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--stm")
+            .arg("--limit")
+            .arg("1")
+            .arg("--lib")
+            .arg(lib);
+        let output = cmd.output()?;
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout)?.lines().count(), 1);
+
+        cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--stmng")
+            .arg("--count")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success().stdout("stable: 4\n");
+
+        cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--com")
+            .arg("--count")
+            .arg("--limit")
+            .arg("2")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success().stdout("complete: 2\n");
+
+        // each count is labelled with its semantics
+        cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--com")
+            .arg("--stm")
+            .arg("--count")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success().stdout("complete: 9\nstable: 4\n");
+    }
+
+    for lib in ["hybrid", "naive", "biodivine"] {
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--twoval")
+            .arg("--count")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success().stdout("two valued: 4\n");
+    }
+
+    // 2^140 two valued models exceed the exact count in each mode
+    let huge = assert_fs::NamedTempFile::new("huge_instance.adf")?;
+    huge.write_str(
+        &(0..140)
+            .map(|idx| format!("s(x{idx}).ac(x{idx},x{idx})."))
+            .collect::<String>(),
+    )?;
+    for lib in ["hybrid", "naive", "biodivine"] {
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(huge.path())
+            .arg("--twoval")
+            .arg("--count")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert()
+            .success()
+            .stdout("")
+            .stderr(predicate::str::contains(
+                "Cannot count the two valued models",
+            ));
+    }
+
+    for lib in ["hybrid", "biodivine"] {
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--stmrew")
+            .arg("--count")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success().stdout("stable: 4\n");
+    }
+    Ok(())
+}
+
+#[test]
+fn explains_grounded() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).s(c).ac(a,c(v)).ac(b,neg(a)).ac(c,c).")?;
+    let dir = assert_fs::TempDir::new()?;
+
+    for lib in ["hybrid", "naive"] {
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--grd")
+            .arg("--explain")
+            .arg("--dot")
+            .arg(dir.path())
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success().stdout("T(a) F(b) u(c) \nT(a) in iteration 0, as its acceptance condition is constant\nF(b) in iteration 1, because of T(a)\nu(c), as its acceptance condition accepts T(a) F(b) T(c) and rejects T(a) F(b) F(c)\n");
+        dir.child("grounded_explanation.dot")
+            .assert(predicate::str::contains("s0 -> s1;"));
+    }
+    Ok(())
+}
+
+#[test]
+fn explains_no_stable() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).s(c).ac(a,and(neg(a),neg(b))).ac(b,b).ac(c,c(v)).")?;
+
+    for lib in ["hybrid", "naive"] {
+        for semantics in ["--stm", "--stmng"] {
+            let mut cmd = Command::cargo_bin("adf-bdd")?;
+            cmd.arg(file.path())
+                .arg(semantics)
+                .arg("--explain")
+                .arg("--lib")
+                .arg(lib);
+            cmd.assert().success().stdout("No stable model exists, as each two valued model assigns an unjustified value to one of b\n");
+        }
+    }
+
+    // the explanation is derived from a sequential search
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--stmng")
+        .arg("--explain")
+        .arg("--threads")
+        .arg("2");
+    cmd.assert().success().stdout(
+        "No stable model exists, as each two valued model assigns an unjustified value to one of b\n",
+    );
+
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path()).arg("--stm");
+    cmd.assert().success().stdout("");
+    Ok(())
+}
+
+#[test]
+fn export_cnf() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).ac(a,neg(b)).ac(b,neg(a)).")?;
+    let dir = assert_fs::TempDir::new()?;
+
+    for (lib, formulae) in [("hybrid", false), ("naive", false), ("naive", true)] {
+        let target = dir.child(format!("{lib}_{formulae}.cnf"));
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--export-cnf")
+            .arg(target.path())
+            .arg("--lib")
+            .arg(lib);
+        if formulae {
+            cmd.arg("--cnf-formulae");
+        }
+        cmd.assert().success();
+        target.assert("c semantics TwoValued\nc T(a) 1\nc T(b) 2\np cnf 3 5\n3 0\n-1 -2 0\n1 2 0\n-2 -1 0\n2 1 0\n");
+    }
+
+    let target = dir.child("complete.cnf");
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--export-cnf")
+        .arg(target.path())
+        .arg("--cnf-semantics")
+        .arg("Complete");
+    cmd.assert().success();
+    target.assert(predicate::str::starts_with(
+        "c semantics Complete\nc T(a) 1\nc F(a) 2\nc T(b) 3\nc F(b) 4\np cnf 5 ",
+    ));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path()).arg("--cnf-semantics").arg("Complete");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--export-cnf <EXPORT_CNF>"));
+    Ok(())
+}
+
+#[test]
+fn export_asp() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).ac(a,c(v)).ac(b,neg(a)).")?;
+    let dir = assert_fs::TempDir::new()?;
+
+    for lib in ["hybrid", "naive"] {
+        let target = dir.child(format!("{lib}.lp"));
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--export-asp")
+            .arg(target.path())
+            .arg("--asp-semantics")
+            .arg("Stable")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success();
+        target.assert(predicate::str::starts_with(
+            "% semantics Stable\n% statements and acceptance conditions\ns(\"a\"). ac(\"a\",1).\ns(\"b\"). ac(\"b\",",
+        ));
+        target.assert(predicate::str::contains(
+            "t(r,S) :- t(m,S), ac(S,N), mt(r,N).\n",
+        ));
+    }
+
+    let target = dir.child("complete.lp");
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path()).arg("--export-asp").arg(target.path());
+    cmd.assert().success();
+    target.assert(predicate::str::contains("% semantics Complete\n"));
+    Ok(())
+}
+
+#[test]
+fn export_qbf() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).ac(a,neg(b)).ac(b,neg(a)).")?;
+    let dir = assert_fs::TempDir::new()?;
+
+    for lib in ["hybrid", "naive"] {
+        let target = dir.child(format!("{lib}.qdimacs"));
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--export-qbf")
+            .arg(target.path())
+            .arg("--qbf-statement")
+            .arg("b")
+            .arg("--lib")
+            .arg(lib);
+        cmd.assert().success();
+        target.assert(predicate::str::starts_with(
+            "c task SkepticalPreferred of b\nc T(a) 1\nc F(a) 2\nc T(b) 3\nc F(b) 4\np cnf ",
+        ));
+        target.assert(predicate::str::contains("\na 1 2 3 4 0\ne 5 6 7 8 9 "));
+    }
+
+    let target = dir.child("credulous.qdimacs");
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--export-qbf")
+        .arg(target.path())
+        .arg("--qbf-statement")
+        .arg("a")
+        .arg("--qbf-task")
+        .arg("CredulousPreferred");
+    cmd.assert().success();
+    target.assert(predicate::str::contains("\ne 1 2 3 4 5 "));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--export-qbf")
+        .arg(dir.child("unknown.qdimacs").path())
+        .arg("--qbf-statement")
+        .arg("c");
+    cmd.assert().success();
+    dir.child("unknown.qdimacs")
+        .assert(predicate::path::missing());
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--export-qbf")
+        .arg(dir.child("missing.qdimacs").path());
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--qbf-statement <QBF_STATEMENT>"));
+    Ok(())
+}
+
+#[test]
+fn sat_lib() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,c).")?;
+
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--lib")
+        .arg("sat")
+        .arg("--twoval")
+        .arg("--count")
+        .arg("-q");
+    cmd.assert().success().stdout("two valued: 4\n");
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--lib")
+        .arg("sat")
+        .arg("--grd")
+        .arg("--stm")
+        .arg("-q");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("u(a) u(b) u(c) \n"))
+        .stdout(predicate::str::contains("T(a) F(b) F(c) \n"))
+        .stdout(predicate::str::contains("F(a) T(b) F(c) \n"))
+        .stdout(predicate::str::contains("T(c)").count(0));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path()).arg("--lib").arg("sat").arg("--stmng");
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("supported in sat mode"));
+
+    cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--lib")
+        .arg("sat")
+        .arg("--stm")
+        .arg("--threads")
+        .arg("2");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("T(a) F(b) F(c)"))
+        .stderr(predicate::str::contains(
+            "--threads is not supported in sat mode",
+        ));
+    Ok(())
+}
+
+#[test]
+fn decomposition() -> Result<(), Box<dyn std::error::Error>> {
+    let file = assert_fs::NamedTempFile::new("input_instance.adf")?;
+    file.write_str("s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,c).")?;
+
+    for lib in ["naive", "hybrid"] {
+        let mut cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--lib")
+            .arg(lib)
+            .arg("--com")
+            .arg("--decompose")
+            .arg("--count");
+        cmd.assert().success().stdout("complete: 9\n");
+
+        cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path()).arg("--lib").arg(lib).arg("--prf");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("T(a) F(b) T(c) \n"))
+            .stdout(predicate::str::contains("F(a) T(b) F(c) \n"))
+            .stdout(predicate::str::contains("u(").count(0))
+            .stdout(predicate::str::contains("\n").count(4));
+
+        cmd = Command::cargo_bin("adf-bdd")?;
+        cmd.arg(file.path())
+            .arg("--lib")
+            .arg(lib)
+            .arg("--stm")
+            .arg("--decompose");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("T(a) F(b) F(c) \n"))
+            .stdout(predicate::str::contains("F(a) T(b) F(c) \n"))
+            .stdout(predicate::str::contains("T(c)").count(0));
+    }
+
+    let mut cmd = Command::cargo_bin("adf-bdd")?;
+    cmd.arg(file.path())
+        .arg("--lib")
+        .arg("biodivine")
+        .arg("--prf");
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("not supported in biodivine mode"));
+    Ok(())
+}
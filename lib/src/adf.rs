@@ -3,32 +3,64 @@ This module describes the abstract dialectical framework.
 
  - computing interpretations and models
  - computing fixpoints
+ - revising statements incrementally
 */
 
+pub mod asp;
+pub mod builder;
+pub mod cnf;
+pub mod dependencies;
+pub mod editing;
+pub mod explanation;
 pub mod heuristics;
-use std::cell::RefCell;
-
+pub mod navigation;
+pub mod qbf;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+#[cfg(doc)]
+use crate::nogoods::NoGood;
 use crate::{
     datatypes::{
         adf::{
             PrintDictionary, PrintableInterpretation, ThreeValuedInterpretationsIterator,
             TwoValuedInterpretationsIterator, VarContainer,
         },
+        budget::{take_until_interrupted, Budget, Interrupted},
+        graph::DependencyGraph,
         FacetCounts, ModelCounts, Term, Var,
     },
-    nogoods::{NoGood, NoGoodStore},
-    obdd::Bdd,
+    nogoods::NoGoodStore,
+    obdd::{Bdd, CountOverflow},
     parser::{AdfParser, Formula},
+    sat::Solver,
 };
-use rand::{rngs::StdRng, SeedableRng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
-use self::heuristics::Heuristic;
+use self::{
+    asp::AspSemantics,
+    cnf::{Cnf, CnfError, CnfSemantics},
+    dependencies::{LinkPolarity, StatementGraph, StatementLink, StatementNode},
+    editing::EditError,
+    explanation::{
+        Derivation, ExplainedStableModels, GroundedExplanation, StableConflict, Witness,
+    },
+    heuristics::Heuristic,
+    navigation::{Facet, FacetCount, Navigation, NavigationError, NavigationSemantics},
+    qbf::{Qbf, QbfTask},
+};
 
 #[derive(Serialize, Deserialize, Debug)]
 /// Representation of an ADF, with an ordering and dictionary which relates statements to numbers, a binary decision diagram, and a list of acceptance conditions in [`Term`][crate::datatypes::Term] representation.
 ///
 /// Please note that due to the nature of the underlying reduced and ordered Bdd the concept of a [`Term`][crate::datatypes::Term] represents one (sub) formula as well as truth-values.
+///
+/// An [`Adf`] is [`Send`] and [`Sync`], so one instance can be shared between threads (e.g. in an [`Arc`][std::sync::Arc]) for all queries which only need a shared reference.
+/// Queries which modify the underlying [`Bdd`] (e.g. computing models) can run concurrently on [clones][Clone::clone] of the shared instance.
+/// After [freezing][Adf::freeze] the instance, these clones share its [`Bdd`] read-only and only keep their own nodes and caches as scratch.
 pub struct Adf {
     /// The ordering or the variables in the ADF including a dictionary for the statements
     pub ordering: VarContainer,
@@ -37,7 +69,23 @@ pub struct Adf {
     /// Acceptance Conditions for the ADF
     pub ac: Vec<Term>,
     #[serde(skip, default = "Adf::default_rng")]
-    rng: RefCell<StdRng>,
+    rng: Mutex<StdRng>,
+}
+
+impl Clone for Adf {
+    /// Clones the [`Adf`].
+    /// The random number generator of the clone is seeded by the one of the original, so clones of a [seeded][Adf::seed] [`Adf`] behave reproducibly.
+    fn clone(&self) -> Self {
+        Self {
+            ordering: self.ordering.clone(),
+            bdd: self.bdd.clone(),
+            ac: self.ac.clone(),
+            rng: Mutex::new(
+                StdRng::from_rng(&mut *self.rng.lock().expect("Lock on rng failed"))
+                    .expect("Seeding from a StdRng should not fail"),
+            ),
+        }
+    }
 }
 
 impl Default for Adf {
@@ -89,9 +137,14 @@ impl Adf {
                     new_order,
                     parser.ac_at(insert_order)
                 );
-                let result_term = result.term(&parser.ac_at(insert_order).expect(
-                    "Index should exist, because the data originates from the same parser object",
-                ));
+                let result_term = result.term(
+                    &parser
+                        .ac_at(insert_order)
+                        .expect(
+                            "Index should exist, because the data originates from the same parser object",
+                        )
+                        .as_formula(),
+                );
                 result.ac[*new_order] = result_term;
             });
         log::info!("[Success] instantiated");
@@ -156,13 +209,18 @@ impl Adf {
         result
     }
 
-    fn default_rng() -> RefCell<StdRng> {
-        RefCell::new(StdRng::from_entropy())
+    fn default_rng() -> Mutex<StdRng> {
+        Mutex::new(StdRng::from_entropy())
     }
 
     /// Sets a cryptographiclly strong seed
     pub fn seed(&mut self, seed: [u8; 32]) {
-        self.rng = RefCell::new(StdRng::from_seed(seed))
+        self.rng = Mutex::new(StdRng::from_seed(seed))
+    }
+
+    /// [Freezes][Bdd::freeze] the underlying [`Bdd`], so [clones][Clone::clone] of the [`Adf`] share its nodes and caches instead of copying them.
+    pub fn freeze(&mut self) {
+        self.bdd.freeze();
     }
 
     /// Instantiates a new ADF, based on a [biodivine adf][crate::adfbiodivine::Adf].
@@ -171,66 +229,203 @@ impl Adf {
     }
 
     fn term(&mut self, formula: &Formula) -> Term {
-        match formula {
+        formula.fold(|formula, terms: Vec<Term>| match formula {
             Formula::Bot => Bdd::constant(false),
             Formula::Top => Bdd::constant(true),
             Formula::Atom(val) => {
                 let t1 = self.ordering.variable(val).expect("Variable should exist, because the ordering has been filled by the same parser as the input formula comes from");
                 self.bdd.variable(t1)
             }
-            Formula::Not(val) => {
-                let t1 = self.term(val);
-                self.bdd.not(t1)
-            }
-            Formula::And(val1, val2) => {
-                let t1 = self.term(val1);
-                let t2 = self.term(val2);
-                self.bdd.and(t1, t2)
-            }
-            Formula::Or(val1, val2) => {
-                let t1 = self.term(val1);
-                let t2 = self.term(val2);
-                self.bdd.or(t1, t2)
-            }
-            Formula::Iff(val1, val2) => {
-                let t1 = self.term(val1);
-                let t2 = self.term(val2);
-                self.bdd.iff(t1, t2)
-            }
-            Formula::Xor(val1, val2) => {
-                let t1 = self.term(val1);
-                let t2 = self.term(val2);
-                self.bdd.xor(t1, t2)
-            }
-            Formula::Imp(val1, val2) => {
-                let t1 = self.term(val1);
-                let t2 = self.term(val2);
-                self.bdd.imp(t1, t2)
-            }
+            Formula::Not(_) => self.bdd.not(terms[0]),
+            Formula::And(..) => self.bdd.and(terms[0], terms[1]),
+            Formula::Or(..) => self.bdd.or(terms[0], terms[1]),
+            Formula::Iff(..) => self.bdd.iff(terms[0], terms[1]),
+            Formula::Xor(..) => self.bdd.xor(terms[0], terms[1]),
+            Formula::Imp(..) => self.bdd.imp(terms[0], terms[1]),
+            Formula::AtLeast(k, _) => self.bdd.at_least(&terms, *k),
+            Formula::AtMost(k, _) => self.bdd.at_most(&terms, *k),
+            Formula::Exactly(k, _) => self.bdd.exactly(&terms, *k),
+        })
+    }
+
+    /// Adds a new statement with the given acceptance condition and returns its [variable][Var].
+    ///
+    /// The statement is ordered after all existing statements, so the existing nodes of the [`Bdd`] stay valid.
+    /// The acceptance condition may refer to the new statement itself.
+    pub fn add_statement(&mut self, name: &str, formula: &Formula) -> Result<Var, EditError> {
+        if self.ordering.variable(name).is_some() {
+            return Err(EditError::DuplicateStatement(name.to_string()));
+        }
+        self.check_atoms(formula, Some(name))?;
+        self.ordering = self.ordering.detached();
+        let var = self.ordering.push(name);
+        log::debug!("adding statement {name} as {var}");
+        self.bdd.variable(var);
+        self.ac.push(Term::BOT);
+        self.ac[var.value()] = self.term(formula);
+        Ok(var)
+    }
+
+    /// Replaces the acceptance condition of the given statement.
+    pub fn set_acceptance_condition(
+        &mut self,
+        name: &str,
+        formula: &Formula,
+    ) -> Result<(), EditError> {
+        let var = self
+            .ordering
+            .variable(name)
+            .ok_or_else(|| EditError::UnknownStatement(name.to_string()))?;
+        self.check_atoms(formula, None)?;
+        log::debug!("replacing acceptance condition of {name}");
+        self.ac[var.value()] = self.term(formula);
+        Ok(())
+    }
+
+    /// Removes the given statement.
+    ///
+    /// The statement must not occur in the acceptance condition of any other statement.
+    /// Each later statement moves one position to the front, so the acceptance conditions are relabelled within the existing [`Bdd`].
+    pub fn remove_statement(&mut self, name: &str) -> Result<(), EditError> {
+        let var = self
+            .ordering
+            .variable(name)
+            .ok_or_else(|| EditError::UnknownStatement(name.to_string()))?;
+        let referenced_by = self
+            .ac
+            .iter()
+            .enumerate()
+            .filter(|&(idx, &ac)| {
+                idx != var.value() && self.bdd.var_dependencies(ac).contains(&var)
+            })
+            .map(|(idx, _)| {
+                self.ordering
+                    .name(Var(idx))
+                    .expect("Each statement has a name")
+            })
+            .collect::<Vec<_>>();
+        if !referenced_by.is_empty() {
+            return Err(EditError::StillReferenced {
+                statement: name.to_string(),
+                referenced_by,
+            });
+        }
+        log::debug!("removing statement {name}");
+        self.ac.remove(var.value());
+        self.ac = self.bdd.remove_variable(&self.ac, var);
+        self.ordering = self.ordering.detached();
+        self.ordering.remove(var);
+        Ok(())
+    }
+
+    fn check_atoms(&self, formula: &Formula, new_statement: Option<&str>) -> Result<(), EditError> {
+        match formula
+            .atoms()
+            .into_iter()
+            .find(|&atom| Some(atom) != new_statement && self.ordering.variable(atom).is_none())
+        {
+            Some(atom) => Err(EditError::UnknownStatement(atom.to_string())),
+            None => Ok(()),
         }
     }
 
     /// Computes the grounded extension and returns it as a list.
     pub fn grounded(&mut self) -> Vec<Term> {
+        self.grounded_budgeted(&Budget::default())
+            .expect("An unlimited budget cannot be exhausted")
+    }
+
+    /// Computes the grounded extension within the given [Budget] and returns it as a list.
+    /// Returns the reason of the [interruption][Interrupted] if the budget has been exhausted.
+    pub fn grounded_budgeted(&mut self, budget: &Budget) -> Result<Vec<Term>, Interrupted> {
         log::info!("[Start] grounded");
         let ac = &self.ac.clone();
-        let result = self.grounded_internal(ac);
+        let result = self.grounded_internal_traced(ac, None, budget)?;
         log::info!("[Done] grounded");
+        Ok(result)
+    }
+
+    /// Computes the grounded model together with an [explanation][GroundedExplanation] of each value.
+    pub fn explain_grounded(&mut self) -> GroundedExplanation {
+        let ac = &self.ac.clone();
+        let mut derivations = Vec::new();
+        let grounded = self
+            .grounded_internal_traced(ac, Some(&mut derivations), &Budget::default())
+            .expect("An unlimited budget cannot be exhausted");
+        let witnesses = grounded
+            .iter()
+            .enumerate()
+            .filter(|(_, term)| !term.is_truth_value())
+            .map(|(idx, &restricted)| Witness {
+                statement: Var(idx),
+                accepting: self.completion(&grounded, restricted, Term::TOP),
+                rejecting: self.completion(&grounded, restricted, Term::BOT),
+            })
+            .collect();
+        GroundedExplanation {
+            grounded,
+            derivations,
+            witnesses,
+        }
+    }
+
+    /// Completes the given interpretation along a path of the given roBDD to the given leaf, where unassigned statements are set to false.
+    fn completion(&self, interpretation: &[Term], tree: Term, leaf: Term) -> Vec<Term> {
+        let mut result = interpretation
+            .iter()
+            .map(|term| Term::from(term.is_truth_value() && term.is_true()))
+            .collect::<Vec<_>>();
+        for (var, value) in self
+            .bdd
+            .path_to(tree, leaf)
+            .expect("A non-constant roBDD reaches both leaves")
+        {
+            result[var.value()] = Term::from(value);
+        }
         result
     }
 
     fn grounded_internal(&mut self, interpretation: &[Term]) -> Vec<Term> {
+        self.grounded_internal_traced(interpretation, None, &Budget::default())
+            .expect("An unlimited budget cannot be exhausted")
+    }
+
+    /// Computes the grounded model within the given [Budget] and records the [derivation][Derivation] of each decided statement, if a trace is given.
+    fn grounded_internal_traced(
+        &mut self,
+        interpretation: &[Term],
+        mut trace: Option<&mut Vec<Derivation>>,
+        budget: &Budget,
+    ) -> Result<Vec<Term>, Interrupted> {
         let mut t_vals: usize = interpretation
             .iter()
             .filter(|elem| elem.is_truth_value())
             .count();
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.extend(
+                interpretation
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, term)| term.is_truth_value())
+                    .map(|(idx, &value)| Derivation {
+                        statement: Var(idx),
+                        value,
+                        iteration: 0,
+                        reasons: Vec::new(),
+                    }),
+            );
+        }
         let mut new_interpretation: Vec<Term> = interpretation.into();
+        let mut iteration = 0;
         loop {
+            budget.check(self.bdd.nodes.len())?;
+            iteration += 1;
             let curr_interpretation = new_interpretation.clone();
             let old_t_vals = t_vals;
-            for ac in new_interpretation
+            for (idx, ac) in new_interpretation
                 .iter_mut()
-                .filter(|term| !term.is_truth_value())
+                .enumerate()
+                .filter(|(_, term)| !term.is_truth_value())
             {
                 *ac = curr_interpretation
                     .iter()
@@ -244,6 +439,21 @@ impl Adf {
                     });
                 if ac.is_truth_value() {
                     t_vals += 1;
+                    if let Some(trace) = trace.as_deref_mut() {
+                        let mut reasons = self
+                            .bdd
+                            .var_dependencies(interpretation[idx])
+                            .into_iter()
+                            .filter(|var| curr_interpretation[var.value()].is_truth_value())
+                            .collect::<Vec<_>>();
+                        reasons.sort();
+                        trace.push(Derivation {
+                            statement: Var(idx),
+                            value: *ac,
+                            iteration,
+                            reasons,
+                        });
+                    }
                 }
             }
             log::debug!(
@@ -256,7 +466,7 @@ impl Adf {
                 break;
             }
         }
-        new_interpretation
+        Ok(new_interpretation)
     }
 
     /// Computes the stable models.
@@ -265,36 +475,239 @@ impl Adf {
     where
         'a: 'c,
     {
-        let grounded = self.grounded();
-        TwoValuedInterpretationsIterator::new(&grounded)
-            .map(|interpretation| {
-                let mut interpr = self.ac.clone();
-                for ac in interpr.iter_mut() {
-                    *ac = interpretation
-                        .iter()
-                        .enumerate()
-                        .fold(*ac, |acc, (var, term)| {
-                            if term.is_truth_value() && !term.is_true() {
-                                self.bdd.restrict(acc, Var(var), false)
-                            } else {
-                                acc
-                            }
-                        });
+        self.stable_budgeted(Budget::default())
+            .map(|model| model.expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the stable models within the given [Budget].
+    /// Returns an Iterator which contains all stable models, found before the budget is exhausted.
+    /// If the budget is exhausted, the last element is the reason of the [interruption][Interrupted].
+    pub fn stable_budgeted<'a, 'c>(
+        &'a mut self,
+        budget: Budget,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>> + 'c
+    where
+        'a: 'c,
+    {
+        let grounded = self.grounded_budgeted(&budget);
+        take_until_interrupted(
+            Self::candidates(grounded, |grounded| {
+                TwoValuedInterpretationsIterator::new(&grounded)
+            })
+            .filter_map(move |candidate| {
+                let interpretation = match candidate {
+                    Ok(interpretation) => interpretation,
+                    Err(reason) => return Some(Err(reason)),
+                };
+                if let Err(reason) = budget.check(self.bdd.nodes.len()) {
+                    return Some(Err(reason));
+                }
+                self.is_stable(&interpretation)
+                    .then_some(Ok(interpretation))
+            }),
+        )
+    }
+
+    /// Counts the stable models, but stops after the given limit.
+    /// The models are enumerated like in [stable][Adf::stable], without collecting them.
+    pub fn stable_count(&mut self, limit: Option<usize>) -> usize {
+        self.stable().take(limit.unwrap_or(usize::MAX)).count()
+    }
+
+    /// Enumerates the candidates, which are built from the result of a budgeted computation (e.g. the grounded model), or yields the reason of its [interruption][Interrupted].
+    fn candidates<G, I>(
+        result: Result<G, Interrupted>,
+        candidates: impl FnOnce(G) -> I,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>>
+    where
+        I: Iterator<Item = Vec<Term>>,
+    {
+        let (candidates, interruption) = match result {
+            Ok(result) => (Some(candidates(result)), None),
+            Err(reason) => (None, Some(reason)),
+        };
+        interruption
+            .into_iter()
+            .map(Err)
+            .chain(candidates.into_iter().flatten().map(Ok))
+    }
+
+    /// Checks whether the given two valued interpretation is a stable model, i.e. whether it coincides with the grounded model of its reduct.
+    fn is_stable(&mut self, interpretation: &[Term]) -> bool {
+        let mut interpr = self.ac.clone();
+        for ac in interpr.iter_mut() {
+            *ac = interpretation
+                .iter()
+                .enumerate()
+                .fold(*ac, |acc, (var, term)| {
+                    if term.is_truth_value() && !term.is_true() {
+                        self.bdd.restrict(acc, Var(var), false)
+                    } else {
+                        acc
+                    }
+                });
+        }
+        let grounded_check = self.grounded_internal(&interpr);
+        log::debug!(
+            "grounded candidate\n{:?}\n{:?}",
+            interpretation,
+            grounded_check
+        );
+        interpretation
+            .iter()
+            .zip(grounded_check.iter())
+            .all(|(it, gr)| it.compare_inf(gr))
+    }
+
+    /// Computes the two valued models with the incremental [SAT solver][Solver].
+    /// Returns an Iterator which contains all two valued models, where each model is excluded by a blocking clause once it has been found.
+    pub fn two_valued_sat<'a, 'c>(&'a mut self) -> impl Iterator<Item = Vec<Term>> + 'c
+    where
+        'a: 'c,
+    {
+        let cnf = self.to_cnf(CnfSemantics::TwoValued);
+        let mut solver = Solver::from_cnf(&cnf);
+        std::iter::from_fn(move || Self::next_sat_model(&cnf, &mut solver))
+    }
+
+    /// Computes the stable models with the incremental [SAT solver][Solver].
+    /// Returns an Iterator which contains all stable models.
+    ///
+    /// The solver enumerates the [stable candidates][CnfSemantics::StableCandidate], whose stability is checked lazily, i.e. only once a candidate has been found.
+    /// Each candidate is excluded by a clause over its false statements and the true statements, which are not justified by its reduct, as the justified ones are true in each model with these false statements.
+    /// This clause is shorter than one over all statements, so the solver rules out partial assignments earlier.
+    pub fn stable_sat<'a, 'c>(&'a mut self) -> impl Iterator<Item = Vec<Term>> + 'c
+    where
+        'a: 'c,
+    {
+        let cnf = self.to_cnf(CnfSemantics::StableCandidate);
+        let mut solver = Solver::from_cnf(&cnf);
+        std::iter::from_fn(move || loop {
+            let candidate = cnf.interpretation(&solver.solve()?);
+            let mut unjustified = vec![false; candidate.len()];
+            for var in self.unjustified(&candidate) {
+                unjustified[var.value()] = true;
+            }
+            // each two valued model, which assigns false to the same statements, derives the justified true statements as well
+            // so the clause only needs the false statements and the unjustified true ones
+            let blocking = candidate
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, term)| {
+                    if !term.is_true() {
+                        Some(cnf.statement_true(Var(idx)))
+                    } else if unjustified[idx] {
+                        Some(cnf.statement_false(Var(idx)))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            solver.add_clause(&blocking);
+            log::debug!(
+                "stable candidate: {:?}, blocking clause {:?}",
+                candidate,
+                blocking
+            );
+            if !unjustified.contains(&true) {
+                return Some(candidate);
+            }
+        })
+    }
+
+    /// Returns the next model of the statements and excludes it from the further search.
+    fn next_sat_model(cnf: &Cnf, solver: &mut Solver) -> Option<Vec<Term>> {
+        let model = cnf.interpretation(&solver.solve()?);
+        let blocking = model
+            .iter()
+            .enumerate()
+            .map(|(idx, term)| {
+                if term.is_true() {
+                    cnf.statement_false(Var(idx))
+                } else {
+                    cnf.statement_true(Var(idx))
                 }
-                let grounded_check = self.grounded_internal(&interpr);
-                log::debug!(
-                    "grounded candidate\n{:?}\n{:?}",
-                    interpretation,
-                    grounded_check
-                );
-                (interpretation, grounded_check)
             })
-            .filter(|(int, grd)| {
-                int.iter()
-                    .zip(grd.iter())
-                    .all(|(it, gr)| it.compare_inf(gr))
+            .collect::<Vec<_>>();
+        solver.add_clause(&blocking);
+        log::debug!("sat model: {:?}", model);
+        Some(model)
+    }
+
+    /// Draws `n` two valued models uniformly at random (with replacement), using the given random number generator.
+    ///
+    /// The models are sampled from a single-formula representation of the ADF, i.e. the conjunction of `s <-> ac(s)` for each statement `s`.
+    /// Returns an empty vector if there is no two valued model, and [CountOverflow] if the models cannot be counted exactly.
+    pub fn sample_two_valued<R: Rng + ?Sized>(
+        &mut self,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<Term>>, CountOverflow> {
+        let representation = self.two_valued_representation();
+        self.bdd
+            .sample_models(representation, self.ac.len(), n, rng)
+    }
+
+    /// Counts the two valued models symbolically on a single-formula representation of the ADF, without enumerating them.
+    /// Returns [CountOverflow] if the number of models does not fit into an [u128].
+    pub fn two_valued_count(&mut self) -> Result<u128, CountOverflow> {
+        let representation = self.two_valued_representation();
+        self.bdd.exact_models(representation, self.ac.len())
+    }
+
+    /// Draws `n` stable models uniformly at random (with replacement), using the random number generator of the ADF (see [seed][Adf::seed]).
+    ///
+    /// Two valued models are sampled from a single-formula representation of the ADF.
+    /// Each sampled model, which is not stable, is excluded from the representation, so the computation terminates even if only few (or no) stable models exist.
+    /// Returns an empty vector if there is no stable model, and [CountOverflow] if the models cannot be counted exactly.
+    pub fn sample_stable(&mut self, n: usize) -> Result<Vec<Vec<Term>>, CountOverflow> {
+        let mut rng = StdRng::from_rng(&mut *self.rng.lock().expect("Lock on rng failed"))
+            .expect("StdRng should be seedable by StdRng");
+        let mut representation = self.two_valued_representation();
+        let mut result = Vec::with_capacity(n);
+        while result.len() < n {
+            let candidate = match self
+                .bdd
+                .sample_models(representation, self.ac.len(), 1, &mut rng)?
+                .pop()
+            {
+                Some(candidate) => candidate,
+                None => break,
+            };
+            if self.stability_check(&candidate) {
+                result.push(candidate);
+            } else {
+                log::trace!("excluding non-stable candidate {:?}", candidate);
+                let minterm = self.minterm(&candidate);
+                let excluded = self.bdd.not(minterm);
+                representation = self.bdd.and(representation, excluded);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Builds the conjunction of `s <-> ac(s)` for each statement `s`, whose models are the two valued models of the ADF.
+    fn two_valued_representation(&mut self) -> Term {
+        (0..self.ac.len()).fold(Term::TOP, |acc, idx| {
+            let var = self.bdd.variable(Var(idx));
+            let equality = self.bdd.iff(var, self.ac[idx]);
+            self.bdd.and(acc, equality)
+        })
+    }
+
+    /// Builds the roBDD, whose only model is the given two valued interpretation.
+    fn minterm(&mut self, interpretation: &[Term]) -> Term {
+        interpretation
+            .iter()
+            .enumerate()
+            .rev()
+            .fold(Term::TOP, |acc, (idx, term)| {
+                if term.is_true() {
+                    self.bdd.node(Var(idx), Term::BOT, acc)
+                } else {
+                    self.bdd.node(Var(idx), acc, Term::BOT)
+                }
             })
-            .map(|(int, _grd)| int)
     }
 
     /// Computes the stable models.
@@ -333,10 +746,34 @@ impl Adf {
     where
         'a: 'c,
     {
-        let grounded = self.grounded();
-        TwoValuedInterpretationsIterator::new(&grounded)
-            .map(|interpretation| {
-                if interpretation.iter().enumerate().all(|(ac_idx, it)| {
+        self.stable_with_prefilter_budgeted(Budget::default())
+            .map(|model| model.expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the stable models within the given [Budget], where candidates are discarded early if they are not a model of the acceptance conditions.
+    /// Returns an Iterator which contains all stable models, found before the budget is exhausted.
+    /// If the budget is exhausted, the last element is the reason of the [interruption][Interrupted].
+    pub fn stable_with_prefilter_budgeted<'a, 'c>(
+        &'a mut self,
+        budget: Budget,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>> + 'c
+    where
+        'a: 'c,
+    {
+        let grounded = self.grounded_budgeted(&budget);
+        take_until_interrupted(
+            Self::candidates(grounded, |grounded| {
+                TwoValuedInterpretationsIterator::new(&grounded)
+            })
+            .filter_map(move |candidate| {
+                let interpretation = match candidate {
+                    Ok(interpretation) => interpretation,
+                    Err(reason) => return Some(Err(reason)),
+                };
+                if let Err(reason) = budget.check(self.bdd.nodes.len()) {
+                    return Some(Err(reason));
+                }
+                let is_model = interpretation.iter().enumerate().all(|(ac_idx, it)| {
                     it.compare_inf(&interpretation.iter().enumerate().fold(
                         self.ac[ac_idx],
                         |acc, (var, term)| {
@@ -347,37 +784,10 @@ impl Adf {
                             }
                         },
                     ))
-                }) {
-                    let mut interpr = self.ac.clone();
-                    for ac in interpr.iter_mut() {
-                        *ac = interpretation
-                            .iter()
-                            .enumerate()
-                            .fold(*ac, |acc, (var, term)| {
-                                if term.is_truth_value() && !term.is_true() {
-                                    self.bdd.restrict(acc, Var(var), false)
-                                } else {
-                                    acc
-                                }
-                            });
-                    }
-                    let grounded_check = self.grounded_internal(&interpr);
-                    log::debug!(
-                        "grounded candidate\n{:?}\n{:?}",
-                        interpretation,
-                        grounded_check
-                    );
-                    (interpretation, grounded_check)
-                } else {
-                    (vec![Term::BOT], vec![Term::TOP])
-                }
-            })
-            .filter(|(int, grd)| {
-                int.iter()
-                    .zip(grd.iter())
-                    .all(|(it, gr)| it.compare_inf(gr))
-            })
-            .map(|(int, _grd)| int)
+                });
+                (is_model && self.is_stable(&interpretation)).then_some(Ok(interpretation))
+            }),
+        )
     }
 
     /// Computes the stable models.
@@ -389,11 +799,21 @@ impl Adf {
     where
         'a: 'c,
     {
-        log::debug!("[Start] stable count optimisation");
-        let grounded = self.grounded();
-        self.two_val_model_counts(&grounded, Self::heu_max_imp_min_nacyc_impact_min_paths)
-            .into_iter()
-            .filter(|int| self.stability_check(int))
+        self.stable_count_optimisation_heu_a_budgeted(Budget::default())
+            .map(|model| model.expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the stable models within the given [Budget], using the heuristic of [stable_count_optimisation_heu_a][Adf::stable_count_optimisation_heu_a].
+    /// Returns an iterator which contains all stable models, found before the budget is exhausted.
+    /// If the budget is exhausted, the last element is the reason of the [interruption][Interrupted].
+    pub fn stable_count_optimisation_heu_a_budgeted<'a, 'c>(
+        &'a mut self,
+        budget: Budget,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>> + 'c
+    where
+        'a: 'c,
+    {
+        self.stable_count_optimisation(Self::heu_max_imp_min_nacyc_impact_min_paths, budget)
     }
 
     /// Computes the stable models.
@@ -404,15 +824,58 @@ impl Adf {
     ) -> impl Iterator<Item = Vec<Term>> + 'c
     where
         'a: 'c,
+    {
+        self.stable_count_optimisation_heu_b_budgeted(Budget::default())
+            .map(|model| model.expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the stable models within the given [Budget], using the heuristic of [stable_count_optimisation_heu_b][Adf::stable_count_optimisation_heu_b].
+    /// Returns an iterator which contains all stable models, found before the budget is exhausted.
+    /// If the budget is exhausted, the last element is the reason of the [interruption][Interrupted].
+    pub fn stable_count_optimisation_heu_b_budgeted<'a, 'c>(
+        &'a mut self,
+        budget: Budget,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>> + 'c
+    where
+        'a: 'c,
+    {
+        self.stable_count_optimisation(Self::heu_min_paths_max_imp, budget)
+    }
+
+    fn stable_count_optimisation<'a, 'c, H>(
+        &'a mut self,
+        heuristic: H,
+        budget: Budget,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>> + 'c
+    where
+        'a: 'c,
+        H: Fn(&Self, (Var, Term), (Var, Term), &[Term]) -> std::cmp::Ordering + Copy,
     {
         log::debug!("[Start] stable count optimisation");
-        let grounded = self.grounded();
-        self.two_val_model_counts(&grounded, Self::heu_min_paths_max_imp)
-            .into_iter()
-            .filter(|int| self.stability_check(int))
+        let candidates = self
+            .grounded_budgeted(&budget)
+            .and_then(|grounded| self.two_val_model_counts(&grounded, heuristic, &budget));
+        take_until_interrupted(Self::candidates(candidates, Vec::into_iter).filter_map(
+            move |candidate| {
+                let interpretation = match candidate {
+                    Ok(interpretation) => interpretation,
+                    Err(reason) => return Some(Err(reason)),
+                };
+                if let Err(reason) = budget.check(self.bdd.nodes.len()) {
+                    return Some(Err(reason));
+                }
+                self.stability_check(&interpretation)
+                    .then_some(Ok(interpretation))
+            },
+        ))
     }
 
     fn stability_check(&mut self, interpretation: &[Term]) -> bool {
+        self.unjustified(interpretation).is_empty()
+    }
+
+    /// Returns the statements, whose value in the given interpretation differs from the grounded model of its reduct.
+    fn unjustified(&mut self, interpretation: &[Term]) -> Vec<Var> {
         let mut new_int = self.ac.clone();
         for ac in new_int.iter_mut() {
             *ac = interpretation
@@ -428,23 +891,80 @@ impl Adf {
         }
 
         let grd = self.grounded_internal(&new_int);
-        for (idx, grd) in grd.iter().enumerate() {
-            if !grd.compare_inf(&interpretation[idx]) {
-                return false;
+        grd.iter()
+            .enumerate()
+            .filter(|(idx, grd)| !grd.compare_inf(&interpretation[*idx]))
+            .map(|(idx, _)| Var(idx))
+            .collect()
+    }
+
+    /// Explains why the ADF has no stable model.
+    /// Returns [None] if a stable model exists.
+    ///
+    /// The nogood-learner rejects each two valued model, as some statements are not justified by the grounded model of its reduct.
+    /// The returned core is an irreducible set of statements, which contains at least one of them for each two valued model.
+    /// If there is no two valued model at all, the core is an irreducible set of statements, whose acceptance conditions contradict each other.
+    pub fn explain_no_stable(&mut self) -> Option<StableConflict> {
+        self.stable_nogood_explained(Heuristic::default())
+            .explanation()
+    }
+
+    /// Computes the stable models like [stable_nogood][Adf::stable_nogood] with the given heuristic, but records the conflicts of the learner.
+    /// If no stable model exists, the explanation is derived from them once the search is exhausted (see [explain_no_stable][Adf::explain_no_stable]).
+    pub fn stable_nogood_explained<'a>(
+        &'a mut self,
+        heuristic: Heuristic<'a>,
+    ) -> ExplainedStableModels<'a> {
+        ExplainedStableModels::new(self, heuristic)
+    }
+
+    /// Computes an irreducible set of statements, such that no interpretation agrees with all of their acceptance conditions.
+    ///
+    /// The statements are removed one after another, as long as the remaining ones still contradict each other.
+    /// The conjunction of the kept statements grows incrementally, and is combined with the precomputed conjunction of the untested ones.
+    fn inconsistent_core(&mut self) -> Vec<Var> {
+        let equalities = (0..self.ac.len())
+            .map(|idx| {
+                let variable = self.bdd.variable(Var(idx));
+                self.bdd.iff(variable, self.ac[idx])
+            })
+            .collect::<Vec<_>>();
+        // suffixes[idx] is the conjunction of the equalities of all statements from idx on
+        let mut suffixes = vec![Term::TOP; equalities.len() + 1];
+        for idx in (0..equalities.len()).rev() {
+            suffixes[idx] = self.bdd.and(equalities[idx], suffixes[idx + 1]);
+        }
+        let mut kept = Term::TOP;
+        let mut core = Vec::new();
+        for (idx, &equality) in equalities.iter().enumerate() {
+            if self.bdd.and(kept, suffixes[idx + 1]) != Term::BOT {
+                kept = self.bdd.and(kept, equality);
+                core.push(Var(idx));
             }
         }
-        true
+        core
     }
 
     fn is_two_valued(&self, interpretation: &[Term]) -> bool {
         interpretation.iter().all(|t| t.is_truth_value())
     }
 
-    fn two_val_model_counts<H>(&mut self, interpr: &[Term], heuristic: H) -> Vec<Vec<Term>>
+    fn two_val_model_counts<H>(
+        &mut self,
+        interpr: &[Term],
+        heuristic: H,
+        budget: &Budget,
+    ) -> Result<Vec<Vec<Term>>, Interrupted>
     where
         H: Fn(&Self, (Var, Term), (Var, Term), &[Term]) -> std::cmp::Ordering + Copy,
     {
-        self.two_val_model_counts_logic(interpr, &vec![Term::UND; interpr.len()], 0, heuristic)
+        self.two_val_model_counts_logic(
+            interpr,
+            &vec![Term::UND; interpr.len()],
+            0,
+            heuristic,
+            budget,
+        )
     }
 
     fn heu_max_imp_min_nacyc_impact_min_paths(
@@ -501,10 +1021,12 @@ impl Adf {
         will_be: &[Term],
         depth: usize,
         heuristic: H,
-    ) -> Vec<Vec<Term>>
+        budget: &Budget,
+    ) -> Result<Vec<Vec<Term>>, Interrupted>
     where
         H: Fn(&Self, (Var, Term), (Var, Term), &[Term]) -> std::cmp::Ordering + Copy,
     {
+        budget.check(self.bdd.nodes.len())?;
         log::debug!("two_val_model_recursion_depth: {}/{}", depth, interpr.len());
         if let Some((idx, ac)) = interpr
             .iter()
@@ -520,6 +1042,7 @@ impl Adf {
             })
         {
             let mut result = Vec::new();
+            let mut interruption = None;
             let check_models = !self.bdd.paths(*ac, true).more_models();
             log::trace!(
                 "Identified Var({}) with ac {:?} to be {}",
@@ -556,16 +1079,26 @@ impl Adf {
                         new_int[idx] = if check_models { Term::TOP } else { Term::BOT };
                         let upd_int = self.update_interpretation_fixpoint(&new_int);
                         if self.check_consistency(&upd_int, will_be) {
-                            result.append(&mut self.two_val_model_counts_logic(
+                            match self.two_val_model_counts_logic(
                                 &upd_int,
                                 will_be,
                                 depth + 1,
                                 heuristic,
-                            ));
+                                budget,
+                            ) {
+                                Ok(mut models) => result.append(&mut models),
+                                Err(reason) => {
+                                    interruption = Some(reason);
+                                    return Err(());
+                                }
+                            }
                         }
                     }
                     res
                 });
+            if let Some(reason) = interruption {
+                return Err(reason);
+            }
             log::trace!("results found so far:{}", result.len());
             // checked one alternative, we can now conclude that only the other option may work
             log::debug!("checked one alternative, concluding the other value");
@@ -586,10 +1119,11 @@ impl Adf {
                         &must_be_new,
                         depth + 1,
                         heuristic,
-                    ));
+                        budget,
+                    )?);
                 }
             }
-            result
+            Ok(result)
         } else {
             // filter has created empty iterator
             let concluded = interpr
@@ -606,9 +1140,9 @@ impl Adf {
             let ac = self.ac.clone();
             let result = self.apply_interpretation(&ac, &concluded);
             if self.check_consistency(&result, &concluded) {
-                vec![result]
+                Ok(vec![result])
             } else {
-                vec![interpr.to_vec()]
+                Ok(vec![interpr.to_vec()])
             }
         }
     }
@@ -625,26 +1159,6 @@ impl Adf {
         }
     }
 
-    /// Constructs the fixpoint of the given interpretation with respect to the ADF.
-    /// sets _update_ to [`true`] if the value has been updated and to [`false`] otherwise.
-    fn update_interpretation_fixpoint_upd(
-        &mut self,
-        interpretation: &[Term],
-        update: &mut bool,
-    ) -> Vec<Term> {
-        let mut cur_int = interpretation.to_vec();
-        *update = false;
-        loop {
-            let new_int = self.update_interpretation(interpretation);
-            if cur_int == new_int {
-                return cur_int;
-            } else {
-                cur_int = new_int;
-                *update = true;
-            }
-        }
-    }
-
     fn update_interpretation(&mut self, interpretation: &[Term]) -> Vec<Term> {
         self.apply_interpretation(interpretation, interpretation)
     }
@@ -679,23 +1193,153 @@ impl Adf {
     where
         'a: 'c,
     {
-        let grounded = self.grounded();
+        self.complete_budgeted(Budget::default())
+            .map(|model| model.expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the complete models within the given [Budget].
+    /// Returns an Iterator which contains all complete models, found before the budget is exhausted.
+    /// If the budget is exhausted, the last element is the reason of the [interruption][Interrupted].
+    pub fn complete_budgeted<'a, 'c>(
+        &'a mut self,
+        budget: Budget,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>> + 'c
+    where
+        'a: 'c,
+    {
+        let grounded = self.grounded_budgeted(&budget);
         let ac = self.ac.clone();
-        ThreeValuedInterpretationsIterator::new(&grounded).filter(move |interpretation| {
-            interpretation.iter().enumerate().all(|(ac_idx, it)| {
-                log::trace!("idx [{}], term: {}", ac_idx, it);
-                it.compare_inf(&interpretation.iter().enumerate().fold(
-                    ac[ac_idx],
-                    |acc, (var, term)| {
-                        if term.is_truth_value() {
-                            self.bdd.restrict(acc, Var(var), term.is_true())
+        take_until_interrupted(
+            Self::candidates(grounded, |grounded| {
+                ThreeValuedInterpretationsIterator::new(&grounded)
+            })
+            .filter_map(move |candidate| {
+                let interpretation = match candidate {
+                    Ok(interpretation) => interpretation,
+                    Err(reason) => return Some(Err(reason)),
+                };
+                if let Err(reason) = budget.check(self.bdd.nodes.len()) {
+                    return Some(Err(reason));
+                }
+                interpretation
+                    .iter()
+                    .enumerate()
+                    .all(|(ac_idx, it)| {
+                        log::trace!("idx [{}], term: {}", ac_idx, it);
+                        it.compare_inf(&interpretation.iter().enumerate().fold(
+                            ac[ac_idx],
+                            |acc, (var, term)| {
+                                if term.is_truth_value() {
+                                    self.bdd.restrict(acc, Var(var), term.is_true())
+                                } else {
+                                    acc
+                                }
+                            },
+                        ))
+                    })
+                    .then_some(Ok(interpretation))
+            }),
+        )
+    }
+
+    /// Counts the complete models, but stops after the given limit.
+    /// The models are enumerated like in [complete][Adf::complete], without collecting them.
+    pub fn complete_count(&mut self, limit: Option<usize>) -> usize {
+        self.complete().take(limit.unwrap_or(usize::MAX)).count()
+    }
+
+    /// Computes the complete models by decomposing the [dependency graph][Self::dependency_graph] into its strongly connected components.
+    /// The components are solved in topological order, where the values of earlier components are propagated into the acceptance conditions of later ones.
+    /// Returns an Iterator which contains all complete models, which are computed lazily.
+    pub fn complete_decomposed<'a, 'c>(&'a mut self) -> impl Iterator<Item = Vec<Term>> + 'c
+    where
+        'a: 'c,
+    {
+        log::info!("[Start] complete (decomposed)");
+        Decomposition::new(self, DecomposedSemantics::Complete)
+    }
+
+    /// Computes the preferred models, i.e. the complete models which are maximal with respect to the information ordering, by decomposing the [dependency graph][Self::dependency_graph] into its strongly connected components.
+    /// A complete model is preferred if and only if the values of each component are maximal among the complete values of this component, when the values of all earlier components are fixed.
+    /// Returns an Iterator which contains all preferred models, which are computed lazily.
+    pub fn preferred_decomposed<'a, 'c>(&'a mut self) -> impl Iterator<Item = Vec<Term>> + 'c
+    where
+        'a: 'c,
+    {
+        log::info!("[Start] preferred (decomposed)");
+        Decomposition::new(self, DecomposedSemantics::Preferred)
+    }
+
+    /// Computes the stable models by decomposing the [dependency graph][Self::dependency_graph] into its strongly connected components.
+    /// The components are solved in topological order, where the values of earlier components are propagated into the acceptance conditions of later ones.
+    /// Returns an Iterator which contains all stable models, which are computed lazily.
+    pub fn stable_decomposed<'a, 'c>(&'a mut self) -> impl Iterator<Item = Vec<Term>> + 'c
+    where
+        'a: 'c,
+    {
+        log::info!("[Start] stable (decomposed)");
+        Decomposition::new(self, DecomposedSemantics::Stable)
+    }
+
+    /// Returns whether the values of the component in the interpretation are accepted by the semantics, when the values of all earlier components are fixed.
+    fn component_check(
+        &mut self,
+        semantics: DecomposedSemantics,
+        component: &[usize],
+        restricted: &[Term],
+        interpretation: &[Term],
+    ) -> bool {
+        match semantics {
+            DecomposedSemantics::Stable => {
+                self.component_stability_check(component, restricted, interpretation)
+            }
+            // the restricted acceptance conditions only depend on the component itself
+            DecomposedSemantics::Complete | DecomposedSemantics::Preferred => {
+                restricted.iter().zip(component.iter()).all(|(&ac, &stm)| {
+                    let ac = component.iter().fold(ac, |acc, &var| {
+                        let val = interpretation[var];
+                        if val.is_truth_value() {
+                            self.bdd.restrict(acc, Var(var), val.is_true())
                         } else {
                             acc
                         }
-                    },
-                ))
+                    });
+                    interpretation[stm].compare_inf(&ac)
+                })
+            }
+        }
+    }
+
+    /// Checks whether the two-valued values of a component are stable, when the values of all earlier components are fixed.
+    fn component_stability_check(
+        &mut self,
+        component: &[usize],
+        restricted: &[Term],
+        interpretation: &[Term],
+    ) -> bool {
+        let mut reduct = interpretation
+            .iter()
+            .map(|val| {
+                if val.is_truth_value() {
+                    *val
+                } else {
+                    Term::BOT
+                }
             })
-        })
+            .collect::<Vec<Term>>();
+        for (&stm, &ac) in component.iter().zip(restricted.iter()) {
+            reduct[stm] = component.iter().fold(ac, |acc, &var| {
+                if interpretation[var] == Term::BOT {
+                    self.bdd.restrict(acc, Var(var), false)
+                } else {
+                    acc
+                }
+            });
+        }
+        let grd = self.grounded_internal(&reduct);
+        component
+            .iter()
+            .all(|&stm| grd[stm].compare_inf(&interpretation[stm]))
     }
 
     /// Returns a [Vector][std::vec::Vec] of [ModelCounts][crate::datatypes::ModelCounts] for each acceptance condition.
@@ -708,6 +1352,100 @@ impl Adf {
             .collect()
     }
 
+    /// Encodes the models of the given semantics into a [Cnf], where the acceptance conditions are translated from the nodes of the [`Bdd`].
+    ///
+    /// Each model of the [Cnf] corresponds to exactly one model of the semantics.
+    /// Note that [stable candidates][CnfSemantics::StableCandidate] still need to be checked for stability.
+    pub fn to_cnf(&mut self, semantics: CnfSemantics) -> Cnf {
+        log::info!("[Start] encoding {semantics} models into CNF");
+        let mut cnf = Cnf::new(semantics, &self.statement_names());
+        let conditions = self
+            .ac
+            .iter()
+            .map(|&ac| {
+                if semantics.is_three_valued() {
+                    cnf.bdd_three_valued(&self.bdd, ac, 0)
+                } else {
+                    let literal = cnf.bdd_two_valued(&self.bdd, ac);
+                    (literal, -literal)
+                }
+            })
+            .collect::<Vec<_>>();
+        cnf.accept(&conditions);
+        if semantics == CnfSemantics::StableCandidate {
+            let grounded = self.grounded();
+            cnf.fix(&grounded);
+        }
+        log::info!(
+            "[Done] encoded into {} variables and {} clauses",
+            cnf.variables,
+            cnf.clauses.len()
+        );
+        cnf
+    }
+
+    /// Encodes the models of the given semantics into a [Cnf], where the acceptance conditions are translated from the formulae of the given parser.
+    /// The [`Adf`] needs to be instantiated from the same parser.
+    ///
+    /// Only two valued semantics can be encoded this way, as the three valued ones need to consider all completions of an interpretation.
+    /// Returns a [CnfError] if the semantics is three valued, or if a formula refers to an unknown statement.
+    pub fn formulae_to_cnf(
+        &mut self,
+        parser: &AdfParser,
+        semantics: CnfSemantics,
+    ) -> Result<Cnf, CnfError> {
+        if semantics.is_three_valued() {
+            return Err(CnfError::UnsupportedSemantics(semantics));
+        }
+        let mut cnf = Cnf::new(semantics, &self.statement_names());
+        let mut conditions = vec![(cnf.constant(false), cnf.constant(true)); self.ac.len()];
+        for (insert_order, new_order) in parser.formula_order().into_iter().enumerate() {
+            let formula = parser.ac_at(insert_order).expect(
+                "Index should exist, because the data originates from the same parser object",
+            );
+            let literal =
+                cnf.formula_two_valued(&formula.as_formula(), |name| self.ordering.variable(name))?;
+            conditions[new_order] = (literal, -literal);
+        }
+        cnf.accept(&conditions);
+        if semantics == CnfSemantics::StableCandidate {
+            let grounded = self.grounded();
+            cnf.fix(&grounded);
+        }
+        Ok(cnf)
+    }
+
+    /// Writes an answer set program, whose answer sets are the models of the given semantics.
+    /// The acceptance conditions are written as ground rules, which evaluate the nodes of the [`Bdd`].
+    ///
+    /// See the [asp module][asp] for the used predicates.
+    pub fn to_asp(&self, semantics: AspSemantics) -> String {
+        asp::program(&self.statement_names(), &self.ac, &self.bdd, semantics)
+    }
+
+    /// Encodes the given preferred reasoning task for the given statement into a [Qbf], where the acceptance conditions are translated from the nodes of the [`Bdd`].
+    ///
+    /// The [Qbf] is true if and only if the statement is true in some or all preferred interpretations respectively.
+    pub fn to_qbf(&self, task: QbfTask, statement: Var) -> Qbf {
+        qbf::encode(
+            &self.statement_names(),
+            &self.ac,
+            &self.bdd,
+            task,
+            statement,
+        )
+    }
+
+    fn statement_names(&self) -> Vec<String> {
+        (0..self.ac.len())
+            .map(|idx| {
+                self.ordering
+                    .name(Var(idx))
+                    .expect("Each statement has a name")
+            })
+            .collect()
+    }
+
     /// Creates a [PrintableInterpretation] for output purposes.
     pub fn print_interpretation<'a, 'b>(
         &'a self,
@@ -724,6 +1462,81 @@ impl Adf {
         PrintDictionary::new(&self.ordering)
     }
 
+    /// Computes the statement dependency graph.
+    /// There is an edge from `s` to `t` whenever `s` occurs in the acceptance condition of `t`.
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        let mut graph = DependencyGraph::new(self.ac.len());
+        self.ac.iter().enumerate().for_each(|(target, ac)| {
+            self.bdd
+                .var_dependencies(*ac)
+                .into_iter()
+                .for_each(|source| graph.add_edge(source.value(), target));
+        });
+        graph
+    }
+
+    /// Computes the labelled [StatementGraph] of the [dependency graph][Self::dependency_graph] for export purposes.
+    ///
+    /// `polarity` controls whether the [polarity][LinkPolarity] of each link is computed.
+    /// `scc` controls whether the membership of each statement to a strongly connected component is computed.
+    pub fn statement_graph(&mut self, polarity: bool, scc: bool) -> StatementGraph {
+        let graph = self.dependency_graph();
+        let membership = scc.then(|| graph.scc_membership());
+        let statements = (0..self.ac.len())
+            .map(|idx| StatementNode {
+                name: self
+                    .ordering
+                    .name(Var(idx))
+                    .expect("Each statement has a name"),
+                scc: membership.as_ref().map(|membership| membership[idx]),
+            })
+            .collect();
+        let links = graph
+            .edges()
+            .map(|(source, target)| StatementLink {
+                source,
+                target,
+                polarity: polarity.then(|| self.link_polarity(Var(source), Var(target))),
+            })
+            .collect();
+        StatementGraph { statements, links }
+    }
+
+    /// Computes the [polarity][LinkPolarity] of the link from `source` to `target`.
+    pub fn link_polarity(&mut self, source: Var, target: Var) -> LinkPolarity {
+        let ac = self.ac[target.value()];
+        let lo = self.bdd.restrict(ac, source, false);
+        let hi = self.bdd.restrict(ac, source, true);
+        if self.bdd.imp(lo, hi) == Term::TOP {
+            LinkPolarity::Support
+        } else if self.bdd.imp(hi, lo) == Term::TOP {
+            LinkPolarity::Attack
+        } else {
+            LinkPolarity::Dependent
+        }
+    }
+
+    /// Renders the acceptance conditions of the given statements as one graphviz DOT diagram (see [Bdd::to_dot]).
+    pub fn ac_to_dot(&self, statements: &[Var]) -> String {
+        let roots = statements
+            .iter()
+            .map(|&var| (var, self.ac[var.value()]))
+            .collect::<Vec<(Var, Term)>>();
+        self.bdd.to_dot(&roots, &self.ordering)
+    }
+
+    /// Renders all acceptance conditions, restricted by the given interpretation, as one graphviz DOT diagram (see [Bdd::to_dot]).
+    pub fn restricted_ac_to_dot(&mut self, interpretation: &[Term]) -> String {
+        let ac = self.ac.clone();
+        let roots = self
+            .apply_interpretation(&ac, interpretation)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, term)| (Var(idx), term))
+            .collect::<Vec<(Var, Term)>>();
+        self.bdd.to_dot(&roots, &self.ordering)
+    }
+
     /// Fixes the bdd after an import with serde.
     pub fn fix_import(&mut self) {
         self.bdd.fix_import();
@@ -753,18 +1566,144 @@ impl Adf {
             .collect::<Vec<_>>()
     }
 
+    /// Counts the models of the given roBDD over all statements exactly, together with the number of models, in which each statement is true.
+    ///
+    /// In contrast to [facet_count][Adf::facet_count], the statements which do not occur in the roBDD are taken into account, and the counts do not saturate.
+    /// Returns [CountOverflow] if a count does not fit into an [u128].
+    pub fn exact_facet_count(&self, term: Term) -> Result<(u128, Vec<u128>), CountOverflow> {
+        self.bdd.exact_facet_counts(term, self.ac.len())
+    }
+
+    /// Computes the facets of the models of the given semantics, in which all fixed facets hold.
+    ///
+    /// Each returned [facet][Facet] holds in some but not all of these models, together with the number of models which remain after fixing it.
+    /// Two valued models are counted symbolically, whereas the complete and stable models are enumerated.
+    /// Returns [NavigationError::CountOverflow] if the number of two valued models does not fit into an [u128].
+    pub fn navigate(
+        &mut self,
+        semantics: NavigationSemantics,
+        fixed: &[Facet],
+    ) -> Result<Navigation, NavigationError> {
+        self.navigate_budgeted(semantics, fixed, &Budget::default())
+    }
+
+    /// Computes the facets of the models of the given semantics within the given [Budget], in which all fixed facets hold.
+    /// Returns the reason of the [interruption][Interrupted] if the budget has been exhausted, and [NavigationError::CountOverflow] if the number of two valued models does not fit into an [u128].
+    pub fn navigate_budgeted(
+        &mut self,
+        semantics: NavigationSemantics,
+        fixed: &[Facet],
+        budget: &Budget,
+    ) -> Result<Navigation, NavigationError> {
+        let holds = |model: &Vec<Term>| fixed.iter().all(|facet| facet.holds(model));
+        match semantics {
+            NavigationSemantics::Complete => {
+                let models = self
+                    .complete_budgeted(budget.clone())
+                    .filter(|model| model.as_ref().map_or(true, holds))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Navigation::from_models(self.ac.len(), &models))
+            }
+            NavigationSemantics::Stable => {
+                let models = self
+                    .stable_nogood_budgeted(Heuristic::default(), budget.clone())
+                    .filter(|model| model.as_ref().map_or(true, holds))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Navigation::from_models(self.ac.len(), &models))
+            }
+            NavigationSemantics::TwoValued => self.navigate_two_valued(fixed, budget),
+        }
+    }
+
+    fn navigate_two_valued(
+        &mut self,
+        fixed: &[Facet],
+        budget: &Budget,
+    ) -> Result<Navigation, NavigationError> {
+        let mut representation = self.two_valued_representation();
+        for facet in fixed {
+            budget.check(self.bdd.nodes.len())?;
+            representation = if facet.value.is_truth_value() {
+                let var = self.bdd.variable(facet.statement);
+                let literal = if facet.value.is_true() {
+                    var
+                } else {
+                    self.bdd.not(var)
+                };
+                self.bdd.and(representation, literal)
+            } else {
+                Term::BOT
+            };
+        }
+        budget.check(self.bdd.nodes.len())?;
+        let (model_count, true_counts) = self.exact_facet_count(representation)?;
+        let facets = true_counts
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, true_count)| true_count > 0 && true_count < model_count)
+            .flat_map(|(statement, true_count)| {
+                [
+                    FacetCount {
+                        facet: Facet::new(Var(statement), Term::BOT),
+                        model_count: model_count - true_count,
+                    },
+                    FacetCount {
+                        facet: Facet::new(Var(statement), Term::TOP),
+                        model_count: true_count,
+                    },
+                ]
+            })
+            .collect();
+        Ok(Navigation {
+            model_count,
+            facets,
+        })
+    }
+
     /// Computes the stable extensions of a given [`Adf`], using the [`NoGood`]-learner.
-    pub fn stable_nogood<'a, 'c>(
+    /// The models are computed lazily, i.e. the search only continues until the next model has been found.
+    pub fn stable_nogood<'a, 'b, 'c>(
         &'a mut self,
-        heuristic: Heuristic,
+        heuristic: Heuristic<'b>,
     ) -> impl Iterator<Item = Vec<Term>> + 'c
     where
         'a: 'c,
+        'b: 'c,
     {
-        let grounded = self.grounded();
-        let heu = heuristic.get_heuristic();
-        let (s, r) = crossbeam_channel::unbounded::<Vec<Term>>();
-        self.stable_nogood_get_vec(&grounded, heu, s, r).into_iter()
+        self.stable_nogood_budgeted(heuristic, Budget::default())
+            .map(|model| model.expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the stable extensions of a given [`Adf`] within the given [Budget], using the [`NoGood`]-learner.
+    /// The models are computed lazily, i.e. the search only continues until the next model has been found.
+    /// Returns an Iterator which contains all stable models, found before the budget is exhausted.
+    /// If the budget is exhausted, the last element is the reason of the [interruption][Interrupted].
+    pub fn stable_nogood_budgeted<'a, 'b, 'c>(
+        &'a mut self,
+        heuristic: Heuristic<'b>,
+        budget: Budget,
+    ) -> impl Iterator<Item = Result<Vec<Term>, Interrupted>> + 'c
+    where
+        'a: 'c,
+        'b: 'c,
+    {
+        let heuristic: Heuristic<'c> = heuristic;
+        let grounded = self.grounded_budgeted(&budget);
+        let mut search = grounded
+            .as_ref()
+            .ok()
+            .map(|grounded| NoGoodSearch::new(self, grounded));
+        let mut interruption = grounded.err();
+        std::iter::from_fn(move || match (interruption.take(), search.as_mut()) {
+            (Some(reason), _) => Some(Err(reason)),
+            (None, Some(search)) => search.next_model(
+                self,
+                heuristic.get_heuristic(),
+                Self::stability_check,
+                &budget,
+            ),
+            (None, None) => None,
+        })
     }
 
     /// Computes the stable extension of a given [`Adf`], using the [`NoGood`]-learner.
@@ -774,13 +1713,27 @@ impl Adf {
         heuristic: Heuristic,
         sender: crossbeam_channel::Sender<Vec<Term>>,
     ) {
-        let grounded = self.grounded();
-        self.nogood_internal(
+        self.stable_nogood_channel_budgeted(heuristic, sender, &Budget::default())
+            .expect("An unlimited budget cannot be exhausted")
+    }
+
+    /// Computes the stable extension of a given [`Adf`] within the given [Budget], using the [`NoGood`]-learner.
+    /// Needs a [`Sender`][crossbeam_channel::Sender<Vec<crate::datatypes::Term>>] where the results of the computation can be put to.
+    /// Returns the reason of the [interruption][Interrupted] if the budget has been exhausted.
+    pub fn stable_nogood_channel_budgeted(
+        &mut self,
+        heuristic: Heuristic,
+        sender: crossbeam_channel::Sender<Vec<Term>>,
+        budget: &Budget,
+    ) -> Result<(), Interrupted> {
+        let grounded = self.grounded_budgeted(budget)?;
+        self.nogood_internal_budgeted(
             &grounded,
             heuristic.get_heuristic(),
             Self::stability_check,
             sender,
-        );
+            budget,
+        )
     }
 
     /// Computes the two valued  extension of a given [`Adf`], using the [`NoGood`]-learner.
@@ -790,154 +1743,895 @@ impl Adf {
         heuristic: Heuristic,
         sender: crossbeam_channel::Sender<Vec<Term>>,
     ) {
-        let grounded = self.grounded();
-        self.nogood_internal(
+        self.two_val_nogood_channel_budgeted(heuristic, sender, &Budget::default())
+            .expect("An unlimited budget cannot be exhausted")
+    }
+
+    /// Computes the two valued extension of a given [`Adf`] within the given [Budget], using the [`NoGood`]-learner.
+    /// Needs a [`Sender`][crossbeam_channel::Sender<Vec<crate::datatypes::Term>>] where the results of the computation can be put to.
+    /// Returns the reason of the [interruption][Interrupted] if the budget has been exhausted.
+    pub fn two_val_nogood_channel_budgeted(
+        &mut self,
+        heuristic: Heuristic,
+        sender: crossbeam_channel::Sender<Vec<Term>>,
+        budget: &Budget,
+    ) -> Result<(), Interrupted> {
+        let grounded = self.grounded_budgeted(budget)?;
+        self.nogood_internal_budgeted(
             &grounded,
             heuristic.get_heuristic(),
             |_self: &mut Self, _int: &[Term]| true,
             sender,
+            budget,
         )
     }
 
-    fn stable_nogood_get_vec<H>(
+    /// Computes the stable extension of a given [`Adf`] in parallel, using the [`NoGood`]-learner.
+    /// The search space is split on the first `split_depth` choices of the heuristic into at most 2<sup>`split_depth`</sup> subproblems, which are solved by `threads` worker threads.
+    /// Needs a [`Sender`][crossbeam_channel::Sender<Vec<crate::datatypes::Term>>] where the results of the computation can be put to.
+    ///
+    /// Returns immediately, so the results can be consumed while the workers are still searching.
+    /// The workers stop as soon as the receiver has been dropped, and the returned handle can be joined to wait for them.
+    pub fn stable_nogood_channel_parallel(
+        &mut self,
+        heuristic: Heuristic<'static>,
+        split_depth: usize,
+        threads: NonZeroUsize,
+        sender: crossbeam_channel::Sender<Vec<Term>>,
+    ) -> JoinHandle<()> {
+        let search = self.nogood_parallel(
+            heuristic,
+            split_depth,
+            threads,
+            Self::stability_check,
+            sender,
+            &Budget::default(),
+        );
+        std::thread::spawn(move || search().expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the stable extension of a given [`Adf`] in parallel within the given [Budget], using the [`NoGood`]-learner (see [stable_nogood_channel_parallel][Adf::stable_nogood_channel_parallel]).
+    /// Each worker thread checks the budget on its own copy of the BDD, and no further subproblems are started once one of them has been interrupted.
+    /// Joining the returned handle yields the reason of the first [interruption][Interrupted] if the budget has been exhausted.
+    pub fn stable_nogood_channel_parallel_budgeted(
+        &mut self,
+        heuristic: Heuristic<'static>,
+        split_depth: usize,
+        threads: NonZeroUsize,
+        sender: crossbeam_channel::Sender<Vec<Term>>,
+        budget: &Budget,
+    ) -> JoinHandle<Result<(), Interrupted>> {
+        std::thread::spawn(self.nogood_parallel(
+            heuristic,
+            split_depth,
+            threads,
+            Self::stability_check,
+            sender,
+            budget,
+        ))
+    }
+
+    /// Computes the two valued extension of a given [`Adf`] in parallel, using the [`NoGood`]-learner.
+    /// The search space is split on the first `split_depth` choices of the heuristic into at most 2<sup>`split_depth`</sup> subproblems, which are solved by `threads` worker threads.
+    /// Needs a [`Sender`][crossbeam_channel::Sender<Vec<crate::datatypes::Term>>] where the results of the computation can be put to.
+    ///
+    /// Returns immediately, so the results can be consumed while the workers are still searching.
+    /// The workers stop as soon as the receiver has been dropped, and the returned handle can be joined to wait for them.
+    pub fn two_val_nogood_channel_parallel(
+        &mut self,
+        heuristic: Heuristic<'static>,
+        split_depth: usize,
+        threads: NonZeroUsize,
+        sender: crossbeam_channel::Sender<Vec<Term>>,
+    ) -> JoinHandle<()> {
+        let search = self.nogood_parallel(
+            heuristic,
+            split_depth,
+            threads,
+            |_self: &mut Self, _int: &[Term]| true,
+            sender,
+            &Budget::default(),
+        );
+        std::thread::spawn(move || search().expect("An unlimited budget cannot be exhausted"))
+    }
+
+    /// Computes the two valued extension of a given [`Adf`] in parallel within the given [Budget], using the [`NoGood`]-learner (see [two_val_nogood_channel_parallel][Adf::two_val_nogood_channel_parallel]).
+    /// Each worker thread checks the budget on its own copy of the BDD, and no further subproblems are started once one of them has been interrupted.
+    /// Joining the returned handle yields the reason of the first [interruption][Interrupted] if the budget has been exhausted.
+    pub fn two_val_nogood_channel_parallel_budgeted(
+        &mut self,
+        heuristic: Heuristic<'static>,
+        split_depth: usize,
+        threads: NonZeroUsize,
+        sender: crossbeam_channel::Sender<Vec<Term>>,
+        budget: &Budget,
+    ) -> JoinHandle<Result<(), Interrupted>> {
+        std::thread::spawn(self.nogood_parallel(
+            heuristic,
+            split_depth,
+            threads,
+            |_self: &mut Self, _int: &[Term]| true,
+            sender,
+            budget,
+        ))
+    }
+
+    /// Prepares the parallel search, which is run by the returned closure on a frozen copy of the [`Adf`], so the [`Adf`] itself is left unchanged.
+    /// The closure computes the grounded interpretation, splits the search space, and distributes the subproblems to the worker threads.
+    fn nogood_parallel<I>(
+        &self,
+        heuristic: Heuristic<'static>,
+        split_depth: usize,
+        threads: NonZeroUsize,
+        stability_check: I,
+        sender: crossbeam_channel::Sender<Vec<Term>>,
+        budget: &Budget,
+    ) -> impl FnOnce() -> Result<(), Interrupted> + Send + 'static
+    where
+        I: Fn(&mut Self, &[Term]) -> bool + Copy + Send + 'static,
+    {
+        // the workers share the nodes of the frozen copy, and only keep their own ones
+        let mut adf = self.clone();
+        adf.freeze();
+        let budget = budget.clone();
+        move || {
+            let grounded = adf.grounded_budgeted(&budget)?;
+            let subproblems = adf.split_search_space(&grounded, heuristic, split_depth, &budget)?;
+            log::info!(
+                "[Start] solving {} subproblems on {} threads",
+                subproblems.len(),
+                threads
+            );
+            let (job_sender, job_receiver) = crossbeam_channel::unbounded::<Vec<Term>>();
+            for subproblem in subproblems {
+                job_sender
+                    .send(subproblem)
+                    .expect("Job receiver should be alive");
+            }
+            drop(job_sender);
+            let interruption = Mutex::new(None);
+            let disconnected = AtomicBool::new(false);
+            std::thread::scope(|scope| {
+                for _ in 0..threads.get() {
+                    let mut worker = adf.clone();
+                    let job_receiver = job_receiver.clone();
+                    let sender = sender.clone();
+                    let (interruption, disconnected, budget) =
+                        (&interruption, &disconnected, &budget);
+                    scope.spawn(move || {
+                        for subproblem in job_receiver.iter() {
+                            if disconnected.load(Ordering::Relaxed)
+                                || interruption
+                                    .lock()
+                                    .expect("Lock on interruption failed")
+                                    .is_some()
+                            {
+                                break;
+                            }
+                            log::debug!("solving subproblem {:?}", subproblem);
+                            match worker.nogood_until_disconnected(
+                                &subproblem,
+                                heuristic.get_heuristic(),
+                                stability_check,
+                                &sender,
+                                budget,
+                            ) {
+                                Ok(true) => {}
+                                Ok(false) => {
+                                    disconnected.store(true, Ordering::Relaxed);
+                                    break;
+                                }
+                                Err(reason) => {
+                                    interruption
+                                        .lock()
+                                        .expect("Lock on interruption failed")
+                                        .get_or_insert(reason);
+                                    break;
+                                }
+                            }
+                        }
+                    });
+                }
+            });
+            log::info!("[Done] solving subproblems");
+            match interruption
+                .into_inner()
+                .expect("Lock on interruption failed")
+            {
+                Some(reason) => Err(reason),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Splits the search space of the given interpretation on the first `depth` choices of the heuristic.
+    /// The returned interpretations are pairwise disjoint and cover all two-valued interpretations of the given one.
+    fn split_search_space(
+        &mut self,
+        interpretation: &[Term],
+        heuristic: Heuristic,
+        depth: usize,
+        budget: &Budget,
+    ) -> Result<Vec<Vec<Term>>, Interrupted> {
+        let heu = heuristic.get_heuristic();
+        let mut frontier = vec![interpretation.to_vec()];
+        for _ in 0..depth {
+            let mut next_frontier = Vec::with_capacity(frontier.len() * 2);
+            for interpr in frontier {
+                budget.check(self.bdd.nodes.len())?;
+                let interpr = self.update_interpretation_fixpoint(&interpr);
+                match heu(&*self, &interpr) {
+                    Some((var, term)) => {
+                        let mut chosen = interpr.clone();
+                        chosen[var.value()] = term;
+                        let mut negated = interpr;
+                        negated[var.value()] = Term::from(!term.is_true());
+                        next_frontier.push(chosen);
+                        next_frontier.push(negated);
+                    }
+                    None => next_frontier.push(interpr),
+                }
+            }
+            frontier = next_frontier;
+        }
+        Ok(frontier)
+    }
+
+    #[cfg(test)]
+    fn nogood_internal<H, I>(
         &mut self,
         interpretation: &[Term],
         heuristic: H,
+        stability_check: I,
         s: crossbeam_channel::Sender<Vec<Term>>,
-        r: crossbeam_channel::Receiver<Vec<Term>>,
-    ) -> Vec<Vec<Term>>
-    where
+    ) where
         H: Fn(&Self, &[Term]) -> Option<(Var, Term)>,
+        I: Fn(&mut Self, &[Term]) -> bool,
     {
-        self.nogood_internal(interpretation, heuristic, Self::stability_check, s);
-        r.iter().collect()
+        self.nogood_internal_budgeted(
+            interpretation,
+            heuristic,
+            stability_check,
+            s,
+            &Budget::default(),
+        )
+        .expect("An unlimited budget cannot be exhausted")
     }
 
-    fn nogood_internal<H, I>(
+    fn nogood_internal_budgeted<H, I>(
         &mut self,
         interpretation: &[Term],
         heuristic: H,
         stability_check: I,
         s: crossbeam_channel::Sender<Vec<Term>>,
-    ) where
+        budget: &Budget,
+    ) -> Result<(), Interrupted>
+    where
         H: Fn(&Self, &[Term]) -> Option<(Var, Term)>,
         I: Fn(&mut Self, &[Term]) -> bool,
     {
-        let mut cur_interpr = interpretation.to_vec();
-        let mut ng_store = NoGoodStore::new(
-            self.ac
-                .len()
-                .try_into()
-                .expect("Expecting only u32 many statements"),
-        );
-        let mut stack: Vec<(bool, NoGood)> = Vec::new();
-        let mut interpr_history: Vec<Vec<Term>> = Vec::new();
-        let mut backtrack = false;
-        let mut update_ng;
-        let mut update_fp = false;
-        let mut choice = false;
+        let mut search = NoGoodSearch::new(self, interpretation);
+        while let Some(model) = search.next_model(self, &heuristic, &stability_check, budget) {
+            s.send(model?).expect("Sender should accept results");
+        }
+        Ok(())
+    }
 
-        log::debug!("start learning loop");
+    /// Sends the models of the given interpretation like [nogood_internal_budgeted][Adf::nogood_internal_budgeted], but stops as soon as the receiver has been dropped.
+    /// Returns [false] in this case.
+    fn nogood_until_disconnected<H, I>(
+        &mut self,
+        interpretation: &[Term],
+        heuristic: H,
+        stability_check: I,
+        s: &crossbeam_channel::Sender<Vec<Term>>,
+        budget: &Budget,
+    ) -> Result<bool, Interrupted>
+    where
+        H: Fn(&Self, &[Term]) -> Option<(Var, Term)>,
+        I: Fn(&mut Self, &[Term]) -> bool,
+    {
+        let mut search = NoGoodSearch::new(self, interpretation);
+        while let Some(model) = search.next_model(self, &heuristic, &stability_check, budget) {
+            if s.send(model?).is_err() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Semantics, whose models can be computed along the strongly connected components of the dependency graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum DecomposedSemantics {
+    Complete,
+    Preferred,
+    Stable,
+}
+
+/// Lazy enumeration of the models of a [DecomposedSemantics], which solves the strongly connected components in topological order.
+///
+/// The search keeps a single interpretation, where the components on the stack are assigned to their current candidate, and the later ones keep their grounded values.
+/// Each frame on the stack holds the remaining candidates of its component, so the search backtracks without recursion and without copying the interpretation.
+struct Decomposition<'a> {
+    adf: &'a mut Adf,
+    semantics: DecomposedSemantics,
+    components: Vec<Vec<usize>>,
+    grounded: Vec<Term>,
+    interpretation: Vec<Term>,
+    frames: Vec<ComponentFrame>,
+    finished: bool,
+}
+
+/// The remaining candidates of a component and its acceptance conditions, which are restricted by the values of the earlier components.
+struct ComponentFrame {
+    restricted: Vec<Term>,
+    candidates: Box<dyn Iterator<Item = Vec<Term>>>,
+    /// Whether the candidates are already known to be accepted.
+    checked: bool,
+}
+
+impl<'a> Decomposition<'a> {
+    fn new(adf: &'a mut Adf, semantics: DecomposedSemantics) -> Self {
+        let grounded = adf.grounded();
+        let components = adf.dependency_graph().sccs();
+        log::debug!("{} strongly connected components", components.len());
+        let mut result = Self {
+            adf,
+            semantics,
+            components,
+            interpretation: grounded.clone(),
+            grounded,
+            frames: Vec::new(),
+            finished: false,
+        };
+        if !result.components.is_empty() {
+            result.enter();
+        }
+        result
+    }
+
+    /// Pushes the frame of the next component, whose statements still have their grounded values.
+    fn enter(&mut self) {
+        let component = &self.components[self.frames.len()];
+        // the acceptance conditions only depend on earlier components and the component itself
+        let restricted = component
+            .iter()
+            .map(|&stm| {
+                let ac = self.adf.ac[stm];
+                self.adf
+                    .bdd
+                    .var_dependencies(ac)
+                    .into_iter()
+                    .fold(ac, |acc, var| {
+                        let val = self.interpretation[var.value()];
+                        if val.is_truth_value() {
+                            self.adf.bdd.restrict(acc, var, val.is_true())
+                        } else {
+                            acc
+                        }
+                    })
+            })
+            .collect::<Vec<Term>>();
+        let local = component
+            .iter()
+            .map(|&stm| self.interpretation[stm])
+            .collect::<Vec<Term>>();
+        let frame = match self.semantics {
+            DecomposedSemantics::Complete => ComponentFrame {
+                restricted,
+                candidates: Box::new(ThreeValuedInterpretationsIterator::new(&local)),
+                checked: false,
+            },
+            DecomposedSemantics::Stable => ComponentFrame {
+                restricted,
+                candidates: Box::new(TwoValuedInterpretationsIterator::new(&local)),
+                checked: false,
+            },
+            DecomposedSemantics::Preferred => {
+                let mut complete = Vec::new();
+                for candidate in ThreeValuedInterpretationsIterator::new(&local) {
+                    if self.accepts(&restricted, &candidate) {
+                        complete.push(candidate);
+                    }
+                }
+                self.assign(&local);
+                let maximal = complete
+                    .iter()
+                    .filter(|candidate| {
+                        !complete.iter().any(|other| {
+                            other != *candidate
+                                && candidate
+                                    .iter()
+                                    .zip(other.iter())
+                                    .all(|(val, other)| !val.is_truth_value() || val == other)
+                        })
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                ComponentFrame {
+                    restricted,
+                    candidates: Box::new(maximal.into_iter()),
+                    checked: true,
+                }
+            }
+        };
+        self.frames.push(frame);
+    }
+
+    /// Assigns the values to the statements of the topmost component.
+    fn assign(&mut self, values: &[Term]) {
+        let component = &self.components[self.frames.len()];
+        for (&stm, &val) in component.iter().zip(values.iter()) {
+            self.interpretation[stm] = val;
+        }
+    }
+
+    /// Assigns the candidate to the statements of the component, which is entered next, and checks whether the semantics accepts it.
+    fn accepts(&mut self, restricted: &[Term], candidate: &[Term]) -> bool {
+        self.assign(candidate);
+        let component = &self.components[self.frames.len()];
+        let accepted =
+            self.adf
+                .component_check(self.semantics, component, restricted, &self.interpretation);
+        log::trace!("component {:?}: {:?} -> {}", component, candidate, accepted);
+        accepted
+    }
+}
+
+impl Iterator for Decomposition<'_> {
+    type Item = Vec<Term>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        if self.components.is_empty() {
+            self.finished = true;
+            return Some(self.interpretation.clone());
+        }
         loop {
-            log::trace!("interpr: {:?}", cur_interpr);
-            log::trace!("choice: {}", choice);
-            if choice {
-                choice = false;
-                if let Some((var, term)) = heuristic(&*self, &cur_interpr) {
-                    log::trace!("choose {}->{}", var, term.is_true());
-                    interpr_history.push(cur_interpr.to_vec());
-                    cur_interpr[var.value()] = term;
-                    stack.push((true, cur_interpr.as_slice().into()));
+            let mut frame = match self.frames.pop() {
+                Some(frame) => frame,
+                None => {
+                    self.finished = true;
+                    log::info!("[Done] decomposition");
+                    return None;
+                }
+            };
+            let mut accepted = false;
+            for candidate in frame.candidates.by_ref() {
+                if frame.checked {
+                    self.assign(&candidate);
+                    accepted = true;
                 } else {
-                    backtrack = true;
+                    accepted = self.accepts(&frame.restricted, &candidate);
                 }
-            }
-            update_ng = true;
-            log::trace!("backtrack: {}", backtrack);
-            if backtrack {
-                backtrack = false;
-                if stack.is_empty() {
+                if accepted {
                     break;
                 }
-                while let Some((choice, ng)) = stack.pop() {
-                    log::trace!("adding ng: {:?}", ng);
-                    ng_store.add_ng(ng);
-
-                    if choice {
-                        cur_interpr = interpr_history.pop().expect("both stacks (interpr_history and `stack`) should always be synchronous");
-                        log::trace!(
-                            "choice found, reverting interpretation to {:?}",
-                            cur_interpr
-                        );
-                        break;
-                    }
-                }
             }
-            match ng_store.conclusion_closure(&cur_interpr) {
-                crate::nogoods::ClosureResult::Update(new_int) => {
-                    cur_interpr = new_int;
-                    log::trace!("ng update: {:?}", cur_interpr);
-                    stack.push((false, cur_interpr.as_slice().into()));
-                }
-                crate::nogoods::ClosureResult::NoUpdate => {
-                    log::trace!("no update");
-                    update_ng = false;
+            if !accepted {
+                // the later components are entered again with the grounded values of this one
+                let grounded = self.components[self.frames.len()]
+                    .iter()
+                    .map(|&stm| self.grounded[stm])
+                    .collect::<Vec<_>>();
+                self.assign(&grounded);
+                continue;
+            }
+            self.frames.push(frame);
+            if self.frames.len() == self.components.len() {
+                return Some(self.interpretation.clone());
+            }
+            self.enter();
+        }
+    }
+}
+
+/// Resumable state of the [`NoGood`]-learning search.
+///
+/// Each call of [next_model][NoGoodSearch::next_model] continues the search where the previous one stopped, which allows to enumerate the models lazily.
+/// The assigned statements are kept on a trail, together with their decision level and the assignments, which imply their value.
+/// On a conflict, a short [`NoGood`] is learned by a 1-UIP analysis of these reasons and the search jumps back to the level, where the learned [`NoGood`] concludes a new value.
+#[derive(Debug)]
+struct NoGoodSearch {
+    cur_interpr: Vec<Term>,
+    /// The interpretation, which the search has been started with.
+    initial: Vec<Term>,
+    ng_store: NoGoodStore,
+    /// The assigned statements in the order of their assignment.
+    trail: Vec<usize>,
+    /// The position on the trail, where each decision level starts with its choice.
+    level_starts: Vec<usize>,
+    /// The decision level of each assigned statement.
+    level: Vec<usize>,
+    /// The assignments, which imply the value of each assigned statement, or [None] for the choices and the initial values.
+    reason: Vec<Option<Vec<(usize, bool)>>>,
+    /// The statements, which occur in the acceptance condition of each statement.
+    dependencies: Vec<Vec<usize>>,
+    /// The statements, whose acceptance condition contains each statement.
+    dependents: Vec<Vec<usize>>,
+    /// The position on the trail, up to which the assignments have been propagated by the nogoods.
+    ng_propagated: usize,
+    /// The position on the trail, up to which the assignments have been propagated by the acceptance conditions.
+    ac_propagated: usize,
+    /// The statements, whose acceptance condition needs to be checked again, independently of the newly assigned statements.
+    unchecked: Vec<usize>,
+    finished: bool,
+}
+
+/// State of a statement during the analysis of a conflict.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mark {
+    Unseen,
+    /// The value is part of the learned [`NoGood`], or implied by its assignments.
+    Implied,
+    /// The value is not implied by the assignments of the learned [`NoGood`].
+    Failed,
+}
+
+impl NoGoodSearch {
+    fn new(adf: &Adf, interpretation: &[Term]) -> Self {
+        log::debug!("start learning loop");
+        let statements = adf.ac.len();
+        let dependencies = adf
+            .ac
+            .iter()
+            .map(|&ac| {
+                let mut dependencies = adf
+                    .bdd
+                    .var_dependencies(ac)
+                    .into_iter()
+                    .map(|var| var.value())
+                    .collect::<Vec<_>>();
+                dependencies.sort_unstable();
+                dependencies
+            })
+            .collect::<Vec<_>>();
+        let mut dependents = vec![Vec::new(); statements];
+        for (pos, deps) in dependencies.iter().enumerate() {
+            for &dep in deps {
+                dependents[dep].push(pos);
+            }
+        }
+        Self {
+            cur_interpr: interpretation.to_vec(),
+            initial: interpretation.to_vec(),
+            ng_store: NoGoodStore::new(
+                statements
+                    .try_into()
+                    .expect("Expecting only u32 many statements"),
+            ),
+            trail: interpretation
+                .iter()
+                .enumerate()
+                .filter(|(_, val)| val.is_truth_value())
+                .map(|(pos, _)| pos)
+                .collect(),
+            level_starts: Vec::new(),
+            level: vec![0; statements],
+            reason: vec![None; statements],
+            dependencies,
+            dependents,
+            ng_propagated: 0,
+            ac_propagated: 0,
+            // the acceptance conditions of the initial interpretation may already conclude values
+            unchecked: (0..statements).collect(),
+            finished: false,
+        }
+    }
+
+    /// Continues the search until the next model has been found.
+    /// Returns [None] if the search space is exhausted and the reason of the [interruption][Interrupted] if the budget has been exhausted.
+    fn next_model<H, I>(
+        &mut self,
+        adf: &mut Adf,
+        heuristic: H,
+        stability_check: I,
+        budget: &Budget,
+    ) -> Option<Result<Vec<Term>, Interrupted>>
+    where
+        H: Fn(&Adf, &[Term]) -> Option<(Var, Term)>,
+        I: Fn(&mut Adf, &[Term]) -> bool,
+    {
+        if self.finished {
+            return None;
+        }
+        loop {
+            if let Err(reason) = budget.check(adf.bdd.nodes.len()) {
+                log::info!("learning loop interrupted: {reason}");
+                log::info!("{}", self.ng_store);
+                self.finished = true;
+                return Some(Err(reason));
+            }
+            log::trace!("interpr: {:?}", self.cur_interpr);
+            if let Some(conflict) = self.propagate(adf) {
+                if self.learn(&conflict) {
+                    continue;
                 }
-                crate::nogoods::ClosureResult::Inconsistent => {
-                    log::trace!("inconsistency");
-                    backtrack = true;
+                break;
+            }
+            if !adf.is_two_valued(&self.cur_interpr) {
+                if let Some((var, term)) = heuristic(&*adf, &self.cur_interpr) {
+                    log::trace!("choose {}->{}", var, term.is_true());
+                    self.level_starts.push(self.trail.len());
+                    self.cur_interpr[var.value()] = term;
+                    self.assign(var.value(), None);
                     continue;
                 }
             }
-
-            let ac_consistent_interpr = self.apply_interpretation(&self.ac.clone(), &cur_interpr);
-            log::trace!(
-                "checking consistency of {:?} against {:?}",
-                ac_consistent_interpr,
-                cur_interpr
-            );
-            if cur_interpr
+            // the choices determine the whole interpretation, so they are excluded from the further search
+            let choices = self
+                .level_starts
                 .iter()
-                .zip(ac_consistent_interpr.iter())
-                .any(|(cur, ac)| {
-                    cur.is_truth_value() && ac.is_truth_value() && cur.is_true() != ac.is_true()
-                })
+                .map(|&start| self.literal(self.trail[start]))
+                .collect::<Vec<_>>();
+            let model = if adf.is_two_valued(&self.cur_interpr)
+                && stability_check(adf, &self.cur_interpr)
             {
-                log::trace!("ac_inconsistency");
-                backtrack = true;
-                continue;
+                log::trace!("stable model found");
+                Some(self.cur_interpr.clone())
+            } else {
+                log::trace!("2 val not stable");
+                None
+            };
+            let exhausted = !self.learn(&choices);
+            if let Some(model) = model {
+                self.finished = exhausted;
+                return Some(Ok(model));
             }
+            if exhausted {
+                break;
+            }
+        }
+        self.finished = true;
+        log::info!("{}", self.ng_store);
+        log::debug!("{:?}", self.ng_store);
+        None
+    }
 
-            cur_interpr = self.update_interpretation_fixpoint_upd(&cur_interpr, &mut update_fp);
-            if update_fp {
-                log::trace!("fixpount updated");
-                //stack.push((false, cur_interpr.as_slice().into()));
-            } else if !update_ng {
-                // No updates done this loop
-                if !self.is_two_valued(&cur_interpr) {
-                    choice = true;
-                } else if stability_check(self, &cur_interpr) {
-                    // stable model found
-                    stack.push((false, cur_interpr.as_slice().into()));
-                    s.send(cur_interpr.clone())
-                        .expect("Sender should accept results");
-                    backtrack = true;
+    /// Returns the assignment of the given statement.
+    fn literal(&self, pos: usize) -> (usize, bool) {
+        (pos, self.cur_interpr[pos].is_true())
+    }
+
+    /// Puts the already assigned statement on the trail.
+    fn assign(&mut self, pos: usize, reason: Option<Vec<(usize, bool)>>) {
+        self.level[pos] = self.level_starts.len();
+        self.reason[pos] = reason;
+        self.trail.push(pos);
+    }
+
+    /// Propagates the nogoods and the acceptance conditions, until no further value can be concluded.
+    /// Only the assignments on the trail, which have not been propagated yet, are taken into account, i.e. the nogoods which watch them and the acceptance conditions which depend on them.
+    /// *Returns* a set of assignments, which cannot hold together, if there is a conflict.
+    fn propagate(&mut self, adf: &mut Adf) -> Option<Vec<(usize, bool)>> {
+        loop {
+            let mut implied = Vec::new();
+            let conflict = self.ng_store.propagate(
+                &mut self.cur_interpr,
+                &self.trail[self.ng_propagated..],
+                |pos, literals| {
+                    implied.push((
+                        pos,
+                        literals
+                            .iter()
+                            .copied()
+                            .filter(|&(other, _)| other != pos)
+                            .collect::<Vec<_>>(),
+                    ))
+                },
+            );
+            for (pos, reason) in implied {
+                self.assign(pos, Some(reason));
+            }
+            if conflict.is_some() {
+                log::trace!("inconsistency");
+                return conflict;
+            }
+            self.ng_propagated = self.trail.len();
+
+            let mut candidates = self.unchecked.clone();
+            for &pos in &self.trail[self.ac_propagated..] {
+                candidates.push(pos);
+                candidates.extend_from_slice(&self.dependents[pos]);
+            }
+            candidates.sort_unstable();
+            candidates.dedup();
+            let mut derived = Vec::new();
+            for pos in candidates {
+                let assigned = self.cur_interpr[pos].is_truth_value();
+                let condition = if assigned {
+                    adf.ac[pos]
                 } else {
-                    // not stable
-                    log::trace!("2 val not stable");
-                    stack.push((false, cur_interpr.as_slice().into()));
-                    backtrack = true;
+                    self.cur_interpr[pos]
+                };
+                let restricted = self.dependencies[pos].iter().fold(condition, |acc, &dep| {
+                    let val = self.cur_interpr[dep];
+                    if val.is_truth_value() {
+                        adf.bdd.restrict(acc, Var(dep), val.is_true())
+                    } else {
+                        acc
+                    }
+                });
+                if restricted.is_truth_value() {
+                    let reason = self.dependencies[pos]
+                        .iter()
+                        .filter(|&&dep| self.cur_interpr[dep].is_truth_value())
+                        .map(|&dep| self.literal(dep))
+                        .collect::<Vec<_>>();
+                    if !assigned {
+                        derived.push((pos, restricted, reason));
+                    } else if restricted != self.cur_interpr[pos] {
+                        log::trace!("ac_inconsistency");
+                        let mut conflict = reason;
+                        if !conflict.contains(&self.literal(pos)) {
+                            conflict.push(self.literal(pos));
+                        }
+                        return Some(conflict);
+                    }
+                } else if !assigned {
+                    self.cur_interpr[pos] = restricted;
+                }
+            }
+            self.unchecked.clear();
+            self.ac_propagated = self.trail.len();
+            if derived.is_empty() {
+                return None;
+            }
+            for (pos, value, reason) in derived {
+                self.cur_interpr[pos] = value;
+                self.assign(pos, Some(reason));
+            }
+        }
+    }
+
+    /// Learns a [`NoGood`] from the conflict and jumps back to the decision level, where it concludes a new value.
+    /// Returns [false] if the conflict does not depend on any choice.
+    fn learn(&mut self, conflict: &[(usize, bool)]) -> bool {
+        let level = conflict
+            .iter()
+            .map(|&(pos, _)| self.level[pos])
+            .max()
+            .unwrap_or(0);
+        if level == 0 {
+            return false;
+        }
+        self.backjump(level);
+        let (mut learned, backjump) = self.analyze(conflict);
+        log::trace!(
+            "adding ng: {:?}, jumping back to level {}",
+            learned,
+            backjump
+        );
+        self.backjump(backjump);
+        // the unique implication point and the last assigned other statement are watched
+        let last = learned.len() - 1;
+        learned.swap(0, last);
+        if let Some(latest) = (1..learned.len()).max_by_key(|&idx| self.level[learned[idx].0]) {
+            learned.swap(1, latest);
+        }
+        let (uip, val) = learned[0];
+        self.ng_store.add_learned(learned.clone());
+        // the learned nogood concludes the negation of the unique implication point on the level, where the search has jumped back to
+        self.cur_interpr[uip] = Term::from(!val);
+        learned.remove(0);
+        self.assign(uip, Some(learned));
+        true
+    }
+
+    /// Resolves the conflict with the reasons of the current decision level, until a single assignment of this level is left.
+    /// Returns the minimised [`NoGood`] and the decision level, where it concludes the negation of this assignment.
+    fn analyze(&self, conflict: &[(usize, bool)]) -> (Vec<(usize, bool)>, usize) {
+        let current = self.level_starts.len();
+        let mut marks = vec![Mark::Unseen; self.cur_interpr.len()];
+        let mut learned = Vec::new();
+        let mut open = 0;
+        let mut literals = conflict.to_vec();
+        let mut idx = self.trail.len();
+        let uip = loop {
+            for &(pos, _) in literals.iter() {
+                if marks[pos] == Mark::Unseen && self.level[pos] > 0 {
+                    marks[pos] = Mark::Implied;
+                    if self.level[pos] == current {
+                        open += 1;
+                    } else {
+                        learned.push(pos);
+                    }
+                }
+            }
+            // the assignments of the current level are the last ones on the trail
+            let pos = loop {
+                idx -= 1;
+                if marks[self.trail[idx]] == Mark::Implied {
+                    break self.trail[idx];
+                }
+            };
+            marks[pos] = Mark::Unseen;
+            open -= 1;
+            if open == 0 {
+                break pos;
+            }
+            literals = self.reason[pos]
+                .clone()
+                .expect("Only the first assignment of a decision level is a choice");
+        };
+        learned.retain(|&pos| !self.redundant(pos, &mut marks));
+        let backjump = learned
+            .iter()
+            .map(|&pos| self.level[pos])
+            .max()
+            .unwrap_or(0);
+        let mut nogood = learned
+            .into_iter()
+            .map(|pos| self.literal(pos))
+            .collect::<Vec<_>>();
+        nogood.push(self.literal(uip));
+        (nogood, backjump)
+    }
+
+    /// Returns whether the value of the statement is implied by the other assignments of the learned [`NoGood`], i.e. whether each chain of reasons ends in them or in initial values.
+    fn redundant(&self, pos: usize, marks: &mut [Mark]) -> bool {
+        if self.reason[pos].is_none() {
+            return false;
+        }
+        // depth-first search with an explicit stack of the visited statements and their next reason
+        let mut stack = vec![(pos, 0)];
+        while let Some((current, next)) = stack.last_mut() {
+            let reason = self.reason[*current]
+                .as_ref()
+                .expect("Only statements with a reason are visited");
+            match reason.get(*next) {
+                Some(&(dep, _)) => {
+                    *next += 1;
+                    if self.level[dep] == 0 || marks[dep] == Mark::Implied {
+                        continue;
+                    }
+                    if marks[dep] == Mark::Failed || self.reason[dep].is_none() {
+                        for &(visited, _) in stack.iter().skip(1) {
+                            marks[visited] = Mark::Failed;
+                        }
+                        return false;
+                    }
+                    stack.push((dep, 0));
+                }
+                None => {
+                    let current = *current;
+                    stack.pop();
+                    marks[current] = Mark::Implied;
                 }
             }
         }
-        log::info!("{ng_store}");
-        log::debug!("{:?}", ng_store);
+        true
+    }
+
+    /// Reverts all assignments above the given decision level.
+    fn backjump(&mut self, level: usize) {
+        if let Some(&start) = self.level_starts.get(level) {
+            for pos in self.trail.drain(start..) {
+                self.cur_interpr[pos] = self.initial[pos];
+                self.reason[pos] = None;
+                // the acceptance conditions, which have been restricted by the reverted value, are restricted again by the next propagation
+                self.unchecked.push(pos);
+                for &dependent in &self.dependents[pos] {
+                    if !self.cur_interpr[dependent].is_truth_value() {
+                        self.cur_interpr[dependent] = self.initial[dependent];
+                        self.unchecked.push(dependent);
+                    }
+                }
+            }
+            self.level_starts.truncate(level);
+            self.ng_propagated = self.ng_propagated.min(start);
+            self.ac_propagated = self.ac_propagated.min(start);
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::adf::explanation::ConflictKind;
     use crossbeam_channel::unbounded;
     use test_log::test;
 
@@ -1020,13 +2714,85 @@ mod test {
         );
 
         let parser = AdfParser::default();
-        parser.parse()(
-            "s(a).s(b).s(c).s(d).s(e).ac(a,c(v)).ac(b,a).ac(c,b).ac(d,neg(c)).ac(e,and(a,d)).",
-        )
-        .unwrap();
+        parser.parse()(
+            "s(a).s(b).s(c).s(d).s(e).ac(a,c(v)).ac(b,a).ac(c,b).ac(d,neg(c)).ac(e,and(a,d)).",
+        )
+        .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let result = adf.grounded();
+        assert_eq!(result, vec![Term(1), Term(1), Term(1), Term(0), Term(0)]);
+    }
+
+    #[test]
+    fn dot() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).")
+            .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+
+        let dot = adf.ac_to_dot(&[Var(2)]);
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("[label=\"a\"];"));
+        assert!(dot.contains("[label=\"b\"];"));
+        assert!(dot.contains("r0 [label=\"c\", shape=plaintext];"));
+        assert!(!dot.contains("r1 "));
+
+        let grounded = adf.grounded();
+        let dot = adf.restricted_ac_to_dot(&grounded);
+        assert!(dot.contains("r3 [label=\"d\", shape=plaintext];"));
+        assert!(dot.contains("r0 -> t1;"));
+        assert!(!dot.contains("[label=\"a\"];"));
+        assert!(dot.contains("[label=\"b\"];"));
+    }
+
+    #[test]
+    fn statement_graph() {
+        let parser = AdfParser::default();
+        parser.parse()(
+            "s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,xor(neg(b),d)).",
+        )
+        .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+
+        let graph = adf.dependency_graph();
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            vec![(0, 2), (1, 1), (1, 2), (1, 3), (3, 3)]
+        );
+
+        let graph = adf.statement_graph(false, false);
+        assert_eq!(graph.statements[2].name, "c");
+        assert!(graph.statements.iter().all(|stm| stm.scc.is_none()));
+        assert!(graph.links.iter().all(|link| link.polarity.is_none()));
+
+        let graph = adf.statement_graph(true, true);
+        assert_eq!(
+            graph
+                .links
+                .iter()
+                .map(|link| link.polarity.unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                LinkPolarity::Support,
+                LinkPolarity::Support,
+                LinkPolarity::Support,
+                LinkPolarity::Dependent,
+                LinkPolarity::Dependent
+            ]
+        );
+        let scc = graph
+            .statements
+            .iter()
+            .map(|stm| stm.scc.unwrap())
+            .collect::<Vec<_>>();
+        assert!(scc[0] < scc[2] && scc[1] < scc[2] && scc[1] < scc[3]);
+        assert_eq!(adf.link_polarity(Var(1), Var(3)), LinkPolarity::Dependent);
+
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).ac(a,neg(b)).ac(b,c(f)).").unwrap();
         let mut adf = Adf::from_parser(&parser);
-        let result = adf.grounded();
-        assert_eq!(result, vec![Term(1), Term(1), Term(1), Term(0), Term(0)]);
+        assert_eq!(adf.link_polarity(Var(1), Var(0)), LinkPolarity::Attack);
+        assert!(adf.statement_graph(true, true).to_json().contains("attack"));
     }
 
     #[test]
@@ -1258,6 +3024,130 @@ mod test {
         solving.join().unwrap();
     }
 
+    #[test]
+    fn nogood_parallel() {
+        let instances = [
+            "s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).\ns(e).ac(e,and(b,or(neg(b),c(f)))).s(f).\n\nac(f,xor(a,e)).",
+            "s(a).s(b).s(c).s(d).s(e).ac(a,neg(b)).ac(b,neg(a)).ac(c,neg(d)).ac(d,neg(c)).ac(e,or(a,c)).",
+            "s(a).s(b).s(c).ac(a,iff(a,b)).ac(b,xor(a,c)).ac(c,neg(c)).",
+        ];
+        for instance in instances {
+            let parser = AdfParser::default();
+            parser.parse()(instance).unwrap();
+            let mut adf = Adf::from_parser(&parser);
+            let mut stable = adf.stable_nogood(Heuristic::Simple).collect::<Vec<_>>();
+            let (s, r) = unbounded();
+            adf.two_val_nogood_channel(Heuristic::Simple, s);
+            let mut two_val = r.iter().collect::<Vec<_>>();
+            stable.sort();
+            two_val.sort();
+
+            for (split_depth, threads) in [(0, 1), (1, 2), (3, 4), (8, 3)] {
+                let threads = NonZeroUsize::new(threads).unwrap();
+                let (s, r) = unbounded();
+                let handle =
+                    adf.stable_nogood_channel_parallel(Heuristic::Simple, split_depth, threads, s);
+                let mut result = r.iter().collect::<Vec<_>>();
+                handle.join().unwrap();
+                result.sort();
+                assert_eq!(result, stable, "{instance}");
+
+                let (s, r) = unbounded();
+                let handle = adf.two_val_nogood_channel_parallel(
+                    Heuristic::MinModMaxVarImpMinPaths,
+                    split_depth,
+                    threads,
+                    s,
+                );
+                let mut result = r.iter().collect::<Vec<_>>();
+                handle.join().unwrap();
+                result.sort();
+                assert_eq!(result, two_val, "{instance}");
+            }
+        }
+    }
+
+    #[test]
+    fn nogood_parallel_streaming() {
+        let instance = (0..20)
+            .map(|i| format!("s({i}).ac({i},{i})."))
+            .collect::<String>();
+        let parser = AdfParser::default();
+        parser.parse()(&instance).unwrap();
+        let mut adf = Adf::from_parser(&parser);
+
+        // the models are streamed, while the workers are still searching
+        let (s, r) = crossbeam_channel::bounded(0);
+        let handle = adf.two_val_nogood_channel_parallel(
+            Heuristic::Simple,
+            2,
+            NonZeroUsize::new(2).unwrap(),
+            s,
+        );
+        assert_eq!(r.iter().take(3).count(), 3);
+        assert!(!handle.is_finished());
+
+        // the workers stop, once the receiver is gone
+        drop(r);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn thread_safety() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Adf>();
+        assert_send_sync::<Bdd>();
+
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).s(e).ac(a,neg(b)).ac(b,neg(a)).ac(c,neg(d)).ac(d,neg(c)).ac(e,or(a,c)).")
+            .unwrap();
+        let adf = std::sync::Arc::new(Adf::from_parser(&parser));
+        let counts = adf.formulacounts(true);
+
+        let handles = (0..4)
+            .map(|_| {
+                let adf = std::sync::Arc::clone(&adf);
+                std::thread::spawn(move || {
+                    let counts = adf.formulacounts(true);
+                    let mut worker = (*adf).clone();
+                    let mut stable = worker.stable_nogood(Heuristic::Simple).collect::<Vec<_>>();
+                    stable.sort();
+                    (counts, stable)
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            let (thread_counts, stable) = handle.join().unwrap();
+            assert_eq!(thread_counts, counts);
+            assert_eq!(stable.len(), 4);
+        }
+        assert_eq!(adf.formulacounts(true), counts);
+
+        // clones of a frozen ADF share its nodes and only add their own ones
+        let mut adf = std::sync::Arc::try_unwrap(adf).unwrap();
+        adf.freeze();
+        let nodes = adf.bdd.nodes.clone();
+        let adf = std::sync::Arc::new(adf);
+        let handles = (0..4)
+            .map(|_| {
+                let adf = std::sync::Arc::clone(&adf);
+                std::thread::spawn(move || {
+                    let mut worker = (*adf).clone();
+                    let mut two_valued = worker.two_valued_sat().collect::<Vec<_>>();
+                    two_valued.sort();
+                    let representation = worker.two_valued_representation();
+                    (two_valued, worker.exact_facet_count(representation))
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            let (two_valued, counts) = handle.join().unwrap();
+            assert_eq!(two_valued.len(), 4);
+            assert_eq!(counts.map(|(count, _)| count), Ok(4));
+        }
+        assert_eq!(adf.bdd.nodes, nodes);
+    }
+
     #[test]
     fn rand_stable_heu() {
         let parser = AdfParser::default();
@@ -1277,6 +3167,193 @@ mod test {
         assert_eq!(result, vec![vec![Term(1), Term(0)], vec![Term(0), Term(1)]]);
     }
 
+    #[test]
+    fn sampling() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,c).").unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let seed = [
+            122, 186, 240, 42, 235, 102, 89, 81, 187, 203, 127, 188, 167, 198, 126, 156, 25, 205,
+            204, 132, 112, 93, 23, 193, 21, 108, 166, 231, 158, 250, 128, 135,
+        ];
+
+        let mut rng = StdRng::from_seed(seed);
+        let two_valued = adf.sample_two_valued(50, &mut rng).unwrap();
+        assert_eq!(two_valued.len(), 50);
+        let mut distinct = two_valued.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 4);
+        let mut rng = StdRng::from_seed(seed);
+        assert_eq!(adf.sample_two_valued(50, &mut rng), Ok(two_valued));
+
+        adf.seed(seed);
+        let stable = adf.sample_stable(20).unwrap();
+        assert_eq!(stable.len(), 20);
+        assert!(stable.iter().all(|model| !model[2].is_true()));
+        adf.seed(seed);
+        assert_eq!(adf.sample_stable(20), Ok(stable));
+
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).ac(a,a).ac(b,neg(b)).").unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        assert_eq!(adf.sample_stable(5), Ok(Vec::new()));
+
+        // the models of more than 2^128 interpretations are sampled exactly
+        let parser = AdfParser::default();
+        let input = (0..200)
+            .map(|i| format!("s(a{i}).ac(a{i},a{i})."))
+            .collect::<String>();
+        parser.parse()(&input).unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let mut rng = StdRng::from_seed(seed);
+        let two_valued = adf.sample_two_valued(2, &mut rng).unwrap();
+        assert_eq!(two_valued.len(), 2);
+        assert!(two_valued.iter().all(|model| model.len() == 200));
+    }
+
+    #[test]
+    fn stable_nogood_lazy() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).ac(a,neg(b)).ac(b,neg(a)).ac(c,neg(d)).ac(d,neg(c)).")
+            .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        static CHOICES: AtomicUsize = AtomicUsize::new(0);
+        let counting_heu = |adf: &Adf, interpr: &[Term]| {
+            CHOICES.fetch_add(1, Ordering::Relaxed);
+            heuristics::heu_simple(adf, interpr)
+        };
+
+        let first = adf.stable_nogood(Heuristic::Custom(&counting_heu)).next();
+        assert!(first.is_some());
+        let choices_first = CHOICES.swap(0, Ordering::Relaxed);
+
+        let all = adf
+            .stable_nogood(Heuristic::Custom(&counting_heu))
+            .collect::<Vec<_>>();
+        assert_eq!(all.len(), 4);
+        assert_eq!(first, all.first().cloned());
+        assert!(choices_first < CHOICES.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn budgeted() {
+        use crate::datatypes::budget::CancellationToken;
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).\ns(e).ac(e,and(b,or(neg(b),c(f)))).s(f).\n\nac(f,xor(a,e)).")
+            .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+
+        let complete = adf.complete().collect::<Vec<_>>();
+        assert_eq!(
+            adf.complete_budgeted(Budget::new())
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(complete)
+        );
+        let stable = adf.stable().collect::<Vec<_>>();
+        assert_eq!(
+            adf.stable_budgeted(Budget::new())
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(stable.clone())
+        );
+        assert_eq!(
+            adf.stable_nogood_budgeted(Heuristic::Simple, Budget::new())
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(stable.clone())
+        );
+        assert_eq!(
+            adf.stable_with_prefilter_budgeted(Budget::new())
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(stable.clone())
+        );
+        assert_eq!(
+            adf.stable_count_optimisation_heu_a_budgeted(Budget::new())
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(adf.stable_count_optimisation_heu_a().collect())
+        );
+        assert_eq!(
+            adf.stable_count_optimisation_heu_b_budgeted(Budget::new())
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(adf.stable_count_optimisation_heu_b().collect())
+        );
+        let grounded = adf.grounded();
+        assert_eq!(adf.grounded_budgeted(&Budget::new()), Ok(grounded));
+        let (s, r) = unbounded();
+        let handle = adf.stable_nogood_channel_parallel_budgeted(
+            Heuristic::Simple,
+            2,
+            NonZeroUsize::new(2).unwrap(),
+            s,
+            &Budget::new(),
+        );
+        let mut parallel = r.iter().collect::<Vec<_>>();
+        assert_eq!(handle.join().unwrap(), Ok(()));
+        parallel.sort();
+        let mut expected = stable.clone();
+        expected.sort();
+        assert_eq!(parallel, expected);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let budget = Budget::new().with_token(token);
+        assert_eq!(
+            adf.complete_budgeted(budget.clone()).collect::<Vec<_>>(),
+            vec![Err(Interrupted::Cancelled)]
+        );
+        assert_eq!(
+            adf.stable_budgeted(budget.clone()).collect::<Vec<_>>(),
+            vec![Err(Interrupted::Cancelled)]
+        );
+        assert_eq!(
+            adf.stable_nogood_budgeted(Heuristic::Simple, budget.clone())
+                .collect::<Vec<_>>(),
+            vec![Err(Interrupted::Cancelled)]
+        );
+        let (s, r) = unbounded();
+        assert_eq!(
+            adf.two_val_nogood_channel_budgeted(Heuristic::Simple, s, &budget),
+            Err(Interrupted::Cancelled)
+        );
+        assert_eq!(r.try_iter().count(), 0);
+        assert_eq!(adf.grounded_budgeted(&budget), Err(Interrupted::Cancelled));
+        assert_eq!(
+            adf.stable_with_prefilter_budgeted(budget.clone())
+                .collect::<Vec<_>>(),
+            vec![Err(Interrupted::Cancelled)]
+        );
+        assert_eq!(
+            adf.stable_count_optimisation_heu_a_budgeted(budget.clone())
+                .collect::<Vec<_>>(),
+            vec![Err(Interrupted::Cancelled)]
+        );
+        assert_eq!(
+            adf.stable_count_optimisation_heu_b_budgeted(budget.clone())
+                .collect::<Vec<_>>(),
+            vec![Err(Interrupted::Cancelled)]
+        );
+        let (s, r) = unbounded();
+        assert_eq!(
+            adf.two_val_nogood_channel_parallel_budgeted(
+                Heuristic::Simple,
+                2,
+                NonZeroUsize::new(2).unwrap(),
+                s,
+                &budget
+            )
+            .join()
+            .unwrap(),
+            Err(Interrupted::Cancelled)
+        );
+        assert_eq!(r.try_iter().count(), 0);
+
+        let budget = Budget::new().with_node_limit(0);
+        assert_eq!(
+            adf.complete_budgeted(budget).last(),
+            Some(Err(Interrupted::NodeLimit))
+        );
+    }
+
     #[test]
     fn complete() {
         let parser = AdfParser::default();
@@ -1299,6 +3376,192 @@ mod test {
         );
     }
 
+    #[test]
+    fn counts() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).ac(a,neg(b)).ac(b,neg(a)).ac(c,c).ac(d,or(a,c)).")
+            .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+
+        assert_eq!(adf.complete_count(None), adf.complete().count());
+        assert_eq!(adf.complete_count(Some(2)), 2);
+        assert_eq!(adf.stable_count(None), 2);
+        assert_eq!(adf.stable_count(Some(1)), 1);
+        assert_eq!(adf.stable_count(Some(0)), 0);
+        let (s, r) = unbounded();
+        adf.two_val_nogood_channel(Heuristic::Simple, s);
+        assert_eq!(adf.two_valued_count(), Ok(r.iter().count() as u128));
+        assert_eq!(adf.two_valued_count(), Ok(4));
+    }
+
+    #[test]
+    fn decomposed() {
+        let instances = [
+            "s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).\ns(e).ac(e,and(b,or(neg(b),c(f)))).s(f).\n\nac(f,xor(a,e)).",
+            "s(a).s(b).s(c).s(d).ac(a,neg(b)).ac(b,neg(a)).ac(c,and(neg(a),d)).ac(d,or(c,neg(b))).",
+            "s(a).s(b).s(c).s(d).s(e).ac(a,neg(b)).ac(b,neg(c)).ac(c,neg(a)).ac(d,neg(d)).ac(e,or(a,neg(d))).",
+            "s(a).s(b).s(c).ac(a,iff(a,b)).ac(b,xor(a,c)).ac(c,neg(c)).",
+        ];
+        for instance in instances {
+            let parser = AdfParser::default();
+            parser.parse()(instance).unwrap();
+            let mut adf = Adf::from_parser(&parser);
+
+            let mut complete = adf.complete().collect::<Vec<_>>();
+            let mut complete_decomposed = adf.complete_decomposed().collect::<Vec<_>>();
+            complete.sort();
+            complete_decomposed.sort();
+            assert_eq!(complete, complete_decomposed, "{instance}");
+
+            // the preferred models are the complete ones, which are not less informative than another one
+            let less_informative = |less: &[Term], more: &[Term]| {
+                less != more
+                    && less
+                        .iter()
+                        .zip(more.iter())
+                        .all(|(val, other)| !val.is_truth_value() || val == other)
+            };
+            let preferred = complete
+                .iter()
+                .filter(|model| !complete.iter().any(|other| less_informative(model, other)))
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut preferred_decomposed = adf.preferred_decomposed().collect::<Vec<_>>();
+            preferred_decomposed.sort();
+            assert_eq!(preferred, preferred_decomposed, "{instance}");
+
+            let mut stable = adf.stable().collect::<Vec<_>>();
+            let mut stable_decomposed = adf.stable_decomposed().collect::<Vec<_>>();
+            stable.sort();
+            stable_decomposed.sort();
+            assert_eq!(stable, stable_decomposed, "{instance}");
+        }
+
+        // a long chain of components is solved without recursion, and the models are enumerated lazily
+        let instance = (1..2000).fold("s(a0).ac(a0,a0).".to_string(), |acc, idx| {
+            format!("{acc}s(a{idx}).ac(a{idx},a{}).", idx - 1)
+        });
+        let parser = AdfParser::default();
+        parser.parse()(&instance).unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        assert_eq!(adf.complete_decomposed().count(), 3);
+        assert_eq!(adf.preferred_decomposed().count(), 2);
+        let stable = adf.stable_decomposed().collect::<Vec<_>>();
+        assert_eq!(stable, vec![vec![Term::BOT; 2000]]);
+        assert!(adf.complete_decomposed().next().is_some());
+    }
+
+    #[test]
+    fn sat_backend() {
+        let instances = [
+            "s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).\ns(e).ac(e,and(b,or(neg(b),c(f)))).s(f).\n\nac(f,xor(a,e)).",
+            "s(a).s(b).s(c).s(d).ac(a,neg(b)).ac(b,neg(a)).ac(c,and(neg(a),d)).ac(d,or(c,neg(b))).",
+            "s(a).s(b).s(c).s(d).s(e).ac(a,neg(b)).ac(b,neg(c)).ac(c,neg(a)).ac(d,neg(d)).ac(e,or(a,neg(d))).",
+            "s(a).s(b).s(c).ac(a,iff(a,b)).ac(b,xor(a,c)).ac(c,neg(c)).",
+            "s(a).s(b).s(c).s(d).ac(a,b).ac(b,a).ac(c,or(a,neg(d))).ac(d,neg(c)).",
+        ];
+        for instance in instances {
+            let parser = AdfParser::default();
+            parser.parse()(instance).unwrap();
+            let mut adf = Adf::from_parser(&parser);
+
+            let mut two_valued = adf
+                .complete()
+                .filter(|model| model.iter().all(Term::is_truth_value))
+                .collect::<Vec<_>>();
+            let mut two_valued_sat = adf.two_valued_sat().collect::<Vec<_>>();
+            two_valued.sort();
+            two_valued_sat.sort();
+            assert_eq!(two_valued, two_valued_sat, "{instance}");
+
+            let mut stable = adf.stable().collect::<Vec<_>>();
+            let mut stable_sat = adf.stable_sat().collect::<Vec<_>>();
+            stable.sort();
+            stable_sat.sort();
+            assert_eq!(stable, stable_sat, "{instance}");
+        }
+
+        // a chain of n mutual attacks has 2^n stable models
+        let instance = (0..10)
+            .map(|idx| {
+                format!("s(a{idx}).s(b{idx}).ac(a{idx},neg(b{idx})).ac(b{idx},neg(a{idx})).")
+            })
+            .collect::<String>();
+        let parser = AdfParser::default();
+        parser.parse()(&instance).unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        assert_eq!(adf.stable_sat().count(), 1024);
+    }
+
+    #[test]
+    fn nogood_learning() {
+        fn formula(rng: &mut StdRng, statements: usize, depth: usize) -> String {
+            if depth == 0 || rng.gen_bool(0.3) {
+                let statement = format!("s{}", rng.gen_range(0..statements));
+                return if rng.gen_bool(0.6) {
+                    statement
+                } else {
+                    format!("neg({statement})")
+                };
+            }
+            let connective = ["and", "or", "imp", "iff", "xor"][rng.gen_range(0..5)];
+            format!(
+                "{connective}({},{})",
+                formula(rng, statements, depth - 1),
+                formula(rng, statements, depth - 1)
+            )
+        }
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..40 {
+            let statements = rng.gen_range(2..=9);
+            let instance = (0..statements)
+                .map(|idx| format!("s(s{idx}).ac(s{idx},{}).", formula(&mut rng, statements, 3)))
+                .collect::<String>();
+            let parser = AdfParser::default();
+            parser.parse()(&instance).unwrap();
+            let mut adf = Adf::from_parser(&parser);
+
+            let mut stable = adf.stable().collect::<Vec<_>>();
+            stable.sort();
+            // the sat backend excludes the candidates by their unjustified statements
+            let mut stable_sat = adf.stable_sat().collect::<Vec<_>>();
+            stable_sat.sort();
+            assert_eq!(stable, stable_sat, "{instance}");
+            let mut two_valued = adf
+                .complete()
+                .filter(|model| model.iter().all(Term::is_truth_value))
+                .collect::<Vec<_>>();
+            two_valued.sort();
+            for heuristic in [
+                Heuristic::Simple,
+                Heuristic::MinModMinPathsMaxVarImp,
+                Heuristic::MinModMaxVarImpMinPaths,
+            ] {
+                let mut stable_nogood = adf.stable_nogood(heuristic).collect::<Vec<_>>();
+                stable_nogood.sort();
+                assert_eq!(stable, stable_nogood, "{instance}");
+
+                let (s, r) = unbounded();
+                adf.two_val_nogood_channel(heuristic, s);
+                let mut two_valued_nogood = r.iter().collect::<Vec<_>>();
+                two_valued_nogood.sort();
+                assert_eq!(two_valued, two_valued_nogood, "{instance}");
+            }
+        }
+
+        // a chain of n mutual attacks has 2^n stable models
+        let instance = (0..10)
+            .map(|idx| {
+                format!("s(a{idx}).s(b{idx}).ac(a{idx},neg(b{idx})).ac(b{idx},neg(a{idx})).")
+            })
+            .collect::<String>();
+        let parser = AdfParser::default();
+        parser.parse()(&instance).unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        assert_eq!(adf.stable_nogood(Heuristic::Simple).count(), 1024);
+    }
+
     #[test]
     fn complete2() {
         let parser = AdfParser::default();
@@ -1335,6 +3598,290 @@ mod test {
         let _adf = Adf::default();
     }
 
+    #[test]
+    fn editing() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,c(v)).").unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let mut shared = adf.clone();
+
+        let and_cd = Formula::And(Box::new(Formula::Atom("c")), Box::new(Formula::Atom("d")));
+        assert_eq!(adf.add_statement("d", &and_cd), Ok(Var(3)));
+        assert_eq!(
+            adf.add_statement("a", &Formula::Top),
+            Err(EditError::DuplicateStatement("a".to_string()))
+        );
+        assert_eq!(
+            adf.add_statement("e", &Formula::Atom("x")),
+            Err(EditError::UnknownStatement("x".to_string()))
+        );
+        assert_eq!(
+            adf.set_acceptance_condition("x", &Formula::Top),
+            Err(EditError::UnknownStatement("x".to_string()))
+        );
+        adf.set_acceptance_condition("a", &Formula::Top).unwrap();
+        assert_eq!(
+            adf.remove_statement("a"),
+            Err(EditError::StillReferenced {
+                statement: "a".to_string(),
+                referenced_by: vec!["b".to_string()]
+            })
+        );
+        adf.remove_statement("b").unwrap();
+        assert_eq!(adf.ac.len(), 3);
+
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(c).s(d).ac(a,c(v)).ac(c,c(v)).ac(d,and(c,d)).").unwrap();
+        let mut rebuilt = Adf::from_parser(&parser);
+        let grounded = adf.grounded();
+        assert_eq!(
+            adf.print_interpretation(&grounded).to_string(),
+            "T(a) T(c) u(d) \n"
+        );
+        assert_eq!(grounded, rebuilt.grounded());
+        let stable = adf.stable().collect::<Vec<_>>();
+        assert_eq!(stable, rebuilt.stable().collect::<Vec<_>>());
+        assert_eq!(
+            adf.print_interpretation(&stable[0]).to_string(),
+            "T(a) T(c) F(d) \n"
+        );
+
+        // revisions do not affect other instances, which share the dictionary
+        let grounded = shared.grounded();
+        assert_eq!(
+            shared.print_interpretation(&grounded).to_string(),
+            "u(a) u(b) T(c) \n"
+        );
+    }
+
+    #[test]
+    fn explain_grounded() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).s(e).ac(a,c(v)).ac(b,neg(a)).ac(c,or(b,d)).ac(d,d).ac(e,and(a,neg(b))).")
+            .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let explanation = adf.explain_grounded();
+
+        assert_eq!(explanation.grounded, adf.grounded());
+        assert_eq!(
+            explanation.derivations,
+            vec![
+                Derivation {
+                    statement: Var(0),
+                    value: Term::TOP,
+                    iteration: 0,
+                    reasons: vec![]
+                },
+                Derivation {
+                    statement: Var(1),
+                    value: Term::BOT,
+                    iteration: 1,
+                    reasons: vec![Var(0)]
+                },
+                Derivation {
+                    statement: Var(4),
+                    value: Term::TOP,
+                    iteration: 2,
+                    reasons: vec![Var(0), Var(1)]
+                },
+            ]
+        );
+        assert_eq!(explanation.derivation(Var(4)).unwrap().iteration, 2);
+        assert!(explanation.derivation(Var(2)).is_none());
+
+        assert_eq!(explanation.witnesses.len(), 2);
+        for witness in explanation.witnesses.iter() {
+            assert!(!explanation.grounded[witness.statement.value()].is_truth_value());
+            let ac = adf.ac[witness.statement.value()];
+            for (completion, expected) in [(&witness.accepting, true), (&witness.rejecting, false)]
+            {
+                // the completions agree with the grounded model
+                assert!(explanation
+                    .grounded
+                    .iter()
+                    .zip(completion.iter())
+                    .all(|(gr, co)| !gr.is_truth_value() || gr == co));
+                let value = completion.iter().enumerate().fold(ac, |acc, (var, term)| {
+                    adf.bdd.restrict(acc, Var(var), term.is_true())
+                });
+                assert_eq!(value, Term::from(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn explain_no_stable() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).ac(a,neg(b)).ac(b,neg(a)).").unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        assert_eq!(adf.explain_no_stable(), None);
+        let mut models = adf.stable_nogood_explained(Heuristic::Simple);
+        assert_eq!(
+            models.by_ref().collect::<Vec<_>>(),
+            vec![vec![Term(1), Term(0)], vec![Term(0), Term(1)]]
+        );
+        assert_eq!(models.explanation(), None);
+
+        // the only two valued model accepts b, which is only self-supported
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,and(neg(a),neg(b))).ac(b,b).ac(c,c(v)).").unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        assert_eq!(adf.stable().next(), None);
+        let mut models = adf.stable_nogood_explained(Heuristic::Simple);
+        assert_eq!(models.next(), None);
+        let conflict = models.explanation().unwrap();
+        assert_eq!(Some(conflict.clone()), adf.explain_no_stable());
+        assert_eq!(conflict.kind, ConflictKind::Unjustified);
+        assert_eq!(conflict.core, vec![Var(1)]);
+
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,neg(a)).ac(b,c).ac(c,b).").unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let conflict = adf.explain_no_stable().unwrap();
+        assert_eq!(conflict.kind, ConflictKind::Inconsistent);
+        assert_eq!(conflict.core, vec![Var(0)]);
+        assert_eq!(
+            conflict.to_text(&adf.print_dictionary()),
+            "No stable model exists, as the acceptance conditions of a contradict each other\n"
+        );
+    }
+
+    #[test]
+    fn navigate() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).ac(a,neg(b)).ac(b,neg(a)).ac(c,c).ac(d,or(a,c)).")
+            .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+
+        for semantics in [
+            NavigationSemantics::Complete,
+            NavigationSemantics::Stable,
+            NavigationSemantics::TwoValued,
+        ] {
+            let models: Vec<Vec<Term>> = match semantics {
+                NavigationSemantics::Complete => adf.complete().collect(),
+                NavigationSemantics::Stable => adf.stable().collect(),
+                NavigationSemantics::TwoValued => {
+                    let (s, r) = unbounded();
+                    adf.two_val_nogood_channel(Heuristic::Simple, s);
+                    r.iter().collect()
+                }
+            };
+            let navigation = adf.navigate(semantics, &[]).unwrap();
+            assert_eq!(navigation, Navigation::from_models(4, &models));
+
+            // fixing each facet shrinks the model space to its count
+            for count in navigation.facets.iter() {
+                let step = adf.navigate(semantics, &[count.facet]).unwrap();
+                assert_eq!(step.model_count, count.model_count);
+                assert!(step.model_count < navigation.model_count);
+            }
+        }
+
+        let navigation = adf
+            .navigate(
+                NavigationSemantics::Stable,
+                &[Facet::new(Var(0), Term::TOP)],
+            )
+            .unwrap();
+        assert_eq!(navigation.model_count, 1);
+        assert!(navigation.facets.is_empty());
+        let navigation = adf
+            .navigate(
+                NavigationSemantics::TwoValued,
+                &[Facet::new(Var(0), Term::UND)],
+            )
+            .unwrap();
+        assert_eq!(navigation, Navigation::default());
+    }
+
+    #[test]
+    fn navigate_exact() {
+        let instance = |statements: usize| {
+            let parser = AdfParser::default();
+            let input = (0..statements)
+                .map(|i| format!("s(a{i}).ac(a{i},a{i})."))
+                .collect::<String>();
+            parser.parse()(&input).unwrap();
+            Adf::from_parser(&parser)
+        };
+
+        let mut adf = instance(70);
+        let navigation = adf.navigate(NavigationSemantics::TwoValued, &[]).unwrap();
+        assert_eq!(navigation.model_count, 1 << 70);
+        assert_eq!(navigation.facets.len(), 140);
+        assert!(navigation
+            .facets
+            .iter()
+            .all(|count| count.model_count == 1 << 69));
+        let navigation = adf
+            .navigate(
+                NavigationSemantics::TwoValued,
+                &[Facet::new(Var(3), Term::BOT)],
+            )
+            .unwrap();
+        assert_eq!(navigation.model_count, 1 << 69);
+        assert_eq!(navigation.facets.len(), 138);
+
+        let mut adf = instance(128);
+        assert_eq!(
+            adf.navigate(NavigationSemantics::TwoValued, &[]),
+            Err(NavigationError::CountOverflow)
+        );
+        assert_eq!(
+            adf.navigate(
+                NavigationSemantics::TwoValued,
+                &[Facet::new(Var(0), Term::TOP)]
+            )
+            .unwrap()
+            .model_count,
+            1 << 127
+        );
+    }
+
+    #[test]
+    fn cardinality() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).ac(a,atmost(1,b,c,d)).ac(b,exactly(2,a,c,neg(d))).ac(c,atleast(2,a,b,c,d)).ac(d,nor(a,b,c)).")
+            .unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let formula = |input: &str| {
+            let parser = AdfParser::default();
+            let input = format!("s(a).s(b).s(c).s(d).ac(a,{input}).");
+            parser.parse()(&input).unwrap();
+            parser.ac_at(0).unwrap()
+        };
+
+        for (cardinality, expanded) in [
+            ("atleast(2,a,b,c)", "or(and(a,b),or(and(a,c),and(b,c)))"),
+            ("atmost(1,a,b,c)", "neg(or(and(a,b),or(and(a,c),and(b,c))))"),
+            (
+                "exactly(1,a,b,c)",
+                "and(or(a,or(b,c)),neg(or(and(a,b),or(and(a,c),and(b,c)))))",
+            ),
+            ("exactly(0,a,b)", "nor(a,b)"),
+            ("atleast(3,a,b,c,d)", "neg(atmost(2,d,c,b,a))"),
+            ("atleast(1,a,neg(a))", "c(v)"),
+            ("atleast(5,a,b,c,d)", "c(f)"),
+            ("atmost(4,a,b,c,d)", "c(v)"),
+            ("nand(a,b,c,d)", "or(neg(a),neg(b),neg(c),neg(d))"),
+        ] {
+            let (cardinality, expanded) = (formula(cardinality), formula(expanded));
+            assert_eq!(
+                adf.term(&cardinality.as_formula()),
+                adf.term(&expanded.as_formula()),
+                "{cardinality:?} differs from {expanded:?}"
+            );
+        }
+
+        let bio_adf = crate::adfbiodivine::Adf::from_parser(&parser);
+        let mut from_bio = Adf::from_biodivine(&bio_adf);
+        assert_eq!(
+            adf.stable().collect::<Vec<_>>(),
+            from_bio.stable().collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn facet_counts() {
         let parser = AdfParser::default();
@@ -0,0 +1,451 @@
+/*!
+This module contains the export of an [ADF][super::Adf] into an answer set program, which can be solved by clingo.
+
+The statements and acceptance conditions are written as facts `s/1` and `ac/2`, as in the representation of DIAMOND.
+Instead of a formula, each acceptance condition refers to a node of the [Bdd], whose evaluation is given by ground rules.
+These rules derive `mt(C,N)` and `mf(C,N)`, if the node `N` is true or false respectively in all completions of the interpretation `t(C,_)`, `f(C,_)` of the context `C`.
+Together with the encoding of the chosen semantics, the answer sets of the program are exactly the models of the [ADF][super::Adf] and are shown as `t/1`, `f/1`, and `u/1`.
+
+# Example
+```
+use adf_bdd::adf::{asp::AspSemantics, Adf};
+use adf_bdd::parser::AdfParser;
+
+let parser = AdfParser::default();
+parser.parse()("s(a).s(b).ac(a,c(v)).ac(b,neg(a)).").unwrap();
+let adf = Adf::from_parser(&parser);
+let program = adf.to_asp(AspSemantics::Grounded);
+assert!(program.starts_with("% semantics Grounded\n"));
+assert!(program.contains("s(\"b\"). ac(\"b\","));
+```
+ */
+use std::fmt::Write;
+
+use crate::{datatypes::Term, obdd::Bdd};
+
+/// Semantics, whose models are the answer sets of the exported program.
+#[derive(
+    strum::Display,
+    strum::EnumString,
+    strum::EnumVariantNames,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum AspSemantics {
+    /// The grounded model, which is the only answer set of a positive program.
+    Grounded,
+    /// Admissible interpretations, where a statement might be undecided.
+    Admissible,
+    /// Complete models, where a statement might be undecided.
+    Complete,
+    /// Two valued models.
+    TwoValued,
+    /// Stable models.
+    Stable,
+}
+
+impl AspSemantics {
+    /// Returns the encoding of the semantics, which refers to the facts and the evaluation of the acceptance conditions.
+    fn encoding(self) -> &'static str {
+        match self {
+            Self::Grounded => {
+                "% grounded: least fixpoint of the characteristic operator
+ctx(m).
+t(m,S) :- ac(S,N), mt(m,N).
+f(m,S) :- ac(S,N), mf(m,N).
+"
+            }
+            Self::Admissible => {
+                "% admissible: each decided value is justified by all completions
+ctx(m).
+1 { t(m,S); f(m,S); u(m,S) } 1 :- s(S).
+:- t(m,S), ac(S,N), not mt(m,N).
+:- f(m,S), ac(S,N), not mf(m,N).
+"
+            }
+            Self::Complete => {
+                "% complete: admissible, and each justified value is decided
+ctx(m).
+1 { t(m,S); f(m,S); u(m,S) } 1 :- s(S).
+:- t(m,S), ac(S,N), not mt(m,N).
+:- f(m,S), ac(S,N), not mf(m,N).
+:- u(m,S), ac(S,N), mt(m,N).
+:- u(m,S), ac(S,N), mf(m,N).
+"
+            }
+            Self::TwoValued => {
+                "% two valued: each statement has the value of its acceptance condition
+ctx(m).
+1 { t(m,S); f(m,S) } 1 :- s(S).
+:- t(m,S), ac(S,N), not mt(m,N).
+:- f(m,S), ac(S,N), not mf(m,N).
+"
+            }
+            Self::Stable => {
+                "% stable: two valued, and the true statements are grounded in the reduct
+ctx(m). ctx(r).
+1 { t(m,S); f(m,S) } 1 :- s(S).
+:- t(m,S), ac(S,N), not mt(m,N).
+:- f(m,S), ac(S,N), not mf(m,N).
+f(r,S) :- f(m,S).
+t(r,S) :- t(m,S), ac(S,N), mt(r,N).
+:- t(m,S), not t(r,S).
+"
+            }
+        }
+    }
+}
+
+/// Writes the program for the given statements and their acceptance conditions in the [Bdd].
+pub(crate) fn program(names: &[String], ac: &[Term], bdd: &Bdd, semantics: AspSemantics) -> String {
+    let mut result = String::new();
+    let name = |idx: usize| format!("\"{}\"", escape(&names[idx]));
+    let mut line = |text: String| writeln!(result, "{text}").expect("Writing into a String works");
+
+    line(format!("% semantics {semantics}"));
+    line("% statements and acceptance conditions".to_string());
+    for (idx, condition) in ac.iter().enumerate() {
+        line(format!(
+            "s({}). ac({},{}).",
+            name(idx),
+            name(idx),
+            condition.value()
+        ));
+    }
+    line("% evaluation of the acceptance conditions".to_string());
+    line("mt(C,1) :- ctx(C).".to_string());
+    line("mf(C,0) :- ctx(C).".to_string());
+    for node in bdd.reachable(ac) {
+        if node.is_truth_value() {
+            continue;
+        }
+        let bdd_node = bdd.nodes[node.value()];
+        let (node, var, lo, hi) = (
+            node.value(),
+            name(bdd_node.var().value()),
+            bdd_node.lo().value(),
+            bdd_node.hi().value(),
+        );
+        for must in ["mt", "mf"] {
+            line(format!(
+                "{must}(C,{node}) :- {must}(C,{lo}), {must}(C,{hi})."
+            ));
+            line(format!("{must}(C,{node}) :- t(C,{var}), {must}(C,{hi})."));
+            line(format!("{must}(C,{node}) :- f(C,{var}), {must}(C,{lo})."));
+        }
+    }
+    result.push_str(semantics.encoding());
+    result.push_str(
+        "#show t(S) : t(m,S).
+#show f(S) : f(m,S).
+#show u(S) : s(S), not t(m,S), not f(m,S).
+",
+    );
+    result
+}
+
+/// Escapes a statement name for a string constant of clingo.
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::datatypes::adf::{
+        ThreeValuedInterpretationsIterator, TwoValuedInterpretationsIterator,
+    };
+    use crate::{
+        adf::{builder::AdfBuilder, Adf},
+        parser::{AdfParser, OwnedFormula},
+        test::normalised,
+    };
+    use std::collections::HashSet;
+    use test_log::test;
+
+    #[test]
+    fn program() {
+        let mut bdd = Bdd::new();
+        let a = bdd.variable(crate::datatypes::Var(0));
+        let not_a = bdd.not(a);
+        let node = not_a.value();
+        let names = vec!["a".to_string(), "b\\c".to_string()];
+        let result = super::program(&names, &[Term::TOP, not_a], &bdd, AspSemantics::Grounded);
+        assert_eq!(
+            result,
+            format!(
+                "% semantics Grounded
+% statements and acceptance conditions
+s(\"a\"). ac(\"a\",1).
+s(\"b\\\\c\"). ac(\"b\\\\c\",{node}).
+% evaluation of the acceptance conditions
+mt(C,1) :- ctx(C).
+mf(C,0) :- ctx(C).
+mt(C,{node}) :- mt(C,1), mt(C,0).
+mt(C,{node}) :- t(C,\"a\"), mt(C,0).
+mt(C,{node}) :- f(C,\"a\"), mt(C,1).
+mf(C,{node}) :- mf(C,1), mf(C,0).
+mf(C,{node}) :- t(C,\"a\"), mf(C,0).
+mf(C,{node}) :- f(C,\"a\"), mf(C,1).
+{}#show t(S) : t(m,S).
+#show f(S) : f(m,S).
+#show u(S) : s(S), not t(m,S), not f(m,S).
+",
+                AspSemantics::Grounded.encoding()
+            )
+        );
+        for semantics in [
+            AspSemantics::Admissible,
+            AspSemantics::Complete,
+            AspSemantics::TwoValued,
+            AspSemantics::Stable,
+        ] {
+            assert!(super::program(&names, &[Term::TOP, not_a], &bdd, semantics)
+                .contains(semantics.encoding()));
+        }
+    }
+
+    /// Predicate and arguments of an atom in the program.
+    type Atom = (String, Vec<String>);
+
+    /// Splits a line of the program into its atoms, where the arguments of string constants are unescaped.
+    fn atoms(line: &str) -> Vec<Atom> {
+        let mut result = Vec::new();
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !c.is_ascii_lowercase() {
+                continue;
+            }
+            let mut predicate = c.to_string();
+            while let Some(c) = chars.next_if(char::is_ascii_alphanumeric) {
+                predicate.push(c);
+            }
+            if chars.next_if_eq(&'(').is_none() {
+                continue;
+            }
+            let mut args = vec![String::new()];
+            while let Some(c) = chars.next() {
+                match c {
+                    ')' => break,
+                    ',' => args.push(String::new()),
+                    '"' => {
+                        while let Some(c) = chars.next() {
+                            match c {
+                                '"' => break,
+                                '\\' => match chars.next() {
+                                    Some('n') => args.last_mut().unwrap().push('\n'),
+                                    Some(c) => args.last_mut().unwrap().push(c),
+                                    None => {}
+                                },
+                                c => args.last_mut().unwrap().push(c),
+                            }
+                        }
+                    }
+                    c => args.last_mut().unwrap().push(c),
+                }
+            }
+            result.push((predicate, args));
+        }
+        result
+    }
+
+    /// Returns the statements with the nodes of their acceptance conditions, and the evaluation rules of the program, whose first atom is the head.
+    fn evaluation(program: &str) -> (Vec<(String, String)>, Vec<Vec<Atom>>) {
+        let mut statements = Vec::new();
+        let mut rules = Vec::new();
+        for line in program.lines() {
+            let atoms = atoms(line);
+            match atoms.first() {
+                Some((predicate, _)) if predicate == "s" => {
+                    statements.push((atoms[1].1[0].clone(), atoms[1].1[1].clone()))
+                }
+                Some((predicate, args))
+                    if (predicate == "mt" || predicate == "mf") && args[0] == "C" =>
+                {
+                    rules.push(atoms)
+                }
+                _ => {}
+            }
+        }
+        (statements, rules)
+    }
+
+    /// Derives the atoms `mt(N)` and `mf(N)` of a context, whose true and false statements are given.
+    fn derive(
+        rules: &[Vec<Atom>],
+        t: &HashSet<String>,
+        f: &HashSet<String>,
+    ) -> HashSet<(String, String)> {
+        let mut derived = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for rule in rules {
+                let (head, body) = rule.split_first().expect("Rules have a head");
+                let holds = body
+                    .iter()
+                    .all(|(predicate, args)| match predicate.as_str() {
+                        "ctx" => true,
+                        "t" => t.contains(&args[1]),
+                        "f" => f.contains(&args[1]),
+                        _ => derived.contains(&(predicate.clone(), args[1].clone())),
+                    });
+                if holds {
+                    changed |= derived.insert((head.0.clone(), head.1[1].clone()));
+                }
+            }
+        }
+        derived
+    }
+
+    /// Computes the models of the evaluation rules in the program, as they are constrained by the encoding of the semantics.
+    fn asp_models(adf: &Adf, semantics: AspSemantics) -> Vec<Vec<Term>> {
+        let (statements, rules) = evaluation(&adf.to_asp(semantics));
+        assert_eq!(
+            statements
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>(),
+            adf.statement_names()
+        );
+        let decided = |interpretation: &[Term], value: Term| {
+            statements
+                .iter()
+                .zip(interpretation)
+                .filter(|(_, term)| **term == value)
+                .map(|((name, _), _)| name.clone())
+                .collect::<HashSet<_>>()
+        };
+        let value = |derived: &HashSet<(String, String)>, node: &String| {
+            if derived.contains(&("mt".to_string(), node.clone())) {
+                Term::TOP
+            } else if derived.contains(&("mf".to_string(), node.clone())) {
+                Term::BOT
+            } else {
+                Term::UND
+            }
+        };
+        // the value of each statement is the one of its acceptance condition
+        let fixpoint = |interpretation: &Vec<Term>| {
+            let derived = derive(
+                &rules,
+                &decided(interpretation, Term::TOP),
+                &decided(interpretation, Term::BOT),
+            );
+            statements
+                .iter()
+                .zip(interpretation)
+                .all(|((_, node), term)| value(&derived, node) == *term)
+        };
+        let undecided = vec![Term::UND; statements.len()];
+        match semantics {
+            AspSemantics::Grounded => {
+                let mut interpretation = undecided;
+                loop {
+                    let derived = derive(
+                        &rules,
+                        &decided(&interpretation, Term::TOP),
+                        &decided(&interpretation, Term::BOT),
+                    );
+                    let next = statements
+                        .iter()
+                        .map(|(_, node)| value(&derived, node))
+                        .collect::<Vec<_>>();
+                    if next == interpretation {
+                        return vec![interpretation];
+                    }
+                    interpretation = next;
+                }
+            }
+            AspSemantics::Complete => ThreeValuedInterpretationsIterator::new(&undecided)
+                .filter(fixpoint)
+                .collect(),
+            AspSemantics::TwoValued => TwoValuedInterpretationsIterator::new(&undecided)
+                .filter(fixpoint)
+                .collect(),
+            AspSemantics::Stable => TwoValuedInterpretationsIterator::new(&undecided)
+                .filter(fixpoint)
+                .filter(|model| {
+                    // the true statements of the model need to be derived in the reduct
+                    let f = decided(model, Term::BOT);
+                    let mut t = HashSet::new();
+                    loop {
+                        let derived = derive(&rules, &t, &f);
+                        let next = statements
+                            .iter()
+                            .zip(model)
+                            .filter(|((_, node), term)| {
+                                term.is_true() && value(&derived, node) == Term::TOP
+                            })
+                            .map(|((name, _), _)| name.clone())
+                            .collect::<HashSet<_>>();
+                        if next == t {
+                            return t == decided(model, Term::TOP);
+                        }
+                        t = next;
+                    }
+                })
+                .collect(),
+            AspSemantics::Admissible => unimplemented!("Admissible models are not compared"),
+        }
+    }
+
+    #[test]
+    fn evaluation_matches_semantics() {
+        let mut adfs = [
+            "s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).",
+            "s(a).s(b).s(c).s(d).ac(a,neg(b)).ac(b,neg(a)).ac(c,c).ac(d,or(a,c)).",
+            "s(a).s(b).s(c).ac(a,neg(a)).ac(b,xor(a,c)).ac(c,neg(b)).",
+        ]
+        .into_iter()
+        .map(|input| {
+            let parser = AdfParser::default();
+            parser.parse()(input).unwrap();
+            Adf::from_parser(&parser)
+        })
+        .collect::<Vec<_>>();
+        // names, which need to be escaped
+        let quote = "say \"b\"";
+        let backslash = "c\\d";
+        adfs.push(
+            AdfBuilder::new()
+                .statement("a.b")
+                .statement(quote)
+                .statement(backslash)
+                .ac("a.b", OwnedFormula::negation(OwnedFormula::atom(quote)))
+                .ac(quote, OwnedFormula::negation(OwnedFormula::atom("a.b")))
+                .ac(
+                    backslash,
+                    OwnedFormula::or(OwnedFormula::atom("a.b"), OwnedFormula::atom(backslash)),
+                )
+                .build()
+                .unwrap(),
+        );
+
+        for mut adf in adfs {
+            let grounded = adf.grounded();
+            assert_eq!(
+                normalised(asp_models(&adf, AspSemantics::Grounded)),
+                normalised([grounded])
+            );
+            assert_eq!(
+                normalised(asp_models(&adf, AspSemantics::Complete)),
+                normalised(adf.complete())
+            );
+            assert_eq!(
+                normalised(asp_models(&adf, AspSemantics::TwoValued)),
+                normalised(adf.two_valued_sat())
+            );
+            assert_eq!(
+                normalised(asp_models(&adf, AspSemantics::Stable)),
+                normalised(adf.stable())
+            );
+        }
+    }
+}
@@ -0,0 +1,129 @@
+/*!
+This module contains a builder, which instantiates an [ADF][super::Adf] from statements and [owned formulae][OwnedFormula], without parsing any text.
+
+# Example
+```
+use adf_bdd::{adf::builder::AdfBuilder, parser::OwnedFormula};
+
+let mut adf = AdfBuilder::new()
+    .statement("a")
+    .statement("b")
+    .ac("a", OwnedFormula::Top)
+    .ac("b", OwnedFormula::negation(OwnedFormula::atom("a")))
+    .build()
+    .expect("All statements are declared");
+let grounded = adf.grounded();
+assert_eq!(adf.print_interpretation(&grounded).to_string(), "T(a) F(b) \n");
+```
+ */
+use crate::{datatypes::Term, parser::OwnedFormula};
+
+use super::{editing::EditError, Adf};
+
+/// Collects statements and their acceptance conditions, to [build][AdfBuilder::build] an [ADF][Adf].
+///
+/// The statements are ordered in the sequence of their declaration.
+/// As in the input format, a statement without an acceptance condition is never accepted, and a later acceptance condition of a statement replaces an earlier one.
+#[derive(Debug, Clone, Default)]
+pub struct AdfBuilder {
+    statements: Vec<String>,
+    acs: Vec<(String, OwnedFormula)>,
+}
+
+impl AdfBuilder {
+    /// Creates a builder without any statements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a statement with the given name.
+    pub fn statement(mut self, name: &str) -> Self {
+        self.statements.push(name.to_string());
+        self
+    }
+
+    /// Sets the acceptance condition of the statement with the given name.
+    pub fn ac(mut self, name: &str, formula: OwnedFormula) -> Self {
+        self.acs.push((name.to_string(), formula));
+        self
+    }
+
+    /// Instantiates the [ADF][Adf].
+    ///
+    /// Returns an [EditError], if a statement is declared twice, or if an acceptance condition refers to an undeclared statement.
+    pub fn build(self) -> Result<Adf, EditError> {
+        let mut result = Adf::default();
+        for name in self.statements.iter() {
+            if result.ordering.variable(name).is_some() {
+                return Err(EditError::DuplicateStatement(name.clone()));
+            }
+            let var = result.ordering.push(name);
+            result.bdd.variable(var);
+            result.ac.push(Term::BOT);
+        }
+        for (name, formula) in self.acs.iter() {
+            result.set_acceptance_condition(name, &formula.as_formula())?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::AdfParser;
+    use test_log::test;
+
+    #[test]
+    fn build() {
+        let mut adf = AdfBuilder::new()
+            .statement("a")
+            .statement("b")
+            .statement("c")
+            .ac("a", OwnedFormula::negation(OwnedFormula::atom("b")))
+            .ac("b", OwnedFormula::negation(OwnedFormula::atom("a")))
+            .ac(
+                "c",
+                OwnedFormula::and(OwnedFormula::atom("a"), OwnedFormula::atom("c")),
+            )
+            .build()
+            .unwrap();
+
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,and(a,c)).").unwrap();
+        let mut parsed = Adf::from_parser(&parser);
+        assert_eq!(
+            adf.stable().collect::<Vec<_>>(),
+            parsed.stable().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            adf.complete().collect::<Vec<_>>(),
+            parsed.complete().collect::<Vec<_>>()
+        );
+
+        // statements without acceptance condition are never accepted
+        let mut adf = AdfBuilder::new().statement("a").build().unwrap();
+        assert_eq!(adf.grounded(), vec![Term::BOT]);
+
+        assert_eq!(
+            AdfBuilder::new()
+                .statement("a")
+                .statement("a")
+                .build()
+                .err(),
+            Some(EditError::DuplicateStatement("a".to_string()))
+        );
+        assert_eq!(
+            AdfBuilder::new()
+                .statement("a")
+                .ac("a", OwnedFormula::atom("b"))
+                .build()
+                .err(),
+            Some(EditError::UnknownStatement("b".to_string()))
+        );
+        assert_eq!(
+            AdfBuilder::new().ac("a", OwnedFormula::Top).build().err(),
+            Some(EditError::UnknownStatement("a".to_string()))
+        );
+    }
+}
@@ -0,0 +1,582 @@
+/*!
+This module contains the encoding of the models of an [ADF][super::Adf] into a propositional formula in conjunctive normal form.
+
+The acceptance conditions are encoded with the Tseitin transformation, either from the nodes of the [Bdd] (see [to_cnf][super::Adf::to_cnf]) or from the [formulae][Formula] of the parser (see [formulae_to_cnf][super::Adf::formulae_to_cnf]).
+The resulting [Cnf] is printed in the DIMACS format, so the models can be computed by external SAT solvers.
+
+# Example
+```
+use adf_bdd::adf::{cnf::CnfSemantics, Adf};
+use adf_bdd::parser::AdfParser;
+
+let parser = AdfParser::default();
+parser.parse()("s(a).s(b).ac(a,neg(b)).ac(b,neg(a)).").unwrap();
+let mut adf = Adf::from_parser(&parser);
+let cnf = adf.to_cnf(CnfSemantics::TwoValued);
+assert!(cnf.to_string().contains("p cnf"));
+```
+ */
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    datatypes::{Term, Var},
+    obdd::{clamp_bound, Bdd},
+    parser::Formula,
+};
+
+/// Semantics, whose models are encoded into a [Cnf].
+#[derive(
+    strum::Display,
+    strum::EnumString,
+    strum::EnumVariantNames,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum CnfSemantics {
+    /// Two valued models, i.e. each statement is true if and only if its acceptance condition holds.
+    TwoValued,
+    /// Two valued models, which extend the grounded model.
+    /// Each stable model is such a candidate, but the stability of a candidate has to be checked separately.
+    StableCandidate,
+    /// Admissible interpretations, where a statement might be undecided.
+    Admissible,
+    /// Complete models, where a statement might be undecided.
+    Complete,
+}
+
+impl CnfSemantics {
+    /// Returns [true], if the models of the semantics are three valued.
+    pub fn is_three_valued(self) -> bool {
+        matches!(self, Self::Admissible | Self::Complete)
+    }
+}
+
+/// The reason why an [ADF][super::Adf] could not be encoded into a [Cnf].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CnfError {
+    /// An acceptance condition refers to a statement, which does not exist.
+    UnknownStatement(String),
+    /// The semantics cannot be encoded from formulae, as deciding a statement depends on all completions of an interpretation.
+    UnsupportedSemantics(CnfSemantics),
+}
+
+impl Display for CnfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownStatement(name) => write!(f, "statement {name} does not exist"),
+            Self::UnsupportedSemantics(semantics) => write!(
+                f,
+                "{semantics} models cannot be encoded from formulae, use the BDD instead"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CnfError {}
+
+/// A propositional formula in conjunctive normal form over the variables `1..=variables`.
+///
+/// A literal is a variable or its negation, i.e. a positive or negative number as in the DIMACS format.
+/// The first variables represent the statements:
+/// two valued semantics use the variable `i + 1` for the truth of the `i`-th statement,
+/// whereas three valued semantics use the variables `2i + 1` and `2i + 2` for the `i`-th statement being true and false respectively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cnf {
+    /// The encoded semantics.
+    pub semantics: CnfSemantics,
+    /// Number of statements of the encoded [ADF][super::Adf].
+    pub statements: usize,
+    /// Number of variables, including the auxiliary variables of the Tseitin transformation.
+    pub variables: usize,
+    /// The clauses, each of which is a disjunction of literals.
+    pub clauses: Vec<Vec<i64>>,
+    /// Comments, which relate the variables to the names of the statements.
+    pub comments: Vec<String>,
+}
+
+impl Cnf {
+    /// Creates a [Cnf], which contains the variables of the given statements.
+    /// In three valued semantics, a statement cannot be true and false at the same time.
+    pub(crate) fn new(semantics: CnfSemantics, names: &[String]) -> Self {
+        let mut result = Self::with_statements(semantics, names.len());
+        result.comments.push(format!("semantics {semantics}"));
+        for (idx, name) in names.iter().enumerate() {
+            if semantics.is_three_valued() {
+                result
+                    .comments
+                    .push(format!("T({name}) {}", result.statement_true(Var(idx))));
+                result
+                    .comments
+                    .push(format!("F({name}) {}", result.statement_false(Var(idx))));
+                result.clause(&[
+                    -result.statement_true(Var(idx)),
+                    -result.statement_false(Var(idx)),
+                ]);
+            } else {
+                result
+                    .comments
+                    .push(format!("T({name}) {}", result.statement_true(Var(idx))));
+            }
+        }
+        result
+    }
+
+    /// Creates a [Cnf] without comments, which contains the variables of the given number of statements and the constant truth value.
+    /// Apart from the constant, the values of the statements are not restricted.
+    pub(crate) fn with_statements(semantics: CnfSemantics, statements: usize) -> Self {
+        let mut result = Self {
+            semantics,
+            statements,
+            variables: 0,
+            clauses: Vec::new(),
+            comments: Vec::new(),
+        };
+        // the last variable represents the constant truth value
+        result.variables = result.top() as usize;
+        result.clause(&[result.top()]);
+        result
+    }
+
+    /// Returns the literal, which holds if the given statement is true.
+    pub fn statement_true(&self, var: Var) -> i64 {
+        if self.semantics.is_three_valued() {
+            2 * var.value() as i64 + 1
+        } else {
+            var.value() as i64 + 1
+        }
+    }
+
+    /// Returns the literal, which holds if the given statement is false.
+    pub fn statement_false(&self, var: Var) -> i64 {
+        if self.semantics.is_three_valued() {
+            2 * var.value() as i64 + 2
+        } else {
+            -(var.value() as i64 + 1)
+        }
+    }
+
+    /// Returns the interpretation, which is represented by the given assignment.
+    ///
+    /// The `i`-th value of the assignment is the value of the variable `i + 1`.
+    /// Undecided statements are represented by [Term::UND].
+    pub fn interpretation(&self, assignment: &[bool]) -> Vec<Term> {
+        let holds = |literal: i64| assignment[literal.unsigned_abs() as usize - 1] == (literal > 0);
+        (0..self.statements)
+            .map(|idx| {
+                if holds(self.statement_true(Var(idx))) {
+                    Term::TOP
+                } else if holds(self.statement_false(Var(idx))) {
+                    Term::BOT
+                } else {
+                    Term::UND
+                }
+            })
+            .collect()
+    }
+
+    /// The literal, which always holds.
+    pub(crate) fn top(&self) -> i64 {
+        let factor = if self.semantics.is_three_valued() {
+            2
+        } else {
+            1
+        };
+        (factor * self.statements + 1) as i64
+    }
+
+    /// Returns the literal, which represents the given truth value.
+    pub(crate) fn constant(&self, value: bool) -> i64 {
+        if value {
+            self.top()
+        } else {
+            -self.top()
+        }
+    }
+
+    pub(crate) fn clause(&mut self, literals: &[i64]) {
+        self.clauses.push(literals.to_vec());
+    }
+
+    fn fresh(&mut self) -> i64 {
+        self.variables += 1;
+        self.variables as i64
+    }
+
+    /// Returns a literal, which is equivalent to the conjunction of both literals.
+    pub(crate) fn and(&mut self, lhs: i64, rhs: i64) -> i64 {
+        if lhs == -self.top() || rhs == -self.top() || lhs == -rhs {
+            -self.top()
+        } else if lhs == self.top() || lhs == rhs {
+            rhs
+        } else if rhs == self.top() {
+            lhs
+        } else {
+            let gate = self.fresh();
+            self.clause(&[-gate, lhs]);
+            self.clause(&[-gate, rhs]);
+            self.clause(&[gate, -lhs, -rhs]);
+            gate
+        }
+    }
+
+    /// Returns a literal, which is equivalent to the disjunction of both literals.
+    pub(crate) fn or(&mut self, lhs: i64, rhs: i64) -> i64 {
+        -self.and(-lhs, -rhs)
+    }
+
+    /// Returns a literal, which is equivalent to `condition ? then : otherwise`.
+    pub(crate) fn ite(&mut self, condition: i64, then: i64, otherwise: i64) -> i64 {
+        if condition == self.top() || then == otherwise {
+            then
+        } else if condition == -self.top() {
+            otherwise
+        } else if then == self.top() || then == condition {
+            self.or(condition, otherwise)
+        } else if then == -self.top() || then == -condition {
+            self.and(-condition, otherwise)
+        } else if otherwise == self.top() || otherwise == -condition {
+            self.or(-condition, then)
+        } else if otherwise == -self.top() || otherwise == condition {
+            self.and(condition, then)
+        } else {
+            let gate = self.fresh();
+            self.clause(&[-gate, -condition, then]);
+            self.clause(&[-gate, condition, otherwise]);
+            self.clause(&[gate, -condition, -then]);
+            self.clause(&[gate, condition, -otherwise]);
+            gate
+        }
+    }
+
+    /// Returns a literal, which is equivalent to the equivalence of both literals.
+    pub(crate) fn iff(&mut self, lhs: i64, rhs: i64) -> i64 {
+        self.ite(lhs, rhs, -rhs)
+    }
+
+    /// Returns a literal, which holds if at least `k` of the given literals hold.
+    pub(crate) fn at_least(&mut self, literals: &[i64], k: usize) -> i64 {
+        if k > literals.len() {
+            return -self.top();
+        }
+        // counts[j] holds if at least j of the processed literals hold,
+        // where only the counts from which k can still be reached are encoded
+        let mut counts = vec![self.top()];
+        counts.resize(k + 1, -self.top());
+        for (idx, &literal) in literals.iter().enumerate() {
+            let remaining = literals.len() - idx - 1;
+            for j in (k.saturating_sub(remaining).max(1)..=k.min(idx + 1)).rev() {
+                counts[j] = self.ite(literal, counts[j - 1], counts[j]);
+            }
+        }
+        counts[k]
+    }
+
+    /// Adds the clauses, which state that the literals are equivalent.
+    pub(crate) fn equal(&mut self, lhs: i64, rhs: i64) {
+        self.clause(&[-lhs, rhs]);
+        self.clause(&[lhs, -rhs]);
+    }
+
+    /// Encodes the given [Term] of the [Bdd], where each variable is replaced by the literal of its statement.
+    /// Returns the literal, which is equivalent to the term.
+    pub(crate) fn bdd_two_valued(&mut self, bdd: &Bdd, term: Term) -> i64 {
+        let mut literals = HashMap::new();
+        for node in bdd.reachable(&[term]) {
+            let literal = if node.is_truth_value() {
+                self.constant(node.is_true())
+            } else {
+                let bdd_node = bdd.nodes[node.value()];
+                let condition = self.statement_true(bdd_node.var());
+                self.ite(
+                    condition,
+                    literals[&bdd_node.hi()],
+                    literals[&bdd_node.lo()],
+                )
+            };
+            literals.insert(node, literal);
+        }
+        literals[&term]
+    }
+
+    /// Encodes the given [Term] of the [Bdd] with respect to a three valued interpretation, where the `i`-th variable is replaced by the statement `offset + i`.
+    /// Returns the literals, which hold if the term is satisfied by some completion and if it is refuted by some completion of the interpretation respectively.
+    pub(crate) fn bdd_three_valued(&mut self, bdd: &Bdd, term: Term, offset: usize) -> (i64, i64) {
+        let mut literals: HashMap<Term, (i64, i64)> = HashMap::new();
+        for node in bdd.reachable(&[term]) {
+            let literal = if node.is_truth_value() {
+                (
+                    self.constant(node.is_true()),
+                    self.constant(!node.is_true()),
+                )
+            } else {
+                let bdd_node = bdd.nodes[node.value()];
+                let var = Var(offset + bdd_node.var().value());
+                let not_false = -self.statement_false(var);
+                let not_true = -self.statement_true(var);
+                let (hi_sat, hi_ref) = literals[&bdd_node.hi()];
+                let (lo_sat, lo_ref) = literals[&bdd_node.lo()];
+                let hi = self.and(not_false, hi_sat);
+                let lo = self.and(not_true, lo_sat);
+                let satisfiable = self.or(hi, lo);
+                let hi = self.and(not_false, hi_ref);
+                let lo = self.and(not_true, lo_ref);
+                let refutable = self.or(hi, lo);
+                (satisfiable, refutable)
+            };
+            literals.insert(node, literal);
+        }
+        literals[&term]
+    }
+
+    /// Encodes the given formula, where each atom is replaced by the literal of its statement.
+    /// Returns the literal, which is equivalent to the formula, or the name of an atom, which is not a statement.
+    pub(crate) fn formula_two_valued(
+        &mut self,
+        formula: &Formula,
+        statement: impl Fn(&str) -> Option<Var>,
+    ) -> Result<i64, CnfError> {
+        formula.fold(|formula, literals: Vec<Result<i64, CnfError>>| {
+            let literals = literals.into_iter().collect::<Result<Vec<_>, _>>()?;
+            Ok(match formula {
+                Formula::Bot => self.constant(false),
+                Formula::Top => self.constant(true),
+                Formula::Atom(name) => self.statement_true(
+                    statement(name).ok_or_else(|| CnfError::UnknownStatement(name.to_string()))?,
+                ),
+                Formula::Not(_) => -literals[0],
+                Formula::And(..) => self.and(literals[0], literals[1]),
+                Formula::Or(..) => self.or(literals[0], literals[1]),
+                Formula::Imp(..) => self.or(-literals[0], literals[1]),
+                Formula::Iff(..) => self.iff(literals[0], literals[1]),
+                Formula::Xor(..) => -self.iff(literals[0], literals[1]),
+                Formula::AtLeast(k, _) => self.at_least(&literals, *k),
+                Formula::AtMost(k, _) => {
+                    -self.at_least(&literals, clamp_bound(*k, literals.len()) + 1)
+                }
+                Formula::Exactly(k, _) => {
+                    let k = clamp_bound(*k, literals.len());
+                    let at_least = self.at_least(&literals, k);
+                    let more = self.at_least(&literals, k + 1);
+                    self.and(at_least, -more)
+                }
+            })
+        })
+    }
+
+    /// Adds the clauses, which relate each statement to the literals of its acceptance condition.
+    ///
+    /// Two valued semantics expect one literal for each acceptance condition, which is equivalent to it.
+    /// Three valued semantics expect the literals, which hold if the acceptance condition is satisfied and refuted by some completion respectively.
+    pub(crate) fn accept(&mut self, conditions: &[(i64, i64)]) {
+        for (idx, &(satisfiable, refutable)) in conditions.iter().enumerate() {
+            let is_true = self.statement_true(Var(idx));
+            let is_false = self.statement_false(Var(idx));
+            match self.semantics {
+                CnfSemantics::TwoValued | CnfSemantics::StableCandidate => {
+                    self.equal(is_true, satisfiable)
+                }
+                CnfSemantics::Admissible => {
+                    self.clause(&[-is_true, -refutable]);
+                    self.clause(&[-is_false, -satisfiable]);
+                }
+                CnfSemantics::Complete => {
+                    self.equal(is_true, -refutable);
+                    self.equal(is_false, -satisfiable);
+                }
+            }
+        }
+    }
+
+    /// Adds the unit clauses, which fix the decided statements of the given interpretation.
+    pub(crate) fn fix(&mut self, interpretation: &[Term]) {
+        for (idx, term) in interpretation.iter().enumerate() {
+            if term.is_truth_value() {
+                let literal = if term.is_true() {
+                    self.statement_true(Var(idx))
+                } else {
+                    self.statement_false(Var(idx))
+                };
+                self.clause(&[literal]);
+            }
+        }
+    }
+}
+
+impl Display for Cnf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for comment in self.comments.iter() {
+            writeln!(f, "c {comment}")?;
+        }
+        writeln!(f, "p cnf {} {}", self.variables, self.clauses.len())?;
+        for clause in self.clauses.iter() {
+            for literal in clause {
+                write!(f, "{literal} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        adf::Adf,
+        datatypes::adf::{ThreeValuedInterpretationsIterator, TwoValuedInterpretationsIterator},
+        parser::AdfParser,
+        test::{normalised, unit_propagation},
+    };
+    use test_log::test;
+
+    /// Fixes the statements to the given interpretation and propagates the unit clauses.
+    /// Returns the interpretation, if the propagation does not run into a conflict.
+    fn propagate(cnf: &Cnf, interpretation: &[Term]) -> Option<Vec<Term>> {
+        let mut assignment = vec![None; cnf.variables];
+        let assign = |assignment: &mut Vec<Option<bool>>, literal: i64| {
+            assignment[literal.unsigned_abs() as usize - 1] = Some(literal > 0)
+        };
+        for (idx, term) in interpretation.iter().enumerate() {
+            let is_true = cnf.statement_true(Var(idx));
+            let is_false = cnf.statement_false(Var(idx));
+            if term.is_truth_value() {
+                assign(
+                    &mut assignment,
+                    if term.is_true() { is_true } else { is_false },
+                );
+            } else {
+                assign(&mut assignment, -is_true);
+                assign(&mut assignment, -is_false);
+            }
+        }
+        unit_propagation(cnf, assignment).map(|assignment| cnf.interpretation(&assignment))
+    }
+
+    fn models(cnf: &Cnf) -> Vec<Vec<Term>> {
+        let undecided = vec![Term::UND; cnf.statements];
+        let mut result: Vec<_> = if cnf.semantics.is_three_valued() {
+            ThreeValuedInterpretationsIterator::new(&undecided)
+                .filter_map(|interpretation| propagate(cnf, &interpretation))
+                .collect()
+        } else {
+            TwoValuedInterpretationsIterator::new(&undecided)
+                .filter_map(|interpretation| propagate(cnf, &interpretation))
+                .collect()
+        };
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn semantics() {
+        for input in [
+            "s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).",
+            "s(a).s(b).s(c).s(d).ac(a,neg(b)).ac(b,neg(a)).ac(c,xor(a,c)).ac(d,iff(c,neg(d))).",
+            "s(a).s(b).s(c).s(d).s(e).ac(a,atleast(2,b,c,d)).ac(b,atmost(1,a,c)).ac(c,exactly(1,a,b,e)).ac(d,imp(d,e)).ac(e,or(neg(a),c)).",
+            "s(a).s(b).s(c).ac(a,b).ac(b,a).ac(c,c(f)).",
+            "s(a).s(b).s(c).ac(a,atmost(18446744073709551615,a,b)).ac(b,atleast(5000000000000,a,c)).ac(c,exactly(18446744073709551615,a,c)).",
+        ] {
+            let parser = AdfParser::default();
+            parser.parse()(input).unwrap();
+            let mut adf = Adf::from_parser(&parser);
+            let complete = normalised(adf.complete());
+            let two_valued = complete
+                .iter()
+                .filter(|model| model.iter().all(Term::is_truth_value))
+                .cloned()
+                .collect::<Vec<_>>();
+            let grounded = adf.grounded();
+            let candidates = two_valued
+                .iter()
+                .filter(|model| {
+                    model
+                        .iter()
+                        .zip(grounded.iter())
+                        .all(|(term, gr)| !gr.is_truth_value() || term == gr)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            let admissible = normalised(
+                ThreeValuedInterpretationsIterator::new(&vec![Term::UND; adf.ac.len()]).filter(
+                    |interpretation| {
+                        let ac = adf.ac.clone();
+                        ac.into_iter().enumerate().all(|(idx, ac)| {
+                            let restricted = interpretation.iter().enumerate().fold(
+                                ac,
+                                |acc, (var, term)| {
+                                    if term.is_truth_value() {
+                                        adf.bdd.restrict(acc, Var(var), term.is_true())
+                                    } else {
+                                        acc
+                                    }
+                                },
+                            );
+                            !interpretation[idx].is_truth_value()
+                                || interpretation[idx] == restricted
+                        })
+                    },
+                ),
+            );
+
+            assert_eq!(models(&adf.to_cnf(CnfSemantics::TwoValued)), two_valued);
+            assert_eq!(models(&adf.to_cnf(CnfSemantics::StableCandidate)), candidates);
+            assert_eq!(models(&adf.to_cnf(CnfSemantics::Admissible)), admissible);
+            assert_eq!(models(&adf.to_cnf(CnfSemantics::Complete)), complete);
+            assert!(normalised(adf.stable())
+                .iter()
+                .all(|model| candidates.contains(model)));
+
+            assert_eq!(
+                models(&adf.formulae_to_cnf(&parser, CnfSemantics::TwoValued).unwrap()),
+                two_valued
+            );
+            assert_eq!(
+                models(
+                    &adf.formulae_to_cnf(&parser, CnfSemantics::StableCandidate)
+                        .unwrap()
+                ),
+                candidates
+            );
+            assert_eq!(
+                adf.formulae_to_cnf(&parser, CnfSemantics::Complete),
+                Err(CnfError::UnsupportedSemantics(CnfSemantics::Complete))
+            );
+        }
+    }
+
+    #[test]
+    fn gates() {
+        let mut cnf = Cnf::new(CnfSemantics::TwoValued, &["a".to_string(), "b".to_string()]);
+        let top = cnf.top();
+        assert_eq!(top, 3);
+        assert_eq!(cnf.and(1, top), 1);
+        assert_eq!(cnf.and(1, -1), -top);
+        assert_eq!(cnf.ite(1, top, -top), 1);
+        assert_eq!(cnf.at_least(&[1, 2], 0), top);
+        assert_eq!(cnf.at_least(&[1, 2], 3), -top);
+        assert_eq!(cnf.clauses.len(), 1);
+
+        let gate = cnf.and(1, 2);
+        assert_eq!(gate, 4);
+        assert_eq!(cnf.variables, 4);
+        assert_eq!(cnf.clauses.len(), 4);
+        assert_eq!(
+            cnf.to_string(),
+            "c semantics TwoValued\nc T(a) 1\nc T(b) 2\np cnf 4 4\n3 0\n-4 1 0\n-4 2 0\n4 -1 -2 0\n"
+        );
+        assert_eq!(
+            cnf.interpretation(&[true, false, true, false]),
+            vec![Term::TOP, Term::BOT]
+        );
+
+        let cnf = Cnf::new(CnfSemantics::Complete, &["a".to_string()]);
+        assert_eq!(cnf.top(), 3);
+        assert_eq!(cnf.clauses, vec![vec![3], vec![-1, -2]]);
+        assert_eq!(cnf.interpretation(&[false, false, true]), vec![Term::UND]);
+        assert_eq!(cnf.interpretation(&[false, true, true]), vec![Term::BOT]);
+    }
+}
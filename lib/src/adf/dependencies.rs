@@ -0,0 +1,251 @@
+/*!
+This module contains the export of the statement dependency graph of an [ADF][super::Adf].
+
+The graph has an edge from `s` to `t` whenever `s` occurs in the acceptance condition of `t`.
+It can be rendered in the [DOT](https://graphviz.org/doc/info/lang.html), [GraphML](http://graphml.graphdrawing.org/) and JSON formats.
+ */
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::obdd::escape_dot;
+
+/// Polarity of a link between two statements.
+#[derive(Serialize, Deserialize, strum::Display, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum LinkPolarity {
+    /// Accepting the source never rejects the target (i.e. the acceptance condition is monotone in the source).
+    Support,
+    /// Accepting the source never accepts the target (i.e. the acceptance condition is anti-monotone in the source).
+    Attack,
+    /// The link is neither supporting nor attacking.
+    Dependent,
+}
+
+/// A statement in a [StatementGraph].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StatementNode {
+    /// Name of the statement.
+    pub name: String,
+    /// Index of the strongly connected component, which contains the statement.
+    /// Components are numbered in topological order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scc: Option<usize>,
+}
+
+/// A link between two statements in a [StatementGraph].
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StatementLink {
+    /// Index of the statement, which occurs in the acceptance condition of the target.
+    pub source: usize,
+    /// Index of the statement, whose acceptance condition contains the source.
+    pub target: usize,
+    /// Polarity of the link.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub polarity: Option<LinkPolarity>,
+}
+
+/// Statement dependency graph of an [ADF][super::Adf] with labelled nodes for export purposes.
+///
+/// Use [statement_graph][super::Adf::statement_graph] to create it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct StatementGraph {
+    /// Statements of the ADF, in the order of their variables.
+    pub statements: Vec<StatementNode>,
+    /// Links between the statements, ordered by their source and target.
+    pub links: Vec<StatementLink>,
+}
+
+impl StatementGraph {
+    /// Renders the graph in the DOT language of graphviz.
+    ///
+    /// Supporting links are drawn green, attacking links are drawn red with a tee-shaped arrowhead and dependent links are drawn dashed.
+    /// Statements of a non-trivial strongly connected component are grouped in a cluster.
+    pub fn to_dot(&self) -> String {
+        let mut result = String::from("digraph {\n");
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+        for (idx, statement) in self.statements.iter().enumerate() {
+            writeln!(
+                result,
+                "    s{idx} [label=\"{}\"];",
+                escape_dot(&statement.name)
+            )
+            .expect("Writing to a String cannot fail");
+            if let Some(scc) = statement.scc {
+                if clusters.len() <= scc {
+                    clusters.resize(scc + 1, Vec::new());
+                }
+                clusters[scc].push(idx);
+            }
+        }
+        for (num, cluster) in clusters.iter().enumerate() {
+            let nontrivial = cluster.len() > 1
+                || cluster.iter().any(|&idx| {
+                    self.links
+                        .iter()
+                        .any(|link| link.source == idx && link.target == idx)
+                });
+            if nontrivial {
+                writeln!(
+                    result,
+                    "    subgraph cluster_{num} {{\n        style=dotted;"
+                )
+                .expect("Writing to a String cannot fail");
+                for idx in cluster {
+                    writeln!(result, "        s{idx};").expect("Writing to a String cannot fail");
+                }
+                result.push_str("    }\n");
+            }
+        }
+        for link in self.links.iter() {
+            let attributes = match link.polarity {
+                Some(LinkPolarity::Support) => " [color=darkgreen]",
+                Some(LinkPolarity::Attack) => " [color=red, arrowhead=tee]",
+                Some(LinkPolarity::Dependent) => " [style=dashed]",
+                None => "",
+            };
+            writeln!(
+                result,
+                "    s{} -> s{}{attributes};",
+                link.source, link.target
+            )
+            .expect("Writing to a String cannot fail");
+        }
+        result.push_str("}\n");
+        result
+    }
+
+    /// Renders the graph in the GraphML format.
+    ///
+    /// Each node carries the `name` of its statement and, if computed, its `scc`.
+    /// Each edge carries its `polarity`, if computed.
+    pub fn to_graphml(&self) -> String {
+        let mut result = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        );
+        result.push_str(
+            "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n",
+        );
+        result.push_str("  <key id=\"scc\" for=\"node\" attr.name=\"scc\" attr.type=\"int\"/>\n");
+        result.push_str(
+            "  <key id=\"polarity\" for=\"edge\" attr.name=\"polarity\" attr.type=\"string\"/>\n",
+        );
+        result.push_str("  <graph id=\"adf\" edgedefault=\"directed\">\n");
+        for (idx, statement) in self.statements.iter().enumerate() {
+            write!(
+                result,
+                "    <node id=\"s{idx}\"><data key=\"name\">{}</data>",
+                escape_xml(&statement.name)
+            )
+            .expect("Writing to a String cannot fail");
+            if let Some(scc) = statement.scc {
+                write!(result, "<data key=\"scc\">{scc}</data>")
+                    .expect("Writing to a String cannot fail");
+            }
+            result.push_str("</node>\n");
+        }
+        for link in self.links.iter() {
+            write!(
+                result,
+                "    <edge source=\"s{}\" target=\"s{}\">",
+                link.source, link.target
+            )
+            .expect("Writing to a String cannot fail");
+            if let Some(polarity) = link.polarity {
+                write!(result, "<data key=\"polarity\">{polarity}</data>")
+                    .expect("Writing to a String cannot fail");
+            }
+            result.push_str("</edge>\n");
+        }
+        result.push_str("  </graph>\n</graphml>\n");
+        result
+    }
+
+    /// Renders the graph as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Serialising a StatementGraph cannot fail")
+    }
+}
+
+fn escape_xml(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_log::test;
+
+    fn example() -> StatementGraph {
+        StatementGraph {
+            statements: vec![
+                StatementNode {
+                    name: "a".to_string(),
+                    scc: Some(0),
+                },
+                StatementNode {
+                    name: "b<c".to_string(),
+                    scc: Some(1),
+                },
+                StatementNode {
+                    name: "d\"".to_string(),
+                    scc: Some(1),
+                },
+            ],
+            links: vec![
+                StatementLink {
+                    source: 0,
+                    target: 1,
+                    polarity: Some(LinkPolarity::Support),
+                },
+                StatementLink {
+                    source: 1,
+                    target: 2,
+                    polarity: Some(LinkPolarity::Attack),
+                },
+                StatementLink {
+                    source: 2,
+                    target: 1,
+                    polarity: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn dot() {
+        assert_eq!(
+            example().to_dot(),
+            "digraph {\n    s0 [label=\"a\"];\n    s1 [label=\"b<c\"];\n    s2 [label=\"d\\\"\"];\n    subgraph cluster_1 {\n        style=dotted;\n        s1;\n        s2;\n    }\n    s0 -> s1 [color=darkgreen];\n    s1 -> s2 [color=red, arrowhead=tee];\n    s2 -> s1;\n}\n"
+        );
+    }
+
+    #[test]
+    fn graphml() {
+        let graphml = example().to_graphml();
+        assert!(graphml.contains(
+            "<node id=\"s1\"><data key=\"name\">b&lt;c</data><data key=\"scc\">1</data></node>"
+        ));
+        assert!(graphml.contains(
+            "<edge source=\"s1\" target=\"s2\"><data key=\"polarity\">attack</data></edge>"
+        ));
+        assert!(graphml.contains("<edge source=\"s2\" target=\"s1\"></edge>"));
+        assert!(graphml.ends_with("</graphml>\n"));
+    }
+
+    #[test]
+    fn json() {
+        let graph = example();
+        let json = graph.to_json();
+        assert!(json.contains("{\"source\":0,\"target\":1,\"polarity\":\"support\"}"));
+        assert!(json.contains("{\"source\":2,\"target\":1}"));
+        let deserialized: StatementGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(graph, deserialized);
+    }
+}
@@ -0,0 +1,43 @@
+/*!
+This module contains the errors of the incremental editing of an [ADF][super::Adf].
+
+An [ADF][super::Adf] can be revised one statement at a time with [add_statement][super::Adf::add_statement], [remove_statement][super::Adf::remove_statement], and [set_acceptance_condition][super::Adf::set_acceptance_condition].
+Each revision reuses the nodes and caches of the underlying [Bdd][crate::obdd::Bdd], instead of instantiating the whole ADF again.
+ */
+use std::fmt::Display;
+
+/// The reason why a revision of an [ADF][super::Adf] has been rejected.
+/// The [ADF][super::Adf] is unchanged in this case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// A statement with the given name exists already.
+    DuplicateStatement(String),
+    /// No statement with the given name exists.
+    UnknownStatement(String),
+    /// The statement still occurs in the acceptance conditions of the listed statements.
+    StillReferenced {
+        /// The statement, which shall be removed.
+        statement: String,
+        /// The statements, whose acceptance condition refers to the statement.
+        referenced_by: Vec<String>,
+    },
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateStatement(name) => write!(f, "statement {name} exists already"),
+            Self::UnknownStatement(name) => write!(f, "statement {name} does not exist"),
+            Self::StillReferenced {
+                statement,
+                referenced_by,
+            } => write!(
+                f,
+                "statement {statement} occurs in the acceptance conditions of {}",
+                referenced_by.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
@@ -0,0 +1,323 @@
+/*!
+This module contains the explanations of the values of the grounded model of an [ADF][super::Adf].
+
+Each decided statement carries a [Derivation], i.e. the iteration of the grounded computation, in which its acceptance condition collapsed to a constant, together with the already decided statements which caused the collapse.
+The derivations form a directed acyclic graph, as each reason has been decided in an earlier iteration.
+Each undecided statement carries a [Witness], i.e. two completions of the grounded model, on which its acceptance condition disagrees.
+Use [explain_grounded][super::Adf::explain_grounded] to create an explanation.
+
+If an ADF has no stable model, a [StableConflict] names a core of statements, which make stability impossible.
+Use [explain_no_stable][super::Adf::explain_no_stable] to compute it, or [stable_nogood_explained][super::Adf::stable_nogood_explained] to derive it from the same search, which enumerates the stable models.
+ */
+use std::cell::RefCell;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::{heuristics::Heuristic, Adf, NoGoodSearch};
+use crate::{
+    datatypes::{adf::PrintDictionary, budget::Budget, Term, Var},
+    obdd::escape_dot,
+};
+
+/// Explanation of a value of a decided statement in the grounded model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Derivation {
+    /// The decided statement.
+    pub statement: Var,
+    /// The value of the statement.
+    pub value: Term,
+    /// Iteration of the grounded computation, in which the statement has been decided.
+    /// Statements with a constant acceptance condition are decided in iteration `0`.
+    pub iteration: usize,
+    /// Already decided statements, which occur in the acceptance condition of the statement.
+    pub reasons: Vec<Var>,
+}
+
+/// Explanation of an undecided statement in the grounded model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    /// The undecided statement.
+    pub statement: Var,
+    /// A two valued completion of the grounded model, which satisfies the acceptance condition of the statement.
+    pub accepting: Vec<Term>,
+    /// A two valued completion of the grounded model, which does not satisfy the acceptance condition of the statement.
+    pub rejecting: Vec<Term>,
+}
+
+/// Explanation of the grounded model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct GroundedExplanation {
+    /// The grounded model.
+    pub grounded: Vec<Term>,
+    /// Derivations of the decided statements, ordered by their iteration.
+    pub derivations: Vec<Derivation>,
+    /// Witnesses of the undecided statements.
+    pub witnesses: Vec<Witness>,
+}
+
+impl GroundedExplanation {
+    /// Returns the [Derivation] of the given statement, if it is decided.
+    pub fn derivation(&self, statement: Var) -> Option<&Derivation> {
+        self.derivations
+            .iter()
+            .find(|derivation| derivation.statement == statement)
+    }
+
+    /// Returns the [Witness] of the given statement, if it is undecided.
+    pub fn witness(&self, statement: Var) -> Option<&Witness> {
+        self.witnesses
+            .iter()
+            .find(|witness| witness.statement == statement)
+    }
+
+    /// Renders the explanation as text, one line for each statement.
+    pub fn to_text(&self, dictionary: &PrintDictionary) -> String {
+        let mut result = String::new();
+        for derivation in self.derivations.iter() {
+            let statement = literal(dictionary, derivation.statement, derivation.value);
+            if derivation.reasons.is_empty() {
+                writeln!(
+                    result,
+                    "{statement} in iteration {}, as its acceptance condition is constant",
+                    derivation.iteration
+                )
+            } else {
+                writeln!(
+                    result,
+                    "{statement} in iteration {}, because of {}",
+                    derivation.iteration,
+                    derivation
+                        .reasons
+                        .iter()
+                        .map(|&reason| literal(dictionary, reason, self.grounded[reason.value()]))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )
+            }
+            .expect("Writing to a String cannot fail");
+        }
+        for witness in self.witnesses.iter() {
+            writeln!(
+                result,
+                "{}, as its acceptance condition accepts {} and rejects {}",
+                literal(dictionary, witness.statement, Term::UND),
+                dictionary
+                    .print_interpretation(&witness.accepting)
+                    .to_string()
+                    .trim_end(),
+                dictionary
+                    .print_interpretation(&witness.rejecting)
+                    .to_string()
+                    .trim_end()
+            )
+            .expect("Writing to a String cannot fail");
+        }
+        result
+    }
+
+    /// Renders the derivations as a directed acyclic graph in the DOT language of graphviz.
+    ///
+    /// Each decided statement is labelled with its value and iteration and has an edge from each of its reasons.
+    /// Undecided statements are drawn dashed.
+    pub fn to_dot(&self, dictionary: &PrintDictionary) -> String {
+        let mut result = String::from("digraph {\n");
+        for derivation in self.derivations.iter() {
+            writeln!(
+                result,
+                "    s{} [label=\"{} [{}]\"];",
+                derivation.statement.value(),
+                escape_dot(&literal(dictionary, derivation.statement, derivation.value)),
+                derivation.iteration
+            )
+            .expect("Writing to a String cannot fail");
+        }
+        for witness in self.witnesses.iter() {
+            writeln!(
+                result,
+                "    s{} [label=\"{}\", style=dashed];",
+                witness.statement.value(),
+                escape_dot(&literal(dictionary, witness.statement, Term::UND))
+            )
+            .expect("Writing to a String cannot fail");
+        }
+        for derivation in self.derivations.iter() {
+            for reason in derivation.reasons.iter() {
+                writeln!(
+                    result,
+                    "    s{} -> s{};",
+                    reason.value(),
+                    derivation.statement.value()
+                )
+                .expect("Writing to a String cannot fail");
+            }
+        }
+        result.push_str("}\n");
+        result
+    }
+}
+
+/// Reason why a [StableConflict] rules out each stable model.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// The acceptance conditions of the core contradict each other, i.e. there is no two valued model at all.
+    Inconsistent,
+    /// Each two valued model assigns a value to a statement of the core, which is not justified by the grounded model of its reduct.
+    Unjustified,
+}
+
+/// Explanation why an ADF has no stable model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StableConflict {
+    /// The kind of the conflict.
+    pub kind: ConflictKind,
+    /// An irreducible set of statements, which causes the conflict, i.e. removing any statement resolves it.
+    pub core: Vec<Var>,
+}
+
+impl StableConflict {
+    /// Renders the conflict as text.
+    pub fn to_text(&self, dictionary: &PrintDictionary) -> String {
+        let core = self
+            .core
+            .iter()
+            .map(|&statement| {
+                dictionary
+                    .name(statement)
+                    .expect("Statement originates from the same ADF as the dictionary")
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.kind {
+            ConflictKind::Inconsistent => format!(
+                "No stable model exists, as the acceptance conditions of {core} contradict each other\n"
+            ),
+            ConflictKind::Unjustified => format!(
+                "No stable model exists, as each two valued model assigns an unjustified value to one of {core}\n"
+            ),
+        }
+    }
+}
+
+/// Stable models of an [ADF][Adf], computed by the nogood-learner, which records the conflicts of the rejected two valued models.
+///
+/// Once the search is exhausted without a stable model, [explanation][ExplainedStableModels::explanation] derives the [StableConflict] from the recorded conflicts, without searching again.
+#[derive(Debug)]
+pub struct ExplainedStableModels<'a> {
+    adf: &'a mut Adf,
+    heuristic: Heuristic<'a>,
+    search: NoGoodSearch,
+    /// The unjustified statements of each rejected two valued model.
+    unjustified: Vec<Vec<Var>>,
+    found: bool,
+}
+
+impl<'a> ExplainedStableModels<'a> {
+    pub(super) fn new(adf: &'a mut Adf, heuristic: Heuristic<'a>) -> Self {
+        let grounded = adf.grounded();
+        let search = NoGoodSearch::new(adf, &grounded);
+        Self {
+            adf,
+            heuristic,
+            search,
+            unjustified: Vec::new(),
+            found: false,
+        }
+    }
+
+    /// Explains why the ADF has no stable model.
+    /// Returns [None] if a stable model has been found.
+    /// The remaining models are enumerated first, if the search has not been exhausted yet.
+    pub fn explanation(mut self) -> Option<StableConflict> {
+        if self.by_ref().count() > 0 || self.found {
+            return None;
+        }
+        if self.unjustified.is_empty() {
+            return Some(StableConflict {
+                kind: ConflictKind::Inconsistent,
+                core: self.adf.inconsistent_core(),
+            });
+        }
+        let mut core = self.unjustified.concat();
+        core.sort();
+        core.dedup();
+        for statement in core.clone() {
+            let hits_all = self.unjustified.iter().all(|unjustified| {
+                unjustified
+                    .iter()
+                    .any(|var| *var != statement && core.contains(var))
+            });
+            if hits_all {
+                core.retain(|var| *var != statement);
+            }
+        }
+        Some(StableConflict {
+            kind: ConflictKind::Unjustified,
+            core,
+        })
+    }
+}
+
+impl Iterator for ExplainedStableModels<'_> {
+    type Item = Vec<Term>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let recorded = RefCell::new(std::mem::take(&mut self.unjustified));
+        let model = self
+            .search
+            .next_model(
+                self.adf,
+                self.heuristic.get_heuristic(),
+                |adf: &mut Adf, interpretation: &[Term]| {
+                    let unjustified = adf.unjustified(interpretation);
+                    let stable = unjustified.is_empty();
+                    if !stable {
+                        recorded.borrow_mut().push(unjustified);
+                    }
+                    stable
+                },
+                &Budget::default(),
+            )
+            .map(|model| model.expect("An unlimited budget cannot be exhausted"));
+        self.unjustified = recorded.into_inner();
+        self.found |= model.is_some();
+        model
+    }
+}
+
+fn literal(dictionary: &PrintDictionary, statement: Var, value: Term) -> String {
+    let name = dictionary
+        .name(statement)
+        .expect("Statement originates from the same ADF as the dictionary");
+    if !value.is_truth_value() {
+        format!("u({name})")
+    } else if value.is_true() {
+        format!("T({name})")
+    } else {
+        format!("F({name})")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{adf::Adf, parser::AdfParser};
+    use test_log::test;
+
+    #[test]
+    fn render() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,c(v)).ac(b,neg(a)).ac(c,c).").unwrap();
+        let mut adf = Adf::from_parser(&parser);
+        let explanation = adf.explain_grounded();
+        let dictionary = adf.print_dictionary();
+
+        assert_eq!(
+            explanation.to_text(&dictionary),
+            "T(a) in iteration 0, as its acceptance condition is constant\nF(b) in iteration 1, because of T(a)\nu(c), as its acceptance condition accepts T(a) F(b) T(c) and rejects T(a) F(b) F(c)\n"
+        );
+        assert_eq!(
+            explanation.to_dot(&dictionary),
+            "digraph {\n    s0 [label=\"T(a) [0]\"];\n    s1 [label=\"F(b) [1]\"];\n    s2 [label=\"u(c)\", style=dashed];\n    s0 -> s1;\n}\n"
+        );
+    }
+}
@@ -86,7 +86,7 @@ pub(crate) fn heu_rand(adf: &Adf, interpr: &[Term]) -> Option<(Var, Term)> {
     if possible.is_empty() {
         return None;
     }
-    let mut rng = adf.rng.borrow_mut();
+    let mut rng = adf.rng.lock().expect("Lock on rng failed");
     if let Ok(position) = usize::try_from(rng.next_u64() % (possible.len() as u64)) {
         Some((Var::from(position), rng.gen_bool(0.5).into()))
     } else {
@@ -0,0 +1,163 @@
+/*!
+This module contains the types for the facet-based navigation of the models of an [ADF][super::Adf].
+
+A facet is a value of a statement, which holds in some but not all models of the chosen semantics.
+Fixing a facet removes all models, in which it does not hold, from the model space.
+Use [navigate][super::Adf::navigate] to compute the facets of the remaining model space.
+ */
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    datatypes::{budget::Interrupted, Term, Var},
+    obdd::CountOverflow,
+};
+
+/// Semantics, whose models are navigated.
+#[derive(
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum NavigationSemantics {
+    /// Complete models, where a statement might be undecided.
+    Complete,
+    /// Stable models.
+    Stable,
+    /// Two valued models.
+    TwoValued,
+}
+
+/// A value of a statement.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Facet {
+    /// The statement.
+    pub statement: Var,
+    /// The value of the statement, where each [Term] which is not a truth value means undecided.
+    pub value: Term,
+}
+
+impl Facet {
+    /// Creates a new facet, which assigns the given value to the statement.
+    pub fn new(statement: Var, value: Term) -> Self {
+        Self { statement, value }
+    }
+
+    /// Returns [true], if the facet holds in the given model.
+    pub fn holds(&self, model: &[Term]) -> bool {
+        let term = model[self.statement.value()];
+        if self.value.is_truth_value() {
+            term == self.value
+        } else {
+            !term.is_truth_value()
+        }
+    }
+}
+
+/// A [Facet] together with the number of models, which remain after fixing it.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FacetCount {
+    /// The facet.
+    pub facet: Facet,
+    /// Number of models, in which the facet holds.
+    pub model_count: u128,
+}
+
+/// The facets of the model space, which remains after fixing some facets.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Navigation {
+    /// Number of models, in which all fixed facets hold.
+    pub model_count: u128,
+    /// Facets of the remaining models, ordered by their statement and value.
+    pub facets: Vec<FacetCount>,
+}
+
+impl Navigation {
+    /// Computes the facets of the given models of the statements `0..statements`.
+    pub(crate) fn from_models(statements: usize, models: &[Vec<Term>]) -> Self {
+        let facets = (0..statements)
+            .flat_map(|statement| {
+                [Term::BOT, Term::TOP, Term::UND]
+                    .into_iter()
+                    .map(move |value| Facet::new(Var(statement), value))
+            })
+            .map(|facet| FacetCount {
+                facet,
+                model_count: models.iter().filter(|model| facet.holds(model)).count() as u128,
+            })
+            .filter(|count| count.model_count > 0 && count.model_count < models.len() as u128)
+            .collect();
+        Self {
+            model_count: models.len() as u128,
+            facets,
+        }
+    }
+}
+
+/// The reason why the facets could not be computed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NavigationError {
+    /// The computation has been [interrupted][Interrupted].
+    Interrupted(Interrupted),
+    /// The number of models does not fit into the counts.
+    CountOverflow,
+}
+
+impl Display for NavigationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Interrupted(reason) => write!(f, "{reason}"),
+            Self::CountOverflow => write!(f, "{CountOverflow}"),
+        }
+    }
+}
+
+impl std::error::Error for NavigationError {}
+
+impl From<Interrupted> for NavigationError {
+    fn from(reason: Interrupted) -> Self {
+        Self::Interrupted(reason)
+    }
+}
+
+impl From<CountOverflow> for NavigationError {
+    fn from(_: CountOverflow) -> Self {
+        Self::CountOverflow
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn from_models() {
+        let models = vec![
+            vec![Term::TOP, Term::BOT, Term::BOT],
+            vec![Term::BOT, Term::TOP, Term::BOT],
+            vec![Term(3), Term(4), Term::BOT],
+        ];
+        assert!(Facet::new(Var(0), Term::UND).holds(&models[2]));
+        assert!(!Facet::new(Var(0), Term::UND).holds(&models[0]));
+
+        let navigation = Navigation::from_models(3, &models);
+        assert_eq!(navigation.model_count, 3);
+        assert_eq!(navigation.facets.len(), 6);
+        assert!(navigation.facets.iter().all(|count| count.model_count == 1));
+        assert!(navigation
+            .facets
+            .iter()
+            .all(|count| count.facet.statement != Var(2)));
+
+        assert_eq!(Navigation::from_models(3, &[]), Navigation::default());
+    }
+}
@@ -0,0 +1,296 @@
+/*!
+This module contains the encoding of preferred reasoning tasks on an [ADF][super::Adf] into quantified boolean formulae.
+
+The preferred interpretations are the admissible interpretations, which are maximal with respect to the information ordering.
+Deciding whether a statement is true in all of them is hard for the second level of the polynomial hierarchy, so it cannot be encoded into a [Cnf] of polynomial size in general.
+Instead, the resulting [Qbf] quantifies over interpretations and is printed in the QDIMACS format, so it can be decided by external QBF solvers.
+The acceptance conditions are encoded with the Tseitin transformation of the nodes of the [Bdd], as in the [cnf module][super::cnf].
+
+# Example
+```
+use adf_bdd::adf::{qbf::QbfTask, Adf};
+use adf_bdd::datatypes::Var;
+use adf_bdd::parser::AdfParser;
+
+let parser = AdfParser::default();
+parser.parse()("s(a).s(b).ac(a,neg(b)).ac(b,neg(a)).").unwrap();
+let adf = Adf::from_parser(&parser);
+let qbf = adf.to_qbf(QbfTask::SkepticalPreferred, Var(0));
+assert!(qbf.to_string().contains("p cnf"));
+```
+ */
+use std::fmt::Display;
+
+use crate::{
+    datatypes::{Term, Var},
+    obdd::Bdd,
+};
+
+use super::cnf::{Cnf, CnfSemantics};
+
+/// Reasoning tasks, which are encoded into a [Qbf].
+#[derive(
+    strum::Display,
+    strum::EnumString,
+    strum::EnumVariantNames,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum QbfTask {
+    /// Whether the statement is true in some preferred interpretation.
+    CredulousPreferred,
+    /// Whether the statement is true in all preferred interpretations.
+    SkepticalPreferred,
+}
+
+/// A quantified boolean formula in prenex normal form, whose matrix is a [Cnf].
+///
+/// The universally quantified variables are followed by an existential block of all the other variables.
+/// The variables `2i + 1` and `2i + 2` represent the `i`-th statement being true and false respectively in the interpretation, which is checked by the task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Qbf {
+    /// The encoded reasoning task.
+    pub task: QbfTask,
+    /// The universally quantified variables of the outermost block.
+    pub universal: Vec<i64>,
+    /// The matrix of the formula.
+    pub matrix: Cnf,
+}
+
+impl Qbf {
+    /// Returns the existentially quantified variables of the innermost block.
+    pub fn existential(&self) -> Vec<i64> {
+        (1..=self.matrix.variables as i64)
+            .filter(|var| !self.universal.contains(var))
+            .collect()
+    }
+}
+
+impl Display for Qbf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for comment in self.matrix.comments.iter() {
+            writeln!(f, "c {comment}")?;
+        }
+        writeln!(
+            f,
+            "p cnf {} {}",
+            self.matrix.variables,
+            self.matrix.clauses.len()
+        )?;
+        for (quantifier, block) in [("a", &self.universal), ("e", &self.existential())] {
+            if !block.is_empty() {
+                write!(f, "{quantifier} ")?;
+                for var in block.iter() {
+                    write!(f, "{var} ")?;
+                }
+                writeln!(f, "0")?;
+            }
+        }
+        for clause in self.matrix.clauses.iter() {
+            for literal in clause {
+                write!(f, "{literal} ")?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes the task for the given statement, where `ac` contains the acceptance conditions in the [Bdd].
+pub(crate) fn encode(
+    names: &[String],
+    ac: &[Term],
+    bdd: &Bdd,
+    task: QbfTask,
+    statement: Var,
+) -> Qbf {
+    let statements = names.len();
+    // the statements 0..n represent the checked interpretation, and n..2n a larger one
+    let copies = match task {
+        QbfTask::CredulousPreferred => 1,
+        QbfTask::SkepticalPreferred => 2,
+    };
+    let mut matrix = Cnf::with_statements(CnfSemantics::Admissible, copies * statements);
+    matrix
+        .comments
+        .push(format!("task {task} of {}", names[statement.value()]));
+    for (idx, name) in names.iter().enumerate() {
+        matrix
+            .comments
+            .push(format!("T({name}) {}", matrix.statement_true(Var(idx))));
+        matrix
+            .comments
+            .push(format!("F({name}) {}", matrix.statement_false(Var(idx))));
+    }
+    let admissible = admissible(&mut matrix, bdd, ac, 0);
+    let accepted = matrix.statement_true(statement);
+    let universal = match task {
+        QbfTask::CredulousPreferred => {
+            matrix.clause(&[admissible]);
+            matrix.clause(&[accepted]);
+            Vec::new()
+        }
+        QbfTask::SkepticalPreferred => {
+            // each admissible interpretation, which does not accept the statement, is not preferred
+            let larger_admissible = admissible_larger(&mut matrix, bdd, ac);
+            matrix.clause(&[-admissible, accepted, larger_admissible]);
+            (1..=2 * statements as i64).collect()
+        }
+    };
+    Qbf {
+        task,
+        universal,
+        matrix,
+    }
+}
+
+/// Returns the literal, which holds if the interpretation of the statements `offset..offset + n` is admissible.
+fn admissible(matrix: &mut Cnf, bdd: &Bdd, ac: &[Term], offset: usize) -> i64 {
+    let mut result = matrix.constant(true);
+    for (idx, &condition) in ac.iter().enumerate() {
+        let is_true = matrix.statement_true(Var(offset + idx));
+        let is_false = matrix.statement_false(Var(offset + idx));
+        let (satisfiable, refutable) = matrix.bdd_three_valued(bdd, condition, offset);
+        for forbidden in [
+            (is_true, is_false),
+            (is_true, refutable),
+            (is_false, satisfiable),
+        ] {
+            let violated = matrix.and(forbidden.0, forbidden.1);
+            result = matrix.and(result, -violated);
+        }
+    }
+    result
+}
+
+/// Returns the literal, which holds if the interpretation of the statements `n..2n` is admissible and strictly more informative than the one of `0..n`.
+fn admissible_larger(matrix: &mut Cnf, bdd: &Bdd, ac: &[Term]) -> i64 {
+    let statements = ac.len();
+    let mut result = admissible(matrix, bdd, ac, statements);
+    let mut strict = matrix.constant(false);
+    for idx in 0..statements {
+        let (is_true, is_false) = (
+            matrix.statement_true(Var(idx)),
+            matrix.statement_false(Var(idx)),
+        );
+        let (larger_true, larger_false) = (
+            matrix.statement_true(Var(statements + idx)),
+            matrix.statement_false(Var(statements + idx)),
+        );
+        let keeps_true = matrix.or(-is_true, larger_true);
+        let keeps_false = matrix.or(-is_false, larger_false);
+        result = matrix.and(result, keeps_true);
+        result = matrix.and(result, keeps_false);
+        let undecided = matrix.and(-is_true, -is_false);
+        let decided = matrix.or(larger_true, larger_false);
+        let refined = matrix.and(undecided, decided);
+        strict = matrix.or(strict, refined);
+    }
+    matrix.and(result, strict)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        adf::Adf,
+        parser::AdfParser,
+        test::{normalised, unit_propagation},
+    };
+    use test_log::test;
+
+    /// Returns whether the matrix has a model, which extends the given assignment of the first variables.
+    /// All the other variables are expected to be determined by unit propagation.
+    fn satisfiable(matrix: &Cnf, fixed: &[bool]) -> bool {
+        let mut assignment = vec![None; matrix.variables];
+        for (idx, &value) in fixed.iter().enumerate() {
+            assignment[idx] = Some(value);
+        }
+        unit_propagation(matrix, assignment).is_some()
+    }
+
+    fn assignments(variables: usize) -> impl Iterator<Item = Vec<bool>> {
+        (0..1usize << variables)
+            .map(move |bits| (0..variables).map(|idx| bits & (1 << idx) != 0).collect())
+    }
+
+    fn holds(qbf: &Qbf, statements: usize) -> bool {
+        match qbf.task {
+            QbfTask::CredulousPreferred => {
+                assignments(2 * statements).any(|checked| satisfiable(&qbf.matrix, &checked))
+            }
+            QbfTask::SkepticalPreferred => assignments(2 * statements).all(|checked| {
+                assignments(2 * statements)
+                    .any(|larger| satisfiable(&qbf.matrix, &[checked.clone(), larger].concat()))
+            }),
+        }
+    }
+
+    /// Returns whether the first interpretation is strictly less informative than the second one.
+    fn less_informative(lhs: &[Term], rhs: &[Term]) -> bool {
+        lhs != rhs
+            && lhs
+                .iter()
+                .zip(rhs.iter())
+                .all(|(l, r)| !l.is_truth_value() || l == r)
+    }
+
+    #[test]
+    fn preferred() {
+        for input in [
+            "s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,or(a,b)).",
+            "s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,and(a,neg(c))).",
+            "s(a).s(b).s(c).ac(a,c(v)).ac(b,b).ac(c,xor(a,b)).",
+        ] {
+            let parser = AdfParser::default();
+            parser.parse()(input).unwrap();
+            let mut adf = Adf::from_parser(&parser);
+            let complete = normalised(adf.complete());
+            let preferred = complete
+                .iter()
+                .filter(|model| !complete.iter().any(|other| less_informative(model, other)))
+                .collect::<Vec<_>>();
+            for idx in 0..adf.ac.len() {
+                let credulous = preferred.iter().any(|model| model[idx] == Term::TOP);
+                let skeptical = preferred.iter().all(|model| model[idx] == Term::TOP);
+                assert_eq!(
+                    holds(
+                        &adf.to_qbf(QbfTask::CredulousPreferred, Var(idx)),
+                        adf.ac.len()
+                    ),
+                    credulous,
+                    "{input} {idx}"
+                );
+                assert_eq!(
+                    holds(
+                        &adf.to_qbf(QbfTask::SkepticalPreferred, Var(idx)),
+                        adf.ac.len()
+                    ),
+                    skeptical,
+                    "{input} {idx}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn qdimacs() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).ac(a,c(v)).").unwrap();
+        let adf = Adf::from_parser(&parser);
+        let qbf = adf.to_qbf(QbfTask::SkepticalPreferred, Var(0));
+        let text = qbf.to_string();
+        assert!(text.starts_with("c task SkepticalPreferred of a\nc T(a) 1\nc F(a) 2\np cnf "));
+        assert!(text.contains("\na 1 2 0\ne 3 4 5 "));
+        assert_eq!(qbf.existential().len(), qbf.matrix.variables - 2);
+
+        let qbf = adf.to_qbf(QbfTask::CredulousPreferred, Var(0));
+        assert!(qbf.universal.is_empty());
+        assert!(qbf.to_string().contains("\ne 1 2 3 "));
+        assert_eq!(qbf.matrix.statements, 1);
+    }
+}
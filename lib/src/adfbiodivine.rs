@@ -14,11 +14,15 @@ use crate::{
             PrintDictionary, PrintableInterpretation, ThreeValuedInterpretationsIterator,
             TwoValuedInterpretationsIterator, VarContainer,
         },
+        budget::{Budget, Interrupted},
         Term,
     },
+    obdd::CountOverflow,
     parser::AdfParser,
 };
 
+use std::borrow::Cow;
+
 use biodivine_lib_bdd::{boolean_expression::BooleanExpression, Bdd, BddVariableSet};
 use derivative::Derivative;
 
@@ -70,9 +74,7 @@ impl Adf {
                     new_order,
                     parser.ac_at(insert_order)
                 );
-                result.ac[*new_order] = result
-                    .varset
-                    .eval_expression(&parser.ac_at(insert_order).expect("Insert order needs to exist, as all the data originates from the same parser object").to_boolean_expr());
+                result.ac[*new_order] = parser.ac_at(insert_order).expect("Insert order needs to exist, as all the data originates from the same parser object").as_formula().to_biodivine(&result.varset);
                 log::trace!("instantiated {}", result.ac[*new_order]);
             });
         log::info!("[Success] instantiated");
@@ -89,24 +91,21 @@ impl Adf {
     }
 
     pub(crate) fn stm_rewriting(&mut self, parser: &AdfParser) {
-        let expr = parser.formula_order().iter().enumerate().fold(
-            BooleanExpression::Const(true),
+        let rewrite = parser.formula_order().iter().enumerate().fold(
+            self.varset.mk_true(),
             |acc, (insert_order, new_order)| {
-                BooleanExpression::And(
-                    Box::new(acc),
-                    Box::new(BooleanExpression::Iff(
-                        Box::new(BooleanExpression::Variable(
-                            self.ordering
-                                .name(crate::datatypes::Var(*new_order))
-                                .expect("Variable should exist"),
-                        )),
-                        Box::new(parser.ac_at(insert_order).expect("Insert order needs to exist, as all the data originates from the same parser object").to_boolean_expr()),
-                    )),
-                )
+                let var = self.varset.mk_var_by_name(
+                    &self
+                        .ordering
+                        .name(crate::datatypes::Var(*new_order))
+                        .expect("Variable should exist"),
+                );
+                let ac = parser.ac_at(insert_order).expect("Insert order needs to exist, as all the data originates from the same parser object").as_formula().to_biodivine(&self.varset);
+                acc.and(&var.iff(&ac))
             },
         );
-        log::trace!("{:?}", expr);
-        self.rewrite = Some(self.varset.eval_expression(&expr));
+        log::trace!("{}", rewrite);
+        self.rewrite = Some(rewrite);
     }
 
     /// returns `true` if the stable rewriting for this ADF exists.
@@ -231,100 +230,135 @@ impl Adf {
     where
         'a: 'b,
     {
-        TwoValuedInterpretationsIterator::from_bdd(&self.grounded_internal(&self.ac)).filter(
-            |terms| {
-                let reduction_list = self
-                    .vars
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(idx, elem)| {
-                        if terms[idx].is_truth_value() && !terms[idx].is_true() {
-                            Some((*elem, false))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<(biodivine_lib_bdd::BddVariable, bool)>>();
-                let reduct = self
-                    .ac
-                    .iter()
-                    .map(|ac| ac.restrict(&reduction_list))
-                    .collect::<Vec<_>>();
-                let grounded = self.grounded_internal(&reduct);
-                terms
-                    .iter()
-                    .zip(grounded.iter())
-                    .all(|(left, right)| left.cmp_information(right))
-            },
-        )
+        TwoValuedInterpretationsIterator::from_bdd(&self.grounded_internal(&self.ac))
+            .filter(|terms| self.is_stable(terms))
+    }
+
+    /// Checks whether the given two valued interpretation is stable, i.e. whether it coincides with the grounded model of its reduct.
+    fn is_stable(&self, terms: &[Term]) -> bool {
+        let reduction_list = self
+            .vars
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, elem)| {
+                if terms[idx].is_truth_value() && !terms[idx].is_true() {
+                    Some((*elem, false))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(biodivine_lib_bdd::BddVariable, bool)>>();
+        let reduct = self
+            .ac
+            .iter()
+            .map(|ac| ac.restrict(&reduction_list))
+            .collect::<Vec<_>>();
+        let grounded = self.grounded_internal(&reduct);
+        terms
+            .iter()
+            .zip(grounded.iter())
+            .all(|(left, right)| left.cmp_information(right))
     }
 
     /// Computes the stable models.
     /// This variant returns all stable models and utilises a rewrite of the ADF as one big conjunction of equalities (`if and only if`).
     pub fn stable_bdd_representation(&self) -> Vec<Vec<Term>> {
-        let smc = self.stable_model_candidates();
+        self.stable_bdd_representation_budgeted(&Budget::default())
+            .expect("An unlimited budget cannot be exhausted")
+    }
+
+    /// Computes the stable models within the given [Budget].
+    /// This variant returns all stable models and utilises a rewrite of the ADF as one big conjunction of equalities (`if and only if`).
+    /// Returns the reason of the [interruption][Interrupted] if the budget has been exhausted.
+    pub fn stable_bdd_representation_budgeted(
+        &self,
+        budget: &Budget,
+    ) -> Result<Vec<Vec<Term>>, Interrupted> {
+        let smc = self.stable_model_candidates_budgeted(budget)?;
         log::debug!("[Start] checking for stability");
-        smc.into_iter()
-            .filter(|terms| {
-                let reduction_list = self
-                    .vars
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(idx, elem)| {
-                        if terms[idx].is_truth_value() && !terms[idx].is_true() {
-                            Some((*elem, false))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<(biodivine_lib_bdd::BddVariable, bool)>>();
-                let reduct = self
-                    .ac
-                    .iter()
-                    .map(|ac| ac.restrict(&reduction_list))
-                    .collect::<Vec<_>>();
-                let grounded = self.grounded_internal(&reduct);
-                terms
-                    .iter()
-                    .zip(grounded.iter())
-                    .all(|(left, right)| left.cmp_information(right))
-            })
-            .collect::<Vec<Vec<Term>>>()
+        let mut result = Vec::new();
+        for terms in smc {
+            budget.check(0)?;
+            if self.is_stable(&terms) {
+                result.push(terms);
+            }
+        }
+        Ok(result)
     }
 
     pub(crate) fn stable_model_candidates(&self) -> Vec<Vec<Term>> {
-        let internal_rewriting: Bdd;
-        let sr: &Bdd;
-        if self.has_stm_rewriting() {
-            sr = self.rewrite.as_ref().expect("self.rewrite has to exist, because it has been checked just before calling this reference");
-        } else {
-            internal_rewriting = self.stable_representation();
-            sr = &internal_rewriting;
-        }
+        self.stable_model_candidates_budgeted(&Budget::default())
+            .expect("An unlimited budget cannot be exhausted")
+    }
+
+    pub(crate) fn stable_model_candidates_budgeted(
+        &self,
+        budget: &Budget,
+    ) -> Result<Vec<Vec<Term>>, Interrupted> {
+        let sr = self.stable_representation_cow(budget)?;
         log::debug!("[Start] construct stable model candidates");
         sr.sat_valuations()
             .map(|valuation| {
-                self.vars
-                    .iter()
-                    .map(|var| {
-                        if valuation.value(*var) {
-                            Term::TOP
-                        } else {
-                            Term::BOT
-                        }
-                    })
-                    .collect::<Vec<Term>>()
+                budget.check(sr.size())?;
+                Ok(self.valuation_to_terms(&valuation))
             })
-            .collect::<Vec<Vec<Term>>>()
+            .collect::<Result<Vec<Vec<Term>>, Interrupted>>()
+    }
+
+    /// Computes the two valued models, using the single-formula representation to enumerate them.
+    pub fn two_valued(&self) -> Vec<Vec<Term>> {
+        self.stable_model_candidates()
+    }
+
+    /// Counts the two valued models symbolically on the single-formula representation, without enumerating them.
+    /// Returns [CountOverflow] if the number of models does not fit into an [u128].
+    pub fn two_valued_count(&self) -> Result<u128, CountOverflow> {
+        let sr = self
+            .stable_representation_cow(&Budget::default())
+            .expect("An unlimited budget cannot be exhausted");
+        u128::try_from(sr.exact_cardinality()).map_err(|_| CountOverflow)
+    }
+
+    /// Counts the stable models, using the single-formula representation to enumerate the candidates.
+    /// In contrast to [stable_bdd_representation][Adf::stable_bdd_representation], the models are not materialised.
+    pub fn stable_count(&self) -> usize {
+        let sr = self
+            .stable_representation_cow(&Budget::default())
+            .expect("An unlimited budget cannot be exhausted");
+        sr.sat_valuations()
+            .filter(|valuation| self.is_stable(&self.valuation_to_terms(valuation)))
+            .count()
+    }
+
+    fn valuation_to_terms(&self, valuation: &biodivine_lib_bdd::BddValuation) -> Vec<Term> {
+        self.vars
+            .iter()
+            .map(|var| {
+                if valuation.value(*var) {
+                    Term::TOP
+                } else {
+                    Term::BOT
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the stable rewriting, if it exists, or computes the stable representation otherwise.
+    fn stable_representation_cow(&self, budget: &Budget) -> Result<Cow<'_, Bdd>, Interrupted> {
+        match self.rewrite.as_ref() {
+            Some(rewrite) => Ok(Cow::Borrowed(rewrite)),
+            None => self.stable_representation_budgeted(budget).map(Cow::Owned),
+        }
     }
 
     /// compute the stable representation
-    fn stable_representation(&self) -> Bdd {
+    fn stable_representation_budgeted(&self, budget: &Budget) -> Result<Bdd, Interrupted> {
         log::debug!("[Start] stable representation rewriting");
-        self.ac.iter().enumerate().fold(
+        self.ac.iter().enumerate().try_fold(
             self.varset.eval_expression(&BooleanExpression::Const(true)),
             |acc, (idx, formula)| {
-                acc.and(
+                budget.check(acc.size())?;
+                Ok(acc.and(
                     &formula.iff(
                         &self.varset.eval_expression(&BooleanExpression::Variable(
                             self.ordering
@@ -332,7 +366,7 @@ impl Adf {
                                 .expect("Variable should exist"),
                         )),
                     ),
-                )
+                ))
             },
         )
     }
@@ -486,6 +520,18 @@ mod test {
         );
     }
 
+    #[test]
+    fn huge_bounds() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,atmost(18446744073709551615,a)).ac(b,atleast(5000000000000,b)).ac(c,exactly(18446744073709551615,a,c)).")
+            .unwrap();
+        let adf = Adf::from_parser(&parser);
+        let expected = vec![Term::TOP, Term::BOT, Term::BOT];
+        assert_eq!(adf.grounded(), expected);
+        assert_eq!(adf.hybrid_step().grounded(), expected);
+        assert_eq!(crate::adf::Adf::from_parser(&parser).grounded(), expected);
+    }
+
     #[test]
     fn grounded_eq_naive() {
         let parser = AdfParser::default();
@@ -641,4 +687,54 @@ mod test {
         assert_eq!(stable_naive, stable_v2);
         assert_eq!(stable_v2, stable_v3);
     }
+
+    #[test]
+    fn budgeted() {
+        use crate::datatypes::budget::CancellationToken;
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).ac(a,neg(b)).ac(b,neg(a)).").unwrap();
+        let adf = Adf::from_parser(&parser);
+
+        assert_eq!(
+            adf.stable_bdd_representation_budgeted(&Budget::new()),
+            Ok(adf.stable_bdd_representation())
+        );
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert_eq!(
+            adf.stable_bdd_representation_budgeted(&Budget::new().with_token(token)),
+            Err(Interrupted::Cancelled)
+        );
+        assert_eq!(
+            adf.stable_bdd_representation_budgeted(&Budget::new().with_node_limit(0)),
+            Err(Interrupted::NodeLimit)
+        );
+    }
+
+    #[test]
+    fn counting() {
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).s(d).ac(a,c(v)).ac(b,b).ac(c,and(a,b)).ac(d,neg(b)).\ns(e).ac(e,and(b,or(neg(b),c(f)))).s(f).\n\nac(f,xor(a,e)).")
+            .unwrap();
+        let adf = Adf::from_parser(&parser);
+        assert_eq!(adf.two_valued_count(), Ok(adf.two_valued().len() as u128));
+        assert_eq!(adf.stable_count(), adf.stable_bdd_representation().len());
+
+        let parser = AdfParser::default();
+        parser.parse()("s(a).s(b).s(c).ac(a,neg(b)).ac(b,neg(a)).ac(c,c).").unwrap();
+        let adf = Adf::from_parser_with_stm_rewrite(&parser);
+        assert_eq!(adf.two_valued_count(), Ok(4));
+        assert_eq!(adf.stable_count(), 2);
+
+        // each statement doubles the number of two valued models
+        let input = (0..140)
+            .map(|idx| format!("s(x{idx}).ac(x{idx},x{idx})."))
+            .collect::<String>();
+        let parser = AdfParser::default();
+        parser.parse()(&input).unwrap();
+        let adf = Adf::from_parser(&parser);
+        assert_eq!(adf.two_valued_count(), Err(CountOverflow));
+        assert_eq!(adf.hybrid_step().two_valued_count(), Err(CountOverflow));
+    }
 }
@@ -1,4 +1,6 @@
 //! Collection of all the necessary datatypes of the system.
 pub mod adf;
 mod bdd;
+pub mod budget;
+pub mod graph;
 pub use bdd::*;
@@ -66,6 +66,48 @@ impl VarContainer {
     pub fn print_dictionary(&self) -> PrintDictionary {
         PrintDictionary::new(self)
     }
+
+    /// Creates a deep copy, which does not share its dictionary with `self`.
+    /// Revisions of the copy do not affect other holders of the original dictionary.
+    pub(crate) fn detached(&self) -> Self {
+        VarContainer {
+            names: Arc::new(RwLock::new(
+                self.names.read().expect("ReadLock on names failed").clone(),
+            )),
+            mapping: Arc::new(RwLock::new(
+                self.mapping
+                    .read()
+                    .expect("ReadLock on mapping failed")
+                    .clone(),
+            )),
+        }
+    }
+
+    /// Appends a new variable with the given name and returns it.
+    pub(crate) fn push(&mut self, name: &str) -> Var {
+        let mut names = self.names.write().expect("WriteLock on names failed");
+        let var = Var(names.len());
+        names.push(name.to_string());
+        self.mapping
+            .write()
+            .expect("WriteLock on mapping failed")
+            .insert(name.to_string(), var.value());
+        var
+    }
+
+    /// Removes the given variable; each greater variable is decremented by one.
+    pub(crate) fn remove(&mut self, var: Var) {
+        let mut names = self.names.write().expect("WriteLock on names failed");
+        names.remove(var.value());
+        let mut mapping = self.mapping.write().expect("WriteLock on mapping failed");
+        mapping.clear();
+        mapping.extend(
+            names
+                .iter()
+                .enumerate()
+                .map(|(idx, name)| (name.clone(), idx)),
+        );
+    }
 }
 /// A struct which holds the dictionary to print interpretations and allows to instantiate printable interpretations.
 #[derive(Debug)]
@@ -89,6 +131,11 @@ impl PrintDictionary {
     {
         PrintableInterpretation::new(interpretation, &self.ordering)
     }
+
+    /// Returns the name of the given statement, if it exists.
+    pub fn name(&self, var: Var) -> Option<String> {
+        self.ordering.name(var)
+    }
 }
 
 /// A struct to print a representation, it will be instantiated by [Adf][crate::adf::Adf] by calling the method [print_interpretation][`crate::adf::Adf::print_interpretation`].
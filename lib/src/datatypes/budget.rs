@@ -0,0 +1,168 @@
+//! Cooperative cancellation and resource budgets for the computation of models.
+//!
+//! A [Budget] is checked inside the main loops of the solving methods, which end the run with an [Interrupted] result as soon as it is exhausted.
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// A token to cooperatively cancel a running computation.
+///
+/// Clones of a token share their state, so one clone can be handed to a computation while another one is used to [cancel][CancellationToken::cancel] it from a different thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token, which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of all computations, which use this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns [true], if the cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// The reason why a computation has been interrupted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Interrupted {
+    /// The [CancellationToken] has been cancelled.
+    Cancelled,
+    /// The wall-clock time budget has been exceeded.
+    Timeout,
+    /// The number of nodes in the BDD exceeded the node budget.
+    NodeLimit,
+}
+
+impl Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "computation has been cancelled"),
+            Self::Timeout => write!(f, "computation exceeded its time budget"),
+            Self::NodeLimit => write!(f, "computation exceeded its BDD node budget"),
+        }
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Limits for a computation, consisting of an optional [CancellationToken], a wall-clock deadline and a maximal number of BDD nodes.
+///
+/// The default budget is unlimited.
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    token: Option<CancellationToken>,
+    deadline: Option<Instant>,
+    max_nodes: Option<usize>,
+}
+
+impl Budget {
+    /// Creates a new, unlimited budget.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [CancellationToken], which is checked by the computation.
+    pub fn with_token(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Sets the wall-clock time budget, starting now.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    /// Sets the maximal number of nodes the BDD may consist of.
+    pub fn with_node_limit(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Checks whether the budget is exhausted, given the current number of BDD nodes.
+    /// Returns the reason of the [interruption][Interrupted] if this is the case.
+    pub fn check(&self, nodes: usize) -> Result<(), Interrupted> {
+        if self
+            .token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            Err(Interrupted::Cancelled)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Err(Interrupted::Timeout)
+        } else if self.max_nodes.is_some_and(|max_nodes| nodes > max_nodes) {
+            Err(Interrupted::NodeLimit)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Ends the given iterator after its first [Interrupted] element.
+pub(crate) fn take_until_interrupted<T, I>(iter: I) -> impl Iterator<Item = Result<T, Interrupted>>
+where
+    I: Iterator<Item = Result<T, Interrupted>>,
+{
+    iter.scan(false, |interrupted, result| {
+        if *interrupted {
+            None
+        } else {
+            *interrupted = result.is_err();
+            Some(result)
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn check() {
+        assert_eq!(Budget::new().check(usize::MAX), Ok(()));
+
+        let token = CancellationToken::new();
+        let budget = Budget::new().with_token(token.clone()).with_node_limit(10);
+        assert_eq!(budget.check(10), Ok(()));
+        assert_eq!(budget.check(11), Err(Interrupted::NodeLimit));
+        token.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(budget.check(10), Err(Interrupted::Cancelled));
+
+        let budget = Budget::new().with_timeout(Duration::ZERO);
+        assert_eq!(budget.check(0), Err(Interrupted::Timeout));
+        let budget = Budget::new().with_timeout(Duration::from_secs(3600));
+        assert_eq!(budget.check(0), Ok(()));
+    }
+
+    #[test]
+    fn take_until() {
+        let results = vec![
+            Ok(1),
+            Err(Interrupted::Timeout),
+            Ok(2),
+            Err(Interrupted::Cancelled),
+        ];
+        assert_eq!(
+            take_until_interrupted(results.into_iter()).collect::<Vec<_>>(),
+            vec![Ok(1), Err(Interrupted::Timeout)]
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! Representation of the dependency graph between statements.
+//!
+//! There is an edge from a statement *s* to a statement *t* whenever *s* occurs in the acceptance condition of *t*.
+//! Nodes are identified by the position of the statement in the [VarContainer][super::adf::VarContainer].
+use std::collections::{BTreeSet, VecDeque};
+
+/// Directed graph over the statements of an ADF, where each node is identified by a [usize].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    /// Creates a new graph with `size` nodes and no edges.
+    pub fn new(size: usize) -> Self {
+        Self {
+            successors: vec![Vec::new(); size],
+            predecessors: vec![Vec::new(); size],
+        }
+    }
+
+    /// Adds an edge from `from` to `to`. Duplicate edges are ignored.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        if let Err(pos) = self.successors[from].binary_search(&to) {
+            self.successors[from].insert(pos, to);
+        }
+        if let Err(pos) = self.predecessors[to].binary_search(&from) {
+            self.predecessors[to].insert(pos, from);
+        }
+    }
+
+    /// Returns the number of nodes in the graph.
+    pub fn len(&self) -> usize {
+        self.successors.len()
+    }
+
+    /// Returns [true] if the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.successors.is_empty()
+    }
+
+    /// Returns the ordered list of nodes which can be reached by one edge from `node`.
+    pub fn successors(&self, node: usize) -> &[usize] {
+        &self.successors[node]
+    }
+
+    /// Returns the ordered list of nodes which have an edge to `node`.
+    pub fn predecessors(&self, node: usize) -> &[usize] {
+        &self.predecessors[node]
+    }
+
+    /// Returns all edges of the graph as pairs of `(from, to)`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.successors
+            .iter()
+            .enumerate()
+            .flat_map(|(from, succ)| succ.iter().map(move |&to| (from, to)))
+    }
+
+    /// Returns the ordered list of nodes which are adjacent to `node`, ignoring the direction of the edges.
+    /// A self-loop is not considered to be a neighbour relation.
+    pub fn neighbours(&self, node: usize) -> Vec<usize> {
+        self.successors[node]
+            .iter()
+            .chain(self.predecessors[node].iter())
+            .copied()
+            .filter(|&other| other != node)
+            .collect::<BTreeSet<usize>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Computes the strongly connected components of the graph.
+    ///
+    /// The components are returned in topological order, i.e. if there is an edge from a node in component *i* to a node in component *j*, then *i* ≤ *j*.
+    /// Nodes inside of a component are sorted in ascending order.
+    pub fn sccs(&self) -> Vec<Vec<usize>> {
+        // iterative variant of tarjans algorithm
+        let size = self.len();
+        let mut index = vec![usize::MAX; size];
+        let mut lowlink = vec![0usize; size];
+        let mut on_stack = vec![false; size];
+        let mut stack: Vec<usize> = Vec::new();
+        let mut next_index = 0usize;
+        let mut result: Vec<Vec<usize>> = Vec::new();
+
+        for root in 0..size {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            let mut call_stack: Vec<(usize, usize)> = vec![(root, 0)];
+            index[root] = next_index;
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((node, child)) = call_stack.pop() {
+                if let Some(&succ) = self.successors[node].get(child) {
+                    call_stack.push((node, child + 1));
+                    if index[succ] == usize::MAX {
+                        index[succ] = next_index;
+                        lowlink[succ] = next_index;
+                        next_index += 1;
+                        stack.push(succ);
+                        on_stack[succ] = true;
+                        call_stack.push((succ, 0));
+                    } else if on_stack[succ] {
+                        lowlink[node] = lowlink[node].min(index[succ]);
+                    }
+                } else {
+                    if lowlink[node] == index[node] {
+                        let mut component = Vec::new();
+                        while let Some(member) = stack.pop() {
+                            on_stack[member] = false;
+                            component.push(member);
+                            if member == node {
+                                break;
+                            }
+                        }
+                        component.sort_unstable();
+                        result.push(component);
+                    }
+                    if let Some(&(parent, _)) = call_stack.last() {
+                        lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                    }
+                }
+            }
+        }
+        // tarjan yields the components in reverse topological order
+        result.reverse();
+        result
+    }
+
+    /// Returns for each node the index of its strongly connected component, with respect to the order of [sccs][Self::sccs].
+    pub fn scc_membership(&self) -> Vec<usize> {
+        let mut result = vec![0; self.len()];
+        self.sccs()
+            .iter()
+            .enumerate()
+            .for_each(|(idx, component)| component.iter().for_each(|&node| result[node] = idx));
+        result
+    }
+
+    /// Computes an ordering of the nodes by a depth-first search, where the direction of edges is ignored.
+    /// Unvisited nodes are used as new starting points in ascending order.
+    pub fn dfs_order(&self) -> Vec<usize> {
+        let mut visited = vec![false; self.len()];
+        let mut result = Vec::with_capacity(self.len());
+        for root in 0..self.len() {
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                if visited[node] {
+                    continue;
+                }
+                visited[node] = true;
+                result.push(node);
+                // push in reverse, so the smallest neighbour is visited first
+                stack.extend(
+                    self.neighbours(node)
+                        .into_iter()
+                        .rev()
+                        .filter(|&other| !visited[other]),
+                );
+            }
+        }
+        result
+    }
+
+    /// Computes an ordering of the nodes by a breadth-first search, where the direction of edges is ignored.
+    /// Unvisited nodes are used as new starting points in ascending order.
+    pub fn bfs_order(&self) -> Vec<usize> {
+        let mut visited = vec![false; self.len()];
+        let mut result = Vec::with_capacity(self.len());
+        for root in 0..self.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut queue = VecDeque::from([root]);
+            while let Some(node) = queue.pop_front() {
+                result.push(node);
+                for other in self.neighbours(node) {
+                    if !visited[other] {
+                        visited[other] = true;
+                        queue.push_back(other);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Computes an ordering of the nodes by the greedy minimum-degree elimination.
+    /// In each step the node with the least number of neighbours is chosen and its neighbours get pairwise connected before it is removed from the graph.
+    /// Ties are broken by choosing the smallest node.
+    pub fn min_degree_order(&self) -> Vec<usize> {
+        let mut adjacency: Vec<BTreeSet<usize>> = (0..self.len())
+            .map(|node| self.neighbours(node).into_iter().collect())
+            .collect();
+        let mut eliminated = vec![false; self.len()];
+        let mut result = Vec::with_capacity(self.len());
+        while let Some(node) = (0..self.len())
+            .filter(|&node| !eliminated[node])
+            .min_by_key(|&node| adjacency[node].len())
+        {
+            eliminated[node] = true;
+            result.push(node);
+            let neighbours = std::mem::take(&mut adjacency[node]);
+            for &left in neighbours.iter() {
+                adjacency[left].remove(&node);
+                for &right in neighbours.iter() {
+                    if left != right {
+                        adjacency[left].insert(right);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Computes an ordering of the nodes with the FORCE heuristic (Aloul, Markov, and Sakallah 2003).
+    ///
+    /// Each node together with its predecessors forms a hyperedge.
+    /// Nodes are repeatedly moved towards the centre of gravity of their hyperedges, as long as the total span of all hyperedges decreases.
+    pub fn force_order(&self) -> Vec<usize> {
+        let size = self.len();
+        let hyperedges: Vec<Vec<usize>> = (0..size)
+            .map(|node| {
+                let mut edge = self.predecessors[node].clone();
+                if let Err(pos) = edge.binary_search(&node) {
+                    edge.insert(pos, node);
+                }
+                edge
+            })
+            .filter(|edge| edge.len() > 1)
+            .collect();
+        let mut incident: Vec<Vec<usize>> = vec![Vec::new(); size];
+        hyperedges.iter().enumerate().for_each(|(idx, edge)| {
+            edge.iter().for_each(|&node| incident[node].push(idx));
+        });
+
+        let span = |position: &[usize]| -> usize {
+            hyperedges
+                .iter()
+                .map(|edge| {
+                    let (min, max) = edge.iter().fold((usize::MAX, 0), |(min, max), &node| {
+                        (min.min(position[node]), max.max(position[node]))
+                    });
+                    max - min
+                })
+                .sum()
+        };
+
+        let mut best_order: Vec<usize> = (0..size).collect();
+        let mut position: Vec<usize> = (0..size).collect();
+        let mut best_span = span(&position);
+        let max_iterations = 2 * (usize::BITS - size.leading_zeros()) as usize + 1;
+        for iteration in 0..max_iterations {
+            let cog: Vec<f64> = hyperedges
+                .iter()
+                .map(|edge| {
+                    edge.iter().map(|&node| position[node] as f64).sum::<f64>() / edge.len() as f64
+                })
+                .collect();
+            let target: Vec<f64> = (0..size)
+                .map(|node| {
+                    if incident[node].is_empty() {
+                        position[node] as f64
+                    } else {
+                        incident[node].iter().map(|&edge| cog[edge]).sum::<f64>()
+                            / incident[node].len() as f64
+                    }
+                })
+                .collect();
+            let mut order: Vec<usize> = (0..size).collect();
+            order.sort_by(|&lhs, &rhs| {
+                target[lhs]
+                    .total_cmp(&target[rhs])
+                    .then(position[lhs].cmp(&position[rhs]))
+            });
+            order
+                .iter()
+                .enumerate()
+                .for_each(|(pos, &node)| position[node] = pos);
+            let new_span = span(&position);
+            log::trace!("force iteration {iteration}: span {new_span}");
+            if new_span < best_span {
+                best_span = new_span;
+                best_order = order;
+            } else {
+                break;
+            }
+        }
+        best_order
+    }
+
+    /// Computes an ordering of the nodes, where the [strongly connected components][Self::sccs] are placed in topological order.
+    pub fn scc_order(&self) -> Vec<usize> {
+        self.sccs().into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_log::test;
+
+    fn example() -> DependencyGraph {
+        // 0 -> 1 -> 2 -> 1, 2 -> 3, 4 isolated, 5 -> 5
+        let mut graph = DependencyGraph::new(6);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(2, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(5, 5);
+        graph
+    }
+
+    #[test]
+    fn edges() {
+        let graph = example();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.successors(2), &[1, 3]);
+        assert_eq!(graph.predecessors(1), &[0, 2]);
+        assert_eq!(graph.neighbours(1), vec![0, 2]);
+        assert_eq!(graph.neighbours(5), Vec::<usize>::new());
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 1), (2, 3), (5, 5)]
+        );
+    }
+
+    #[test]
+    fn sccs() {
+        let graph = example();
+        let sccs = graph.sccs();
+        assert_eq!(sccs.len(), 5);
+        let position = |node: usize| sccs.iter().position(|c| c.contains(&node)).unwrap();
+        assert!(sccs.contains(&vec![1, 2]));
+        assert!(position(0) < position(1));
+        assert!(position(2) < position(3));
+        assert_eq!(graph.scc_membership()[1], graph.scc_membership()[2]);
+        assert_eq!(graph.scc_order().len(), 6);
+    }
+
+    #[test]
+    fn orderings() {
+        let graph = example();
+        assert_eq!(graph.dfs_order(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(graph.bfs_order(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(graph.min_degree_order(), vec![4, 5, 0, 1, 2, 3]);
+
+        let mut graph = DependencyGraph::new(5);
+        graph.add_edge(0, 4);
+        graph.add_edge(4, 0);
+        graph.add_edge(1, 2);
+        assert_eq!(graph.dfs_order(), vec![0, 4, 1, 2, 3]);
+        assert_eq!(graph.bfs_order(), vec![0, 4, 1, 2, 3]);
+        assert_eq!(graph.force_order(), vec![1, 2, 0, 4, 3]);
+    }
+}
@@ -31,10 +31,15 @@ Note that import and export only works if the naive library is chosen.
 # Input-file format
 Each statement is defined by an ASP-style unary predicate `s`, where the enclosed term represents the label of the statement.
 The binary predicate `ac` relates each statement to one propositional formula in prefix notation, with the logical operations and constants as follows:
-- `and(x,y)`: conjunction
-- `or(x,y)`: disjunction
+- `and(x,y,...)`: conjunction of two or more formulae
+- `or(x,y,...)`: disjunction of two or more formulae
 - `iff(x,Y)`: if and only if
 - `xor(x,y)`: exclusive or
+- `nand(x,y,...)`: negated conjunction
+- `nor(x,y,...)`: negated disjunction
+- `atleast(k,x,y,...)`: at least k of the formulae hold
+- `atmost(k,x,y,...)`: at most k of the formulae hold
+- `exactly(k,x,y,...)`: exactly k of the formulae hold
 - `neg(x)`: classical negation
 - `c(v)`: constant symbol "verum" - tautology/top
 - `c(f)`: constant symbol "falsum" - inconsistency/bot
@@ -360,5 +365,6 @@ pub mod datatypes;
 pub mod nogoods;
 pub mod obdd;
 pub mod parser;
+pub mod sat;
 #[cfg(test)]
 mod test;
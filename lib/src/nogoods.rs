@@ -157,6 +157,14 @@ impl NoGood {
             .expect("expecting to be on a 64 bit system")
     }
 
+    /// Returns the set values as pairs of the position and the value.
+    fn literals(&self) -> Vec<(usize, bool)> {
+        self.active
+            .iter()
+            .map(|pos| (pos as usize, self.value.contains(pos)))
+            .collect()
+    }
+
     #[must_use]
     /// Returns [true] if the [NoGood] does not set any value.
     pub fn is_empty(&self) -> bool {
@@ -171,18 +179,37 @@ impl From<&[Term]> for NoGood {
 }
 
 /// A structure to store [NoGoods][NoGood] and offer operations and deductions based on them.
+///
+/// The conclusions are drawn by unit propagation with two watched literals, i.e. each [NoGood] with at least two assignments is only visited if one of its two watched assignments becomes true.
 #[derive(Debug)]
 pub struct NoGoodStore {
-    store: Vec<Vec<NoGood>>,
+    /// The indices of the stored nogoods, grouped by their length.
+    store: Vec<Vec<usize>>,
+    nogoods: Vec<WatchedNoGood>,
+    /// The indices of the nogoods, which watch the assignment `2 * statement + value`.
+    watches: Vec<Vec<usize>>,
     duplicates: DuplicateElemination,
 }
 
+/// A [NoGood] together with its assignments, where the first two of them are watched.
+#[derive(Debug, Clone)]
+struct WatchedNoGood {
+    nogood: NoGood,
+    literals: Vec<(usize, bool)>,
+    removed: bool,
+}
+
 impl Display for NoGoodStore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "NoGoodStats: [")?;
         for (arity, vec) in self.store.iter().enumerate() {
             writeln!(f, "{arity}: {}", vec.len())?;
-            log::debug!("Nogoods:\n {:?}", vec);
+            log::debug!(
+                "Nogoods:\n {:?}",
+                vec.iter()
+                    .map(|&id| &self.nogoods[id].nogood)
+                    .collect::<Vec<_>>()
+            );
         }
         write!(f, "]")
     }
@@ -193,6 +220,8 @@ impl NoGoodStore {
     pub fn new(size: u32) -> NoGoodStore {
         Self {
             store: vec![Vec::new(); size as usize],
+            nogoods: Vec::new(),
+            watches: vec![Vec::new(); 2 * size as usize],
             duplicates: DuplicateElemination::Equiv,
         }
     }
@@ -215,24 +244,69 @@ impl NoGoodStore {
             idx -= 1;
             if match self.duplicates {
                 DuplicateElemination::None => true,
-                DuplicateElemination::Equiv => !self.store[idx].contains(&nogood),
+                DuplicateElemination::Equiv => !self.store[idx]
+                    .iter()
+                    .any(|&id| self.nogoods[id].nogood == nogood),
                 DuplicateElemination::Subsume => {
+                    let nogoods = &mut self.nogoods;
                     self.store
                         .iter_mut()
                         .enumerate()
                         .for_each(|(cur_idx, ng_vec)| {
                             if idx >= cur_idx {
-                                ng_vec.retain(|ng| !ng.is_violating(&nogood));
+                                ng_vec.retain(|&id| {
+                                    let subsumed = nogoods[id].nogood.is_violating(&nogood);
+                                    // the watches are dropped lazily during the propagation
+                                    nogoods[id].removed |= subsumed;
+                                    !subsumed
+                                });
                             }
                         });
                     true
                 }
             } {
-                self.store[idx].push(nogood);
+                let literals = nogood.literals();
+                self.insert(nogood, literals);
             }
         }
     }
 
+    /// Adds a learned [NoGood], whose first two assignments are watched.
+    ///
+    /// Unlike [add_ng][NoGoodStore::add_ng], the watches are valid for the current interpretation of the learner, if the first assignment is the undecided one, which the nogood concludes, and the second one has been assigned last of the others.
+    pub(crate) fn add_learned(&mut self, literals: Vec<(usize, bool)>) {
+        let nogood = NoGood::try_from_pair_iter(&mut literals.iter().copied())
+            .expect("A learned nogood contains at least one consistent assignment");
+        if !self.store[nogood.len() - 1]
+            .iter()
+            .any(|&id| self.nogoods[id].nogood == nogood)
+        {
+            self.insert(nogood, literals);
+        }
+    }
+
+    fn insert(&mut self, nogood: NoGood, literals: Vec<(usize, bool)>) {
+        let id = self.nogoods.len();
+        if literals.len() > 1 {
+            self.watch(literals[0], id);
+            self.watch(literals[1], id);
+        }
+        self.store[literals.len() - 1].push(id);
+        self.nogoods.push(WatchedNoGood {
+            nogood,
+            literals,
+            removed: false,
+        });
+    }
+
+    fn watch(&mut self, (pos, val): (usize, bool), id: usize) {
+        let code = 2 * pos + usize::from(val);
+        if code >= self.watches.len() {
+            self.watches.resize(code + 1, Vec::new());
+        }
+        self.watches[code].push(id);
+    }
+
     /// Draws a (Conclusion)[NoGood], based on the [NoGoodStore] and the given [NoGood].
     /// *Returns* [None] if there is a conflict
     pub fn conclusions(&self, nogood: &NoGood) -> Option<NoGood> {
@@ -243,7 +317,11 @@ impl NoGoodStore {
             .enumerate()
             .filter(|(len, _vec)| *len <= nogood.len())
             .filter_map(|(_len, val)| {
-                NoGood::try_from_pair_iter(&mut val.iter().filter_map(|ng| ng.conclude(nogood)))
+                NoGood::try_from_pair_iter(
+                    &mut val
+                        .iter()
+                        .filter_map(|&id| self.nogoods[id].nogood.conclude(nogood)),
+                )
             })
             .try_fold(&mut result, |acc, ng| {
                 if ng.is_violating(acc) {
@@ -260,8 +338,10 @@ impl NoGoodStore {
             .enumerate()
             .filter(|(len, _vec)| *len <= nogood.len())
             .any(|(_, vec)| {
-                vec.iter()
-                    .any(|elem| elem.is_violating(&result) || elem.is_violating(nogood))
+                vec.iter().any(|&id| {
+                    let elem = &self.nogoods[id].nogood;
+                    elem.is_violating(&result) || elem.is_violating(nogood)
+                })
             })
         {
             return None;
@@ -269,34 +349,118 @@ impl NoGoodStore {
         Some(result)
     }
 
-    /// Constructs the Closure of the conclusions drawn by the nogoods with respect to the given `interpretation`
-    pub(crate) fn conclusion_closure(&self, interpretation: &[Term]) -> ClosureResult {
-        let mut update = true;
-        let mut result = match self.conclusions(&interpretation.into()) {
-            Some(val) => {
-                log::trace!(
-                    "conclusion-closure step 1: val:{:?} -> {:?}",
-                    val,
-                    val.update_term_vec(interpretation, &mut update)
-                );
-                val.update_term_vec(interpretation, &mut update)
+    /// Propagates the values of the `assigned` statements of the given `interpretation`, until no further value can be concluded.
+    /// Each concluded value is reported to `implied` together with the assignments of the nogood, which it has been concluded from.
+    /// *Returns* the assignments of a violated nogood if there is a conflict.
+    ///
+    /// Only the nogoods, which watch one of the newly assigned values, are visited, so the values of the other assigned statements need to be propagated already.
+    /// The watches are kept between the calls, as any two assignments of a nogood are valid watches for an interpretation without any assigned values.
+    /// Hence an interpretation can always be propagated from scratch by passing all of its assigned statements.
+    pub(crate) fn propagate<F>(
+        &mut self,
+        interpretation: &mut [Term],
+        assigned: &[usize],
+        mut implied: F,
+    ) -> Option<Vec<(usize, bool)>>
+    where
+        F: FnMut(usize, &[(usize, bool)]),
+    {
+        let mut queue = assigned
+            .iter()
+            .map(|&pos| (pos, interpretation[pos].is_true()))
+            .collect::<Vec<_>>();
+        // nogoods with a single assignment are not watched
+        for &id in self.store.first().into_iter().flatten() {
+            let literals = &self.nogoods[id].literals;
+            match literal_value(interpretation, literals[0]) {
+                Some(true) => return Some(literals.clone()),
+                Some(false) => {}
+                None => {
+                    if conclude(interpretation, &mut queue, literals[0]) {
+                        implied(literals[0].0, literals);
+                    }
+                }
             }
-
-            None => return ClosureResult::Inconsistent,
-        };
-        if !update {
-            return ClosureResult::NoUpdate;
         }
-        while update {
-            match self.conclusions(&result.as_slice().into()) {
-                Some(val) => result = val.update_term_vec(&result, &mut update),
-                None => return ClosureResult::Inconsistent,
+        let mut head = 0;
+        while let Some(&(pos, val)) = queue.get(head) {
+            head += 1;
+            let code = 2 * pos + usize::from(val);
+            if code >= self.watches.len() {
+                continue;
+            }
+            let mut watchers = std::mem::take(&mut self.watches[code]);
+            let mut idx = 0;
+            let mut conflict = None;
+            while idx < watchers.len() {
+                let id = watchers[idx];
+                let watched = &mut self.nogoods[id];
+                if watched.removed {
+                    watchers.swap_remove(idx);
+                    continue;
+                }
+                if watched.literals[0] == (pos, val) {
+                    watched.literals.swap(0, 1);
+                }
+                if let Some(next) = (2..watched.literals.len()).find(|&next| {
+                    literal_value(interpretation, watched.literals[next]) != Some(true)
+                }) {
+                    watched.literals.swap(1, next);
+                    let (next_pos, next_val) = watched.literals[1];
+                    self.watches[2 * next_pos + usize::from(next_val)].push(id);
+                    watchers.swap_remove(idx);
+                    continue;
+                }
+                let other = watched.literals[0];
+                match literal_value(interpretation, other) {
+                    Some(true) => {
+                        log::trace!("ng violated: {:?}", watched.nogood);
+                        conflict = Some(watched.literals.clone());
+                        break;
+                    }
+                    Some(false) => {}
+                    None => {
+                        if conclude(interpretation, &mut queue, other) {
+                            implied(other.0, &watched.literals);
+                        }
+                    }
+                }
+                idx += 1;
+            }
+            self.watches[code] = watchers;
+            if conflict.is_some() {
+                return conflict;
             }
         }
-        ClosureResult::Update(result)
+        None
     }
 }
 
+/// Assigns the negation of the given assignment of a [NoGood] and enqueues it for the propagation.
+/// Returns [false] if the statement is not part of the interpretation.
+fn conclude(
+    interpretation: &mut [Term],
+    queue: &mut Vec<(usize, bool)>,
+    (pos, val): (usize, bool),
+) -> bool {
+    if pos < interpretation.len() {
+        log::trace!("Conclude {:?}", (pos, !val));
+        interpretation[pos] = Term::from(!val);
+        queue.push((pos, !val));
+        true
+    } else {
+        false
+    }
+}
+
+/// Returns whether the assignment of a [NoGood] holds in the interpretation, or [None] if the statement is undecided.
+fn literal_value(interpretation: &[Term], (pos, val): (usize, bool)) -> Option<bool> {
+    interpretation
+        .get(pos)
+        .filter(|term| term.is_truth_value())
+        .map(|term| term.is_true() == val)
+}
+
 /// Allows to define how costly the DuplicateElemination is done.
 #[derive(Debug, Copy, Clone)]
 pub enum DuplicateElemination {
@@ -308,49 +472,24 @@ pub enum DuplicateElemination {
     Subsume,
 }
 
-/// If the closure had some issues, it is represented with this enum
-#[derive(Debug, PartialEq, Eq)]
-pub(crate) enum ClosureResult {
-    Update(Vec<Term>),
-    NoUpdate,
-    Inconsistent,
-}
-
-impl ClosureResult {
-    /// Dead_code due to (currently) unused utility function for the [ClosureResult] enum.
-    #[allow(dead_code)]
-    pub fn is_update(&self) -> bool {
-        matches!(self, Self::Update(_))
-    }
-    /// Dead_code due to (currently) unused utility function for the [ClosureResult] enum.
-    #[allow(dead_code)]
-    pub fn is_no_update(&self) -> bool {
-        matches!(self, Self::NoUpdate)
-    }
-    /// Dead_code due to (currently) unused utility function for the [ClosureResult] enum.
-    #[allow(dead_code)]
-    pub fn is_inconsistent(&self) -> bool {
-        matches!(self, Self::Inconsistent)
-    }
-}
-
-impl TryInto<Vec<Term>> for ClosureResult {
-    type Error = &'static str;
-
-    fn try_into(self) -> Result<Vec<Term>, Self::Error> {
-        match self {
-            ClosureResult::Update(val) => Ok(val),
-            ClosureResult::NoUpdate => Err("No update occurred, use the old value instead"),
-            ClosureResult::Inconsistent => Err("Inconsistency occurred"),
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
     use test_log::test;
 
+    /// Propagates all assigned statements of the interpretation and *returns* [None] if there is a conflict.
+    fn closure(ngs: &mut NoGoodStore, interpretation: &[Term]) -> Option<Vec<Term>> {
+        let mut result = interpretation.to_vec();
+        let assigned = (0..result.len())
+            .filter(|&pos| result[pos].is_truth_value())
+            .collect::<Vec<_>>();
+        match ngs.propagate(&mut result, &assigned, |_, _| ()) {
+            Some(_) => None,
+            None => Some(result),
+        }
+    }
+
     #[test]
     fn create_ng() {
         let terms = vec![Term::TOP, Term(22), Term(13232), Term::BOT, Term::TOP];
@@ -715,7 +854,7 @@ mod test {
     }
 
     #[test]
-    fn conclusion_closure() {
+    fn closure_from_scratch() {
         let mut ngs = NoGoodStore::new(10);
         ngs.add_ng([Term::BOT].as_slice().into());
         ngs.add_ng(
@@ -743,9 +882,7 @@ mod test {
             Term(1000),
         ];
 
-        let result = ngs.conclusion_closure(&interpr);
-        assert!(result.is_update());
-        let resultint: Vec<Term> = result.try_into().expect("just checked conversion");
+        let resultint = closure(&mut ngs, &interpr).expect("no nogood is violated");
         assert_eq!(
             resultint,
             vec![
@@ -761,14 +898,7 @@ mod test {
                 Term(1000)
             ]
         );
-        let result_no_upd = ngs.conclusion_closure(&resultint);
-
-        assert!(result_no_upd.is_no_update());
-        assert_eq!(
-            <ClosureResult as TryInto<Vec<Term>>>::try_into(result_no_upd)
-                .expect_err("just checked that it is an error"),
-            "No update occurred, use the old value instead"
-        );
+        assert_eq!(closure(&mut ngs, &resultint), Some(resultint.clone()));
 
         let inconsistent_interpr = vec![
             Term::TOP,
@@ -782,14 +912,7 @@ mod test {
             Term(999),
             Term(1000),
         ];
-        let result_inconsistent = ngs.conclusion_closure(&inconsistent_interpr);
-
-        assert!(result_inconsistent.is_inconsistent());
-        assert_eq!(
-            <ClosureResult as TryInto<Vec<Term>>>::try_into(result_inconsistent)
-                .expect_err("just checked that it is an error"),
-            "Inconsistency occurred"
-        );
+        assert_eq!(closure(&mut ngs, &inconsistent_interpr), None);
 
         ngs = NoGoodStore::new(6);
         ngs.add_ng(
@@ -805,8 +928,122 @@ mod test {
         ngs.add_ng([Term(22), Term(1)].as_slice().into());
 
         assert_eq!(
-            ngs.conclusion_closure(&[Term(1), Term(3), Term(3), Term(9), Term(0), Term(1)]),
-            ClosureResult::Update(vec![Term(1), Term(0), Term(3), Term(9), Term(0), Term(1)])
+            closure(
+                &mut ngs,
+                &[Term(1), Term(3), Term(3), Term(9), Term(0), Term(1)]
+            ),
+            Some(vec![Term(1), Term(0), Term(3), Term(9), Term(0), Term(1)])
         );
     }
+
+    #[test]
+    fn watched_closure() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let random_interpretation = |rng: &mut StdRng, size: usize, density: f64| {
+            (0..size)
+                .map(|_| {
+                    if rng.gen_bool(density) {
+                        Term::from(rng.gen::<bool>())
+                    } else {
+                        Term(42)
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        for _ in 0..300 {
+            let size = rng.gen_range(1..=8);
+            let mut ngs = NoGoodStore::new(size as u32);
+            ngs.set_dup_elem(if rng.gen() {
+                DuplicateElemination::None
+            } else {
+                DuplicateElemination::Subsume
+            });
+            for _ in 0..rng.gen_range(0..=12) {
+                let density = rng.gen_range(0.1..0.6);
+                ngs.add_ng(
+                    random_interpretation(&mut rng, size, density)
+                        .as_slice()
+                        .into(),
+                );
+            }
+            for _ in 0..10 {
+                let interpr = random_interpretation(&mut rng, size, 0.3);
+                // the closure by drawing a single conclusion at a time
+                let mut expected = Some(interpr.clone());
+                let mut current = interpr.clone();
+                loop {
+                    let assigned: NoGood = current.as_slice().into();
+                    if ngs
+                        .nogoods
+                        .iter()
+                        .any(|ng| !ng.removed && ng.nogood.is_violating(&assigned))
+                    {
+                        expected = None;
+                        break;
+                    }
+                    match ngs
+                        .nogoods
+                        .iter()
+                        .filter(|ng| !ng.removed)
+                        .find_map(|ng| ng.nogood.conclude(&assigned))
+                    {
+                        Some((pos, val)) => {
+                            current[pos] = Term::from(val);
+                            expected = Some(current.clone());
+                        }
+                        None => break,
+                    }
+                }
+                assert_eq!(closure(&mut ngs, &interpr), expected, "{interpr:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_propagation() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..300 {
+            let size = rng.gen_range(2..=8);
+            let mut ngs = NoGoodStore::new(size as u32);
+            for _ in 0..rng.gen_range(0..=12) {
+                let nogood = (0..size)
+                    .map(|_| {
+                        if rng.gen_bool(0.4) {
+                            Term::from(rng.gen::<bool>())
+                        } else {
+                            Term(42)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                ngs.add_ng(nogood.as_slice().into());
+            }
+            // the choices are propagated one at a time, only passing the new ones
+            let mut incremental = vec![Term(42); size];
+            let mut choices = incremental.clone();
+            let mut conflict = ngs.propagate(&mut incremental, &[], |_, _| ()).is_some();
+            for pos in 0..size {
+                if conflict {
+                    break;
+                }
+                if incremental[pos].is_truth_value() || rng.gen() {
+                    continue;
+                }
+                let val = Term::from(rng.gen::<bool>());
+                incremental[pos] = val;
+                choices[pos] = val;
+                conflict = ngs.propagate(&mut incremental, &[pos], |_, _| ()).is_some();
+            }
+            let assigned = (0..size)
+                .filter(|&pos| choices[pos].is_truth_value())
+                .collect::<Vec<_>>();
+            let mut from_scratch = choices.clone();
+            let scratch_conflict = ngs
+                .propagate(&mut from_scratch, &assigned, |_, _| ())
+                .is_some();
+            assert_eq!(conflict, scratch_conflict, "{choices:?}");
+            if !conflict {
+                assert_eq!(incremental, from_scratch, "{choices:?}");
+            }
+        }
+    }
 }
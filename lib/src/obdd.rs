@@ -2,26 +2,31 @@
 //!
 #[cfg(feature = "frontend")]
 pub mod frontend;
+pub mod shared;
 pub mod vectorize;
-use crate::datatypes::*;
+use self::shared::{SharedMap, SharedVec};
+use crate::datatypes::{adf::VarContainer, *};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::{cell::RefCell, cmp::min, collections::HashMap, fmt::Display};
+use std::collections::{BTreeSet, HashSet};
+use std::{cmp::min, collections::HashMap, fmt::Display, sync::RwLock};
 
 /// Contains the data of (possibly) multiple roBDDs, managed over one collection of nodes.
 /// It has a couple of methods to instantiate, update, and query properties on a given roBDD.
 /// Each roBDD is identified by its corresponding [`Term`], which implicitly identifies the root node of a roBDD.
+///
+/// The nodes and caches can be [frozen][Bdd::freeze] into a read-only part, which is shared by all [clones][Clone::clone].
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Bdd {
     /// The nodes of the [`Bdd`] with their edges
-    pub nodes: Vec<BddNode>,
+    pub nodes: SharedVec<BddNode>,
     #[cfg(feature = "variablelist")]
     #[serde(skip)]
-    var_deps: Vec<HashSet<Var>>,
+    var_deps: SharedVec<HashSet<Var>>,
     #[serde(with = "vectorize")]
-    cache: HashMap<BddNode, Term>,
+    cache: SharedMap<BddNode, Term>,
     #[serde(skip, default = "Bdd::default_count_cache")]
-    count_cache: RefCell<HashMap<Term, CountNode>>,
+    count_cache: RwLock<SharedMap<Term, CountNode>>,
     #[cfg(feature = "frontend")]
     #[serde(skip)]
     sender: Option<crossbeam_channel::Sender<BddNode>>,
@@ -29,9 +34,45 @@ pub struct Bdd {
     #[serde(skip)]
     receiver: Option<crossbeam_channel::Receiver<BddNode>>,
     #[serde(skip)]
-    ite_cache: HashMap<(Term, Term, Term), Term>,
+    ite_cache: SharedMap<(Term, Term, Term), Term>,
     #[serde(skip)]
-    restrict_cache: HashMap<(Term, Var, bool), Term>,
+    restrict_cache: SharedMap<(Term, Var, bool), Term>,
+}
+
+/// The number of models exceeds the range of the exact counts of a [Bdd].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CountOverflow;
+
+impl Display for CountOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the number of models exceeds {}", u128::MAX)
+    }
+}
+
+impl std::error::Error for CountOverflow {}
+
+/// Multiplies the given number of models by `2^exponent`, i.e. by the number of assignments of `exponent` unrestricted variables.
+fn scale(count: u128, exponent: usize) -> Result<u128, CountOverflow> {
+    if count == 0 {
+        Ok(0)
+    } else {
+        u32::try_from(exponent)
+            .ok()
+            .and_then(|exponent| 1u128.checked_shl(exponent))
+            .and_then(|factor| count.checked_mul(factor))
+            .ok_or(CountOverflow)
+    }
+}
+
+/// Clamps the bound of a cardinality constraint over `operands` many operands to `operands + 1`.
+/// All bounds beyond the number of operands yield the same constants, and the clamped bound can be incremented without an overflow.
+pub(crate) fn clamp_bound(k: usize, operands: usize) -> usize {
+    k.min(operands + 1)
+}
+
+/// Escapes a label for a quoted string of the [DOT](https://graphviz.org/doc/info/lang.html) language.
+pub(crate) fn escape_dot(input: &str) -> String {
+    input.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
 impl Display for Bdd {
@@ -44,6 +85,31 @@ impl Display for Bdd {
     }
 }
 
+impl Clone for Bdd {
+    /// Clones the nodes and caches of the [`Bdd`].
+    /// The clone is detached from the communication channels of the `frontend` feature.
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            #[cfg(feature = "variablelist")]
+            var_deps: self.var_deps.clone(),
+            cache: self.cache.clone(),
+            count_cache: RwLock::new(
+                self.count_cache
+                    .read()
+                    .expect("ReadLock on count_cache failed")
+                    .clone(),
+            ),
+            #[cfg(feature = "frontend")]
+            sender: None,
+            #[cfg(feature = "frontend")]
+            receiver: None,
+            ite_cache: self.ite_cache.clone(),
+            restrict_cache: self.restrict_cache.clone(),
+        }
+    }
+}
+
 impl Default for Bdd {
     fn default() -> Self {
         Self::new()
@@ -69,48 +135,67 @@ impl Bdd {
         #[cfg(not(feature = "adhoccounting"))]
         {
             Self {
-                nodes: vec![BddNode::bot_node(), BddNode::top_node()],
+                nodes: vec![BddNode::bot_node(), BddNode::top_node()].into(),
                 #[cfg(feature = "variablelist")]
-                var_deps: vec![HashSet::new(), HashSet::new()],
-                cache: HashMap::new(),
-                count_cache: RefCell::new(HashMap::new()),
+                var_deps: vec![HashSet::new(), HashSet::new()].into(),
+                cache: SharedMap::default(),
+                count_cache: RwLock::new(SharedMap::default()),
                 #[cfg(feature = "frontend")]
                 sender: None,
                 #[cfg(feature = "frontend")]
                 receiver: None,
-                ite_cache: HashMap::new(),
-                restrict_cache: HashMap::new(),
+                ite_cache: SharedMap::default(),
+                restrict_cache: SharedMap::default(),
             }
         }
         #[cfg(feature = "adhoccounting")]
         {
             let result = Self {
-                nodes: vec![BddNode::bot_node(), BddNode::top_node()],
+                nodes: vec![BddNode::bot_node(), BddNode::top_node()].into(),
                 #[cfg(feature = "variablelist")]
-                var_deps: vec![HashSet::new(), HashSet::new()],
-                cache: HashMap::new(),
-                count_cache: RefCell::new(HashMap::new()),
+                var_deps: vec![HashSet::new(), HashSet::new()].into(),
+                cache: SharedMap::default(),
+                count_cache: RwLock::new(SharedMap::default()),
                 #[cfg(feature = "frontend")]
                 sender: None,
                 #[cfg(feature = "frontend")]
                 receiver: None,
-                ite_cache: HashMap::new(),
-                restrict_cache: HashMap::new(),
+                ite_cache: SharedMap::default(),
+                restrict_cache: SharedMap::default(),
             };
             result
                 .count_cache
-                .borrow_mut()
+                .write()
+                .expect("WriteLock on count_cache failed")
                 .insert(Term::TOP, (ModelCounts::top(), ModelCounts::top(), 0));
             result
                 .count_cache
-                .borrow_mut()
+                .write()
+                .expect("WriteLock on count_cache failed")
                 .insert(Term::BOT, (ModelCounts::bot(), ModelCounts::bot(), 0));
             result
         }
     }
 
-    fn default_count_cache() -> RefCell<HashMap<Term, CountNode>> {
-        RefCell::new(HashMap::new())
+    fn default_count_cache() -> RwLock<SharedMap<Term, CountNode>> {
+        RwLock::new(SharedMap::default())
+    }
+
+    /// Moves the nodes and caches into a read-only part, which is shared by all [clones][Clone::clone] of the [`Bdd`].
+    ///
+    /// Each clone keeps the nodes and cache entries, which it adds afterwards, as its own scratch.
+    /// So one instantiated [`Bdd`] can serve several threads, which only copy a pointer to the shared part.
+    pub fn freeze(&mut self) {
+        self.nodes.freeze();
+        #[cfg(feature = "variablelist")]
+        self.var_deps.freeze();
+        self.cache.freeze();
+        self.count_cache
+            .get_mut()
+            .expect("WriteLock on count_cache failed")
+            .freeze();
+        self.ite_cache.freeze();
+        self.restrict_cache.freeze();
     }
 
     /// Instantiates a [variable][crate::datatypes::Var] and returns the representing roBDD as a [`Term`][crate::datatypes::Term].
@@ -159,6 +244,44 @@ impl Bdd {
         self.if_then_else(term_a, not_b, term_b)
     }
 
+    /// Returns an roBDD, which represents that at least `k` of the given roBDDs hold.
+    pub fn at_least(&mut self, terms: &[Term], k: usize) -> Term {
+        let k = clamp_bound(k, terms.len());
+        self.thresholds(terms, k)[k]
+    }
+
+    /// Returns an roBDD, which represents that at most `k` of the given roBDDs hold.
+    pub fn at_most(&mut self, terms: &[Term], k: usize) -> Term {
+        let k = clamp_bound(k, terms.len());
+        let more = self.thresholds(terms, k + 1)[k + 1];
+        self.not(more)
+    }
+
+    /// Returns an roBDD, which represents that exactly `k` of the given roBDDs hold.
+    pub fn exactly(&mut self, terms: &[Term], k: usize) -> Term {
+        let k = clamp_bound(k, terms.len());
+        let thresholds = self.thresholds(terms, k + 1);
+        let not_more = self.not(thresholds[k + 1]);
+        self.and(thresholds[k], not_more)
+    }
+
+    /// Computes for each `j` in `0..=max` the roBDD, which represents that at least `j` of the given roBDDs hold.
+    ///
+    /// Uses the counting construction, which needs `terms.len() * max` if-then-else operations instead of enumerating all subsets of size `j`.
+    /// The roBDDs are added in descending order of their root variables, such that the intermediate results stay small.
+    fn thresholds(&mut self, terms: &[Term], max: usize) -> Vec<Term> {
+        let mut thresholds = vec![Term::BOT; max + 1];
+        thresholds[0] = Term::TOP;
+        let mut terms = terms.to_vec();
+        terms.sort_unstable_by_key(|term| std::cmp::Reverse(self.nodes[term.value()].var()));
+        for term in terms {
+            for j in (1..=max).rev() {
+                thresholds[j] = self.if_then_else(term, thresholds[j - 1], thresholds[j]);
+            }
+        }
+        thresholds
+    }
+
     /// Computes the interpretations represented in the roBDD, which are either models or counter-models.
     /// **goal_var** is the [variable][Var] to which the roBDD is related to and it is ensured that the goal is consistent with the respective interpretation.
     /// **goal** is a boolean [variable][Var], which defines whether the models or counter-models are of interest.
@@ -211,75 +334,167 @@ impl Bdd {
         result
     }
 
+    /// Relabels the given roBDDs, such that each [variable][crate::datatypes::Var] greater than **var** is decremented by one.
+    /// The roBDDs must not depend on **var**, so the relabelling preserves the variable order and reuses existing nodes.
+    pub fn remove_variable(&mut self, terms: &[Term], var: Var) -> Vec<Term> {
+        let mut memo = HashMap::new();
+        terms
+            .iter()
+            .map(|&term| self.remove_variable_internal(term, var, &mut memo))
+            .collect()
+    }
+
+    fn remove_variable_internal(
+        &mut self,
+        term: Term,
+        var: Var,
+        memo: &mut HashMap<Term, Term>,
+    ) -> Term {
+        let node = self.nodes[term.value()];
+        if node.var().is_constant() {
+            return term;
+        }
+        if let Some(result) = memo.get(&term) {
+            return *result;
+        }
+        debug_assert_ne!(node.var(), var, "The roBDD must not depend on {var}");
+        let lo = self.remove_variable_internal(node.lo(), var, memo);
+        let hi = self.remove_variable_internal(node.hi(), var, memo);
+        let new_var = if node.var() < var {
+            node.var()
+        } else {
+            Var(node.var().value() - 1)
+        };
+        let result = self.node(new_var, lo, hi);
+        memo.insert(term, result);
+        result
+    }
+
     /// Restrict the value of a given [variable][crate::datatypes::Var] to **val**.
     pub fn restrict(&mut self, tree: Term, var: Var, val: bool) -> Term {
-        if let Some(result) = self.restrict_cache.get(&(tree, var, val)) {
-            *result
-        } else {
-            let node = self.nodes[tree.0];
-            #[cfg(feature = "variablelist")]
-            {
-                if !self.var_deps[tree.value()].contains(&var) {
-                    return tree;
+        if let Some(result) = self.restrict_shortcut(tree, var, val) {
+            return result;
+        }
+        // the nodes above `var` are visited with an explicit stack, so the depth of the roBDD is not limited by the call stack
+        enum Task {
+            Restrict(Term),
+            Node(Term, Var),
+            Cache(Term),
+        }
+        let mut tasks = vec![Task::Restrict(tree)];
+        let mut results = Vec::new();
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Restrict(tree) => {
+                    if let Some(result) = self.restrict_shortcut(tree, var, val) {
+                        results.push(result);
+                        continue;
+                    }
+                    let node = self.nodes[tree.0];
+                    if node.var() < var {
+                        tasks.push(Task::Node(tree, node.var()));
+                        tasks.push(Task::Restrict(node.hi()));
+                        tasks.push(Task::Restrict(node.lo()));
+                    } else {
+                        tasks.push(Task::Cache(tree));
+                        tasks.push(Task::Restrict(if val { node.hi() } else { node.lo() }));
+                    }
                 }
-            }
-            #[allow(clippy::collapsible_else_if)]
-            // Readability of algorithm > code-elegance
-            if node.var() > var || node.var() >= Var::BOT {
-                tree
-            } else if node.var() < var {
-                let lonode = self.restrict(node.lo(), var, val);
-                let hinode = self.restrict(node.hi(), var, val);
-                let result = self.node(node.var(), lonode, hinode);
-                self.restrict_cache.insert((tree, var, val), result);
-                result
-            } else {
-                if val {
-                    let result = self.restrict(node.hi(), var, val);
+                Task::Node(tree, node_var) => {
+                    let hinode = results.pop().expect("The high child has been restricted");
+                    let lonode = results.pop().expect("The low child has been restricted");
+                    let result = self.node(node_var, lonode, hinode);
                     self.restrict_cache.insert((tree, var, val), result);
-                    result
-                } else {
-                    let result = self.restrict(node.lo(), var, val);
+                    results.push(result);
+                }
+                Task::Cache(tree) => {
+                    let result = *results.last().expect("The child has been restricted");
                     self.restrict_cache.insert((tree, var, val), result);
-                    result
                 }
             }
         }
+        results.pop().expect("The tree has been restricted")
+    }
+
+    /// Returns the restriction of the given roBDD, if it is cached or the roBDD does not depend on `var`.
+    fn restrict_shortcut(&self, tree: Term, var: Var, val: bool) -> Option<Term> {
+        if let Some(result) = self.restrict_cache.get(&(tree, var, val)) {
+            return Some(*result);
+        }
+        #[cfg(feature = "variablelist")]
+        {
+            if !self.var_deps[tree.value()].contains(&var) {
+                return Some(tree);
+            }
+        }
+        let node = self.nodes[tree.0];
+        (node.var() > var || node.var() >= Var::BOT).then_some(tree)
     }
 
     /// Creates an roBDD, based on the relation of three roBDDs, which are in an `if-then-else` relation.
     fn if_then_else(&mut self, i: Term, t: Term, e: Term) -> Term {
+        if let Some(result) = self.if_then_else_shortcut(i, t, e) {
+            return result;
+        }
+        // the Shannon expansion uses an explicit stack, so the depth of the roBDD is not limited by the call stack
+        enum Task {
+            Expand(Term, Term, Term),
+            Node(Var, (Term, Term, Term)),
+        }
+        let mut tasks = vec![Task::Expand(i, t, e)];
+        let mut results = Vec::new();
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Expand(i, t, e) => {
+                    if let Some(result) = self.if_then_else_shortcut(i, t, e) {
+                        results.push(result);
+                        continue;
+                    }
+                    log::trace!("if_then_else: i {i} t {t} e {e} not found");
+                    let minvar = Var(min(
+                        self.nodes[i.value()].var().value(),
+                        min(
+                            self.nodes[t.value()].var().value(),
+                            self.nodes[e.value()].var().value(),
+                        ),
+                    ));
+                    let itop = self.restrict(i, minvar, true);
+                    let ttop = self.restrict(t, minvar, true);
+                    let etop = self.restrict(e, minvar, true);
+                    let ibot = self.restrict(i, minvar, false);
+                    let tbot = self.restrict(t, minvar, false);
+                    let ebot = self.restrict(e, minvar, false);
+
+                    tasks.push(Task::Node(minvar, (i, t, e)));
+                    tasks.push(Task::Expand(ibot, tbot, ebot));
+                    tasks.push(Task::Expand(itop, ttop, etop));
+                }
+                Task::Node(minvar, key) => {
+                    let bot_ite = results.pop().expect("The low cofactor has been computed");
+                    let top_ite = results.pop().expect("The high cofactor has been computed");
+                    let result = self.node(minvar, bot_ite, top_ite);
+                    self.ite_cache.insert(key, result);
+                    results.push(result);
+                }
+            }
+        }
+        results
+            .pop()
+            .expect("The if-then-else relation has been computed")
+    }
+
+    /// Returns the result of the `if-then-else` relation, if it is trivial or cached.
+    fn if_then_else_shortcut(&self, i: Term, t: Term, e: Term) -> Option<Term> {
         if i == Term::TOP {
-            t
+            Some(t)
         } else if i == Term::BOT {
-            e
+            Some(e)
         } else if t == e {
-            t
+            Some(t)
         } else if t == Term::TOP && e == Term::BOT {
-            i
-        } else if let Some(result) = self.ite_cache.get(&(i, t, e)) {
-            *result
+            Some(i)
         } else {
-            log::trace!("if_then_else: i {i} t {t} e {e} not found");
-            let minvar = Var(min(
-                self.nodes[i.value()].var().value(),
-                min(
-                    self.nodes[t.value()].var().value(),
-                    self.nodes[e.value()].var().value(),
-                ),
-            ));
-            let itop = self.restrict(i, minvar, true);
-            let ttop = self.restrict(t, minvar, true);
-            let etop = self.restrict(e, minvar, true);
-            let ibot = self.restrict(i, minvar, false);
-            let tbot = self.restrict(t, minvar, false);
-            let ebot = self.restrict(e, minvar, false);
-
-            let top_ite = self.if_then_else(itop, ttop, etop);
-            let bot_ite = self.if_then_else(ibot, tbot, ebot);
-            let result = self.node(minvar, bot_ite, top_ite);
-            self.ite_cache.insert((i, t, e), result);
-            result
+            self.ite_cache.get(&(i, t, e)).copied()
         }
     }
 
@@ -317,7 +532,10 @@ impl Bdd {
                     log::trace!("newterm: {} as {:?}", new_term, node);
                     #[cfg(feature = "adhoccounting")]
                     {
-                        let mut count_cache = self.count_cache.borrow_mut();
+                        let mut count_cache = self
+                            .count_cache
+                            .write()
+                            .expect("WriteLock on count_cache failed");
                         let (lo_counts, lo_paths, lodepth) =
                             *count_cache.get(&lo).expect("Cache corrupted");
                         let (hi_counts, hi_paths, hidepth) =
@@ -376,7 +594,7 @@ impl Bdd {
     pub fn models(&self, term: Term, _memoization: bool) -> ModelCounts {
         #[cfg(feature = "adhoccountmodels")]
         {
-            return self.count_cache.borrow().get(&term).expect("The term should be originating from this bdd, otherwise the result would be inconsistent anyways").0;
+            return self.count_cache.read().expect("ReadLock on count_cache failed").get(&term).expect("The term should be originating from this bdd, otherwise the result would be inconsistent anyways").0;
         }
         #[cfg(not(feature = "adhoccountmodels"))]
         if _memoization {
@@ -392,7 +610,7 @@ impl Bdd {
     pub fn paths(&self, term: Term, _memoization: bool) -> ModelCounts {
         #[cfg(feature = "adhoccounting")]
         {
-            return self.count_cache.borrow().get(&term).expect("The term should be originating from this bdd, otherwise the result would be inconsistent anyways").1;
+            return self.count_cache.read().expect("ReadLock on count_cache failed").get(&term).expect("The term should be originating from this bdd, otherwise the result would be inconsistent anyways").1;
         }
         #[cfg(not(feature = "adhoccounting"))]
         if _memoization {
@@ -409,11 +627,18 @@ impl Bdd {
     pub fn max_depth(&self, term: Term) -> usize {
         #[cfg(feature = "adhoccounting")]
         {
-            return self.count_cache.borrow().get(&term).expect("The term should be originating from this bdd, otherwise the result would be inconsistent anyways").2;
+            return self.count_cache.read().expect("ReadLock on count_cache failed").get(&term).expect("The term should be originating from this bdd, otherwise the result would be inconsistent anyways").2;
         }
         #[cfg(not(feature = "adhoccounting"))]
-        match self.count_cache.borrow().get(&term) {
-            Some((_mc, _pc, depth)) => *depth,
+        let cached = self
+            .count_cache
+            .read()
+            .expect("ReadLock on count_cache failed")
+            .get(&term)
+            .copied();
+        #[cfg(not(feature = "adhoccounting"))]
+        match cached {
+            Some((_mc, _pc, depth)) => depth,
             None => {
                 if term.is_truth_value() {
                     0
@@ -425,79 +650,300 @@ impl Bdd {
         }
     }
 
+    /// Draws `n` models of the given roBDD uniformly at random (with replacement).
+    ///
+    /// Each model assigns [`Term::TOP`] or [`Term::BOT`] to the variables `0..num_vars`, where variables which do not occur in the roBDD are chosen freely.
+    /// Returns an empty vector if the roBDD has no models, and [CountOverflow] if the number of models of a node does not fit into an [u128].
+    pub fn sample_models<R: Rng + ?Sized>(
+        &self,
+        term: Term,
+        num_vars: usize,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<Term>>, CountOverflow> {
+        if term == Term::BOT {
+            return Ok(Vec::new());
+        }
+        let mut counts = HashMap::new();
+        self.exact_counts(term, num_vars, &mut counts)?;
+        Ok((0..n)
+            .map(|_| {
+                let mut model = Vec::with_capacity(num_vars);
+                let mut current = term;
+                while model.len() < num_vars {
+                    let level = self.level(current, num_vars);
+                    if model.len() < level {
+                        // the variable does not occur on this path
+                        model.push(Term::from(rng.gen_bool(0.5)));
+                        continue;
+                    }
+                    let node = self.nodes[current.value()];
+                    // both summands fit, as their sum is the number of models of the node
+                    let [lo, hi] = [node.lo(), node.hi()].map(|child| {
+                        Self::exact_count(child, &counts)
+                            << (self.level(child, num_vars) - level - 1)
+                    });
+                    let choose_hi = rng.gen_range(0..lo + hi) >= lo;
+                    model.push(Term::from(choose_hi));
+                    current = if choose_hi { node.hi() } else { node.lo() };
+                }
+                model
+            })
+            .collect())
+    }
+
+    /// Returns the assignments along a path from the root of the given roBDD to the given leaf, if such a path exists.
+    ///
+    /// Variables, which do not occur on the path, are not assigned.
+    pub fn path_to(&self, term: Term, leaf: Term) -> Option<Vec<(Var, bool)>> {
+        let mut path = Vec::new();
+        let mut current = term;
+        while current != leaf {
+            if current.is_truth_value() {
+                return None;
+            }
+            // each inner node of a reduced diagram reaches both leaves, so only a leaf child can be a dead end
+            let node = self.nodes[current.value()];
+            let value = node.lo().is_truth_value() && node.lo() != leaf;
+            path.push((node.var(), value));
+            current = if value { node.hi() } else { node.lo() };
+        }
+        // the path is given from the leaf to the root
+        path.reverse();
+        Some(path)
+    }
+
+    /// Computes the exact number of models of the given roBDD over the variables `0..num_vars`.
+    ///
+    /// In contrast to [models][Bdd::models], variables which do not occur on a path are taken into account for each path.
+    /// Returns [CountOverflow] if the number of models does not fit into an [u128].
+    pub fn exact_models(&self, term: Term, num_vars: usize) -> Result<u128, CountOverflow> {
+        let mut counts = HashMap::new();
+        self.exact_counts(term, num_vars, &mut counts)?;
+        scale(Self::exact_count(term, &counts), self.level(term, num_vars))
+    }
+
+    /// Computes the exact number of models of the given roBDD over the variables `0..num_vars`, together with the number of models, in which each variable is true.
+    ///
+    /// All counts are computed in a single pass over the roBDD, where the number of assignments reaching a node is propagated from the root to the leaves.
+    /// Returns [CountOverflow] if one of the counts does not fit into an [u128].
+    pub fn exact_facet_counts(
+        &self,
+        term: Term,
+        num_vars: usize,
+    ) -> Result<(u128, Vec<u128>), CountOverflow> {
+        let mut counts = HashMap::new();
+        self.exact_counts(term, num_vars, &mut counts)?;
+        let root_level = self.level(term, num_vars);
+        let total = scale(Self::exact_count(term, &counts), root_level)?;
+        let mut true_counts = vec![0u128; num_vars];
+        // the variables above the root are not restricted, so they are true in half of the models
+        for count in true_counts.iter_mut().take(root_level) {
+            *count = total / 2;
+        }
+        // the models of the edges, which skip some variables, are added to each skipped variable by a prefix sum
+        let mut skip_starts = vec![0u128; num_vars + 1];
+        let mut skip_ends = vec![0u128; num_vars + 1];
+        let mut reaching = HashMap::new();
+        reaching.insert(term, scale(1, root_level)?);
+        // the children of a node are always created before the node itself
+        for node in self.reachable(&[term]).into_iter().rev() {
+            if node.is_truth_value() {
+                continue;
+            }
+            let weight = reaching[&node];
+            let bdd_node = self.nodes[node.value()];
+            let level = self.level(node, num_vars);
+            for (child, value) in [(bdd_node.lo(), false), (bdd_node.hi(), true)] {
+                if child == Term::BOT {
+                    continue;
+                }
+                let child_level = self.level(child, num_vars);
+                let paths = scale(weight, child_level - level - 1)?;
+                let reached = reaching.entry(child).or_insert(0u128);
+                *reached = reached.checked_add(paths).ok_or(CountOverflow)?;
+                let models = paths
+                    .checked_mul(Self::exact_count(child, &counts))
+                    .ok_or(CountOverflow)?;
+                if value {
+                    true_counts[level] += models;
+                }
+                // each skipped variable is true in half of these models
+                if child_level > level + 1 {
+                    skip_starts[level + 1] += models / 2;
+                    skip_ends[child_level] += models / 2;
+                }
+            }
+        }
+        let mut skipping = 0u128;
+        for (var, count) in true_counts.iter_mut().enumerate() {
+            skipping = skipping + skip_starts[var] - skip_ends[var];
+            *count += skipping;
+        }
+        Ok((total, true_counts))
+    }
+
+    /// Position of the root variable of the given roBDD in the variable order, where the leaves are placed after all `num_vars` variables.
+    fn level(&self, term: Term, num_vars: usize) -> usize {
+        if term.is_truth_value() {
+            num_vars
+        } else {
+            self.nodes[term.value()].var().value()
+        }
+    }
+
+    /// Returns the number of models of the given roBDD, which have been computed by [exact_counts][Bdd::exact_counts].
+    fn exact_count(term: Term, counts: &HashMap<Term, u128>) -> u128 {
+        match term {
+            Term::TOP => 1,
+            Term::BOT => 0,
+            _ => counts[&term],
+        }
+    }
+
+    /// Computes the exact number of models of each node of the given roBDD over the variables, which are at or below the variable of the node in the variable order.
+    fn exact_counts(
+        &self,
+        term: Term,
+        num_vars: usize,
+        counts: &mut HashMap<Term, u128>,
+    ) -> Result<(), CountOverflow> {
+        // post-order traversal with an explicit stack, so the depth of the roBDD is not limited by the call stack
+        let mut stack = vec![term];
+        while let Some(&current) = stack.last() {
+            if current.is_truth_value() || counts.contains_key(&current) {
+                stack.pop();
+                continue;
+            }
+            let node = self.nodes[current.value()];
+            let pending = [node.lo(), node.hi()]
+                .into_iter()
+                .filter(|child| !child.is_truth_value() && !counts.contains_key(child))
+                .collect::<Vec<_>>();
+            if pending.is_empty() {
+                stack.pop();
+                let level = self.level(current, num_vars);
+                let mut count = 0u128;
+                for child in [node.lo(), node.hi()] {
+                    let models = scale(
+                        Self::exact_count(child, counts),
+                        self.level(child, num_vars) - level - 1,
+                    )?;
+                    count = count.checked_add(models).ok_or(CountOverflow)?;
+                }
+                counts.insert(current, count);
+            } else {
+                stack.extend(pending);
+            }
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)] // dead code due to more efficient ad-hoc building, still used for a couple of tests
     /// Computes the number of counter-models, models, and variables for a given roBDD
     fn modelcount_naive(&self, term: Term) -> CountNode {
-        if term == Term::TOP {
-            (ModelCounts::top(), ModelCounts::top(), 0)
-        } else if term == Term::BOT {
-            (ModelCounts::bot(), ModelCounts::bot(), 0)
-        } else {
-            let node = &self.nodes[term.0];
-            let mut lo_exp = 0u32;
-            let mut hi_exp = 0u32;
-            let (lo_counts, lo_paths, lodepth) = self.modelcount_naive(node.lo());
-            let (hi_counts, hi_paths, hidepth) = self.modelcount_naive(node.hi());
-            if lodepth > hidepth {
-                hi_exp = (lodepth - hidepth) as u32;
-            } else {
-                lo_exp = (hidepth - lodepth) as u32;
+        // post-order traversal with an explicit stack, so the depth of the roBDD is not limited by the call stack
+        let mut counts: HashMap<Term, CountNode> = HashMap::new();
+        counts.insert(Term::TOP, (ModelCounts::top(), ModelCounts::top(), 0));
+        counts.insert(Term::BOT, (ModelCounts::bot(), ModelCounts::bot(), 0));
+        let mut stack = vec![term];
+        while let Some(&current) = stack.last() {
+            if counts.contains_key(&current) {
+                stack.pop();
+                continue;
+            }
+            let node = &self.nodes[current.0];
+            match (counts.get(&node.lo()), counts.get(&node.hi())) {
+                (Some(&lo), Some(&hi)) => {
+                    stack.pop();
+                    counts.insert(current, Self::combine_counts(lo, hi));
+                }
+                (lo, hi) => {
+                    if hi.is_none() {
+                        stack.push(node.hi());
+                    }
+                    if lo.is_none() {
+                        stack.push(node.lo());
+                    }
+                }
             }
+        }
+        counts[&term]
+    }
+
+    /// Combines the counts of the two children of a node.
+    fn combine_counts(
+        (lo_counts, lo_paths, lodepth): CountNode,
+        (hi_counts, hi_paths, hidepth): CountNode,
+    ) -> CountNode {
+        let mut lo_exp = 0u32;
+        let mut hi_exp = 0u32;
+        if lodepth > hidepth {
+            hi_exp = (lodepth - hidepth) as u32;
+        } else {
+            lo_exp = (hidepth - lodepth) as u32;
+        }
+        (
             (
-                (
-                    lo_counts.cmodels * 2usize.pow(lo_exp) + hi_counts.cmodels * 2usize.pow(hi_exp),
-                    lo_counts.models * 2usize.pow(lo_exp) + hi_counts.models * 2usize.pow(hi_exp),
-                )
-                    .into(),
-                (
-                    lo_paths.cmodels + hi_paths.cmodels,
-                    lo_paths.models + hi_paths.models,
-                )
-                    .into(),
-                std::cmp::max(lodepth, hidepth) + 1,
+                lo_counts.cmodels * 2usize.pow(lo_exp) + hi_counts.cmodels * 2usize.pow(hi_exp),
+                lo_counts.models * 2usize.pow(lo_exp) + hi_counts.models * 2usize.pow(hi_exp),
             )
-        }
+                .into(),
+            (
+                lo_paths.cmodels + hi_paths.cmodels,
+                lo_paths.models + hi_paths.models,
+            )
+                .into(),
+            std::cmp::max(lodepth, hidepth) + 1,
+        )
     }
 
     fn modelcount_memoization(&self, term: Term) -> CountNode {
-        if term == Term::TOP {
-            (ModelCounts::top(), ModelCounts::top(), 0)
-        } else if term == Term::BOT {
-            (ModelCounts::bot(), ModelCounts::bot(), 0)
-        } else {
-            if let Some(result) = self.count_cache.borrow().get(&term) {
-                return *result;
+        let leaf = |term: Term| {
+            if term == Term::TOP {
+                Some((ModelCounts::top(), ModelCounts::top(), 0))
+            } else if term == Term::BOT {
+                Some((ModelCounts::bot(), ModelCounts::bot(), 0))
+            } else {
+                None
             }
-            let result = {
-                let node = &self.nodes[term.0];
-                let mut lo_exp = 0u32;
-                let mut hi_exp = 0u32;
-                let (lo_counts, lo_paths, lodepth) = self.modelcount_memoization(node.lo());
-                let (hi_counts, hi_paths, hidepth) = self.modelcount_memoization(node.hi());
-                if lodepth > hidepth {
-                    hi_exp = (lodepth - hidepth) as u32;
-                } else {
-                    lo_exp = (hidepth - lodepth) as u32;
+        };
+        let cached = |term: Term| {
+            leaf(term).or_else(|| {
+                self.count_cache
+                    .read()
+                    .expect("ReadLock on count_cache failed")
+                    .get(&term)
+                    .copied()
+            })
+        };
+        // post-order traversal with an explicit stack, so the depth of the roBDD is not limited by the call stack
+        let mut stack = vec![term];
+        while let Some(&current) = stack.last() {
+            if cached(current).is_some() {
+                stack.pop();
+                continue;
+            }
+            let node = &self.nodes[current.0];
+            match (cached(node.lo()), cached(node.hi())) {
+                (Some(lo), Some(hi)) => {
+                    stack.pop();
+                    self.count_cache
+                        .write()
+                        .expect("WriteLock on count_cache failed")
+                        .insert(current, Self::combine_counts(lo, hi));
                 }
-                (
-                    (
-                        lo_counts.cmodels * 2usize.pow(lo_exp)
-                            + hi_counts.cmodels * 2usize.pow(hi_exp),
-                        lo_counts.models * 2usize.pow(lo_exp)
-                            + hi_counts.models * 2usize.pow(hi_exp),
-                    )
-                        .into(),
-                    (
-                        lo_paths.cmodels + hi_paths.cmodels,
-                        lo_paths.models + hi_paths.models,
-                    )
-                        .into(),
-                    std::cmp::max(lodepth, hidepth) + 1,
-                )
-            };
-            self.count_cache.borrow_mut().insert(term, result);
-            result
+                (lo, hi) => {
+                    if hi.is_none() {
+                        stack.push(node.hi());
+                    }
+                    if lo.is_none() {
+                        stack.push(node.lo());
+                    }
+                }
+            }
         }
+        cached(term).expect("The traversal counts the given term")
     }
 
     /// Repairs the internal structures after an import.
@@ -506,10 +952,12 @@ impl Bdd {
         #[cfg(feature = "adhoccounting")]
         {
             self.count_cache
-                .borrow_mut()
+                .write()
+                .expect("WriteLock on count_cache failed")
                 .insert(Term::TOP, (ModelCounts::top(), ModelCounts::top(), 0));
             self.count_cache
-                .borrow_mut()
+                .write()
+                .expect("WriteLock on count_cache failed")
                 .insert(Term::BOT, (ModelCounts::bot(), ModelCounts::bot(), 0));
             for i in 0..self.nodes.len() {
                 log::debug!("fixing Term({})", i);
@@ -534,6 +982,26 @@ impl Bdd {
         });
     }
 
+    /// Returns the nodes, which are reachable from the given roBDDs, ordered such that each node follows its children.
+    pub(crate) fn reachable(&self, roots: &[Term]) -> Vec<Term> {
+        let mut visited = roots.iter().copied().collect::<HashSet<_>>();
+        let mut stack = roots.to_vec();
+        while let Some(node) = stack.pop() {
+            if !node.is_truth_value() {
+                let bdd_node = self.nodes[node.value()];
+                for child in [bdd_node.lo(), bdd_node.hi()] {
+                    if visited.insert(child) {
+                        stack.push(child);
+                    }
+                }
+            }
+        }
+        // the children of a node are always created before the node itself
+        let mut result = visited.into_iter().collect::<Vec<_>>();
+        result.sort();
+        result
+    }
+
     /// Returns a [HashSet] of [variables][crate::datatypes::Var], which occur in a given roBDD.
     pub fn var_dependencies(&self, tree: Term) -> HashSet<Var> {
         #[cfg(feature = "variablelist")]
@@ -542,16 +1010,19 @@ impl Bdd {
         }
         #[cfg(not(feature = "variablelist"))]
         {
-            let node = self.nodes[tree.value()];
-            if node.var().is_constant() {
-                return HashSet::new();
+            // depth-first search with an explicit stack, so the depth of the roBDD is not limited by the call stack
+            let mut var_set = HashSet::new();
+            let mut visited = HashSet::new();
+            let mut stack = vec![tree];
+            while let Some(term) = stack.pop() {
+                let node = self.nodes[term.value()];
+                if node.var().is_constant() || !visited.insert(term) {
+                    continue;
+                }
+                var_set.insert(node.var());
+                stack.push(node.lo());
+                stack.push(node.hi());
             }
-            let mut var_set = self
-                .var_dependencies(node.lo())
-                .union(&self.var_dependencies(node.hi()))
-                .copied()
-                .collect::<HashSet<Var>>();
-            var_set.insert(node.var());
             var_set
         }
     }
@@ -580,6 +1051,64 @@ impl Bdd {
             }
         })
     }
+
+    /// Renders the roBDDs of the given roots in the [DOT](https://graphviz.org/doc/info/lang.html) language of graphviz.
+    ///
+    /// Each root is a pair of the [variable][crate::datatypes::Var] of a statement and the [`Term`] of its (possibly restricted) acceptance condition.
+    /// Roots and decision nodes are labelled with the statement names provided by `ordering`.
+    /// Hi edges are drawn solid and lo edges are drawn dashed.
+    pub fn to_dot(&self, roots: &[(Var, Term)], ordering: &VarContainer) -> String {
+        use std::fmt::Write;
+
+        let label = |var: Var| escape_dot(&ordering.name(var).unwrap_or_else(|| var.to_string()));
+
+        let mut reachable: BTreeSet<usize> = BTreeSet::new();
+        let mut stack: Vec<Term> = roots.iter().map(|(_, term)| *term).collect();
+        while let Some(term) = stack.pop() {
+            if reachable.insert(term.value()) {
+                let node = self.nodes[term.value()];
+                if !node.var().is_constant() {
+                    stack.push(node.lo());
+                    stack.push(node.hi());
+                }
+            }
+        }
+
+        let mut result = String::from("digraph {\n    node [shape=circle];\n");
+        for &idx in reachable.iter() {
+            let node = self.nodes[idx];
+            match node.var() {
+                Var::TOP => writeln!(result, "    t{idx} [label=\"⊤\", shape=box];"),
+                Var::BOT => writeln!(result, "    t{idx} [label=\"⊥\", shape=box];"),
+                var => writeln!(result, "    t{idx} [label=\"{}\"];", label(var)),
+            }
+            .expect("Writing to a String cannot fail");
+        }
+        for &idx in reachable.iter() {
+            let node = self.nodes[idx];
+            if !node.var().is_constant() {
+                writeln!(
+                    result,
+                    "    t{idx} -> t{} [style=dashed];",
+                    node.lo().value()
+                )
+                .expect("Writing to a String cannot fail");
+                writeln!(result, "    t{idx} -> t{};", node.hi().value())
+                    .expect("Writing to a String cannot fail");
+            }
+        }
+        for (num, (var, term)) in roots.iter().enumerate() {
+            writeln!(
+                result,
+                "    r{num} [label=\"{}\", shape=plaintext];\n    r{num} -> t{};",
+                label(*var),
+                term.value()
+            )
+            .expect("Writing to a String cannot fail");
+        }
+        result.push_str("}\n");
+        result
+    }
 }
 
 #[cfg(test)]
@@ -644,6 +1173,56 @@ mod test {
         assert_eq!(a2, Term(7));
     }
 
+    #[test]
+    fn cardinality() {
+        let mut bdd = Bdd::new();
+        let vars = (0..4).map(|idx| bdd.variable(Var(idx))).collect::<Vec<_>>();
+
+        assert_eq!(bdd.at_least(&vars, 0), Term::TOP);
+        assert_eq!(bdd.at_least(&vars, 5), Term::BOT);
+        assert_eq!(bdd.at_most(&vars, 4), Term::TOP);
+        assert_eq!(bdd.exactly(&vars, 5), Term::BOT);
+        assert_eq!(bdd.at_least(&[], 0), Term::TOP);
+        assert_eq!(bdd.at_least(&[], 1), Term::BOT);
+        // bounds beyond the number of operands are constant, without allocating a threshold for each bound
+        assert_eq!(bdd.at_most(&vars, usize::MAX), Term::TOP);
+        assert_eq!(bdd.exactly(&vars, usize::MAX), Term::BOT);
+        assert_eq!(bdd.at_least(&vars, usize::MAX), Term::BOT);
+        assert_eq!(bdd.at_least(&vars, 5_000_000_000_000), Term::BOT);
+
+        let or = bdd.or(vars[0], vars[1]);
+        let or = bdd.or(or, vars[2]);
+        let or = bdd.or(or, vars[3]);
+        assert_eq!(bdd.at_least(&vars, 1), or);
+        let and = bdd.and(vars[0], vars[1]);
+        let and = bdd.and(and, vars[2]);
+        let and = bdd.and(and, vars[3]);
+        assert_eq!(bdd.at_least(&vars, 4), and);
+        assert_eq!(bdd.exactly(&vars, 4), and);
+
+        // the number of models of each threshold is a binomial coefficient
+        let binomial: [u128; 5] = [1, 4, 6, 4, 1];
+        for k in 0..=4 {
+            let exactly = bdd.exactly(&vars, k);
+            assert_eq!(bdd.exact_models(exactly, 4), Ok(binomial[k]));
+            let at_least = bdd.at_least(&vars, k);
+            assert_eq!(
+                bdd.exact_models(at_least, 4),
+                Ok(binomial[k..].iter().sum())
+            );
+            let at_most = bdd.at_most(&vars, k);
+            assert_eq!(
+                bdd.exact_models(at_most, 4),
+                Ok(binomial[..=k].iter().sum())
+            );
+        }
+
+        // the order of the given roBDDs is irrelevant
+        let mut reversed = vars.clone();
+        reversed.reverse();
+        assert_eq!(bdd.at_least(&vars, 2), bdd.at_least(&reversed, 2));
+    }
+
     #[test]
     fn produce_different_conversions() {
         let mut bdd = Bdd::new();
@@ -693,6 +1272,132 @@ mod test {
         assert_eq!(format!("{}", bdd), " \n0 BddNode: Var(18446744073709551614), lo: Term(0), hi: Term(0)\n1 BddNode: Var(18446744073709551615), lo: Term(1), hi: Term(1)\n2 BddNode: Var(0), lo: Term(0), hi: Term(1)\n3 BddNode: Var(1), lo: Term(0), hi: Term(1)\n4 BddNode: Var(2), lo: Term(0), hi: Term(1)\n5 BddNode: Var(0), lo: Term(0), hi: Term(3)\n6 BddNode: Var(1), lo: Term(4), hi: Term(1)\n7 BddNode: Var(0), lo: Term(4), hi: Term(6)\n");
     }
 
+    #[test]
+    fn dot() {
+        let mut bdd = Bdd::new();
+        let ordering = VarContainer::default();
+        ordering
+            .names()
+            .write()
+            .unwrap()
+            .extend(["a", "b", "c\"d"].iter().map(|name| name.to_string()));
+
+        let v1 = bdd.variable(Var(0));
+        let v2 = bdd.variable(Var(1));
+        let v3 = bdd.variable(Var(2));
+        let a1 = bdd.and(v1, v2);
+        let o1 = bdd.or(a1, v3);
+
+        assert_eq!(
+            bdd.to_dot(&[(Var(0), a1), (Var(2), Term::TOP)], &ordering),
+            "digraph {\n    node [shape=circle];\n    t0 [label=\"⊥\", shape=box];\n    t1 [label=\"⊤\", shape=box];\n    t3 [label=\"b\"];\n    t5 [label=\"a\"];\n    t3 -> t0 [style=dashed];\n    t3 -> t1;\n    t5 -> t0 [style=dashed];\n    t5 -> t3;\n    r0 [label=\"a\", shape=plaintext];\n    r0 -> t5;\n    r1 [label=\"c\\\"d\", shape=plaintext];\n    r1 -> t1;\n}\n"
+        );
+        let dot = bdd.to_dot(&[(Var(1), o1)], &ordering);
+        assert!(dot.contains("t4 [label=\"c\\\"d\"];"));
+        assert!(dot.contains("t7 -> t4 [style=dashed];"));
+        assert!(dot.contains("r0 -> t7;"));
+        assert!(!dot.contains("t2 "));
+    }
+
+    #[test]
+    fn sample_models() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut bdd = Bdd::new();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let v0 = bdd.variable(Var(0));
+        let v2 = bdd.variable(Var(2));
+        let formula = bdd.or(v0, v2);
+        let samples = bdd.sample_models(formula, 3, 3000, &mut rng).unwrap();
+        assert_eq!(samples.len(), 3000);
+        let mut frequencies: HashMap<Vec<Term>, usize> = HashMap::new();
+        for sample in samples {
+            assert!(sample[0].is_true() || sample[2].is_true());
+            *frequencies.entry(sample).or_default() += 1;
+        }
+        // 6 models, each expected 500 times
+        assert_eq!(frequencies.len(), 6);
+        assert!(frequencies
+            .values()
+            .all(|&count| (400..600).contains(&count)));
+
+        assert_eq!(bdd.exact_models(formula, 3), Ok(6));
+        assert_eq!(
+            bdd.path_to(formula, Term::TOP),
+            Some(vec![(Var(2), true), (Var(0), false)])
+        );
+        assert_eq!(
+            bdd.path_to(formula, Term::BOT),
+            Some(vec![(Var(2), false), (Var(0), false)])
+        );
+        assert_eq!(bdd.path_to(Term::TOP, Term::BOT), None);
+        assert_eq!(bdd.exact_models(v2, 4), Ok(8));
+        assert_eq!(bdd.exact_models(Term::TOP, 2), Ok(4));
+        assert_eq!(bdd.exact_models(Term::BOT, 2), Ok(0));
+        assert_eq!(bdd.exact_models(Term::TOP, 127), Ok(1 << 127));
+        assert_eq!(bdd.exact_models(Term::TOP, 128), Err(CountOverflow));
+        assert_eq!(bdd.exact_models(Term::BOT, 200), Ok(0));
+        assert_eq!(bdd.exact_models(v2, 128), Ok(1 << 127));
+        assert_eq!(
+            bdd.sample_models(Term::BOT, 3, 10, &mut rng),
+            Ok(Vec::new())
+        );
+        assert_eq!(
+            bdd.sample_models(Term::TOP, 0, 2, &mut rng),
+            Ok(vec![Vec::<Term>::new(), Vec::new()])
+        );
+        // the number of models of the whole roBDD does not need to fit
+        let v199 = bdd.variable(Var(199));
+        let samples = bdd.sample_models(v199, 200, 2, &mut rng).unwrap();
+        assert!(samples
+            .iter()
+            .all(|sample| sample.len() == 200 && sample[199].is_true()));
+        assert_eq!(bdd.sample_models(v2, 200, 2, &mut rng), Err(CountOverflow));
+    }
+
+    #[test]
+    fn exact_facet_counts() {
+        let mut bdd = Bdd::new();
+        let vars: Vec<Term> = (0..5).map(|var| bdd.variable(Var(var))).collect();
+        let not = bdd.not(vars[3]);
+        let left = bdd.and(vars[1], not);
+        let right = bdd.xor(vars[0], vars[3]);
+        let formula = bdd.or(left, right);
+        let formula = bdd.or(formula, vars[4]);
+        let skipping = bdd.iff(vars[1], vars[4]);
+
+        for term in [Term::TOP, Term::BOT, vars[2], formula, skipping] {
+            for num_vars in 5..7 {
+                let models: Vec<Vec<bool>> = (0..1usize << num_vars)
+                    .map(|bits| (0..num_vars).map(|var| bits >> var & 1 == 1).collect())
+                    .filter(|model: &Vec<bool>| {
+                        let restricted = model
+                            .iter()
+                            .enumerate()
+                            .fold(term, |acc, (var, &val)| bdd.restrict(acc, Var(var), val));
+                        restricted == Term::TOP
+                    })
+                    .collect();
+                let true_counts = (0..num_vars)
+                    .map(|var| models.iter().filter(|model| model[var]).count() as u128)
+                    .collect();
+                assert_eq!(
+                    bdd.exact_facet_counts(term, num_vars),
+                    Ok((models.len() as u128, true_counts))
+                );
+            }
+        }
+
+        let (count, true_counts) = bdd.exact_facet_counts(vars[2], 127).unwrap();
+        assert_eq!(count, 1 << 126);
+        assert_eq!(true_counts[2], 1 << 126);
+        assert!(true_counts
+            .iter()
+            .enumerate()
+            .all(|(var, &true_count)| var == 2 || true_count == 1 << 125));
+        assert_eq!(bdd.exact_facet_counts(Term::TOP, 128), Err(CountOverflow));
+    }
+
     #[test]
     fn counting() {
         let mut bdd = Bdd::new();
@@ -708,7 +1413,12 @@ mod test {
 
         #[cfg(feature = "adhoccountmodels")]
         assert_eq!(bdd.models(v1, false), (1, 1).into());
-        let mut x = bdd.count_cache.get_mut().iter().collect::<Vec<_>>();
+        let mut x = bdd
+            .count_cache
+            .get_mut()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
         x.sort();
         log::debug!("{:?}", formula1);
         for x in bdd.nodes.iter().enumerate() {
@@ -828,7 +1538,7 @@ mod test {
         bdd.not(formula4);
 
         let constructed = bdd.var_deps.clone();
-        bdd.var_deps = Vec::new();
+        bdd.var_deps = SharedVec::default();
         bdd.generate_var_dependencies();
 
         constructed
@@ -925,4 +1635,37 @@ mod test {
             vec![]
         );
     }
+
+    #[test]
+    fn deep_diagram() {
+        // the conjunction of the variables is pushed without the ad-hoc caches, which would need quadratic memory for this depth
+        let conjunction = |bdd: &mut Bdd, depth: usize| {
+            let mut result = Term::TOP;
+            for var in (0..depth).rev() {
+                bdd.nodes.push(BddNode::new(Var(var), Term::BOT, result));
+                result = Term(bdd.nodes.len() - 1);
+            }
+            result
+        };
+        let depth = 200_000;
+        let mut bdd = Bdd::new();
+        let deep = conjunction(&mut bdd, depth);
+        let path = bdd.path_to(deep, Term::TOP).unwrap();
+        assert_eq!(path.len(), depth);
+        assert!(path.iter().all(|(_, value)| *value));
+        assert_eq!(path.last(), Some(&(Var(0), true)));
+        assert_eq!(bdd.path_to(deep, Term::BOT), Some(vec![(Var(0), false)]));
+
+        // the number of counter-models of a deeper conjunction does not fit into an usize
+        let mut bdd = Bdd::new();
+        let shallow = conjunction(&mut bdd, 60);
+        assert_eq!(
+            bdd.modelcount_memoization(shallow),
+            (((1 << 60) - 1, 1).into(), (60, 1).into(), 60)
+        );
+        assert_eq!(
+            bdd.modelcount_naive(shallow),
+            bdd.modelcount_memoization(shallow)
+        );
+    }
 }
@@ -0,0 +1,255 @@
+//! Collections, whose contents can be shared between several [Bdd][super::Bdd]s.
+//!
+//! Each collection consists of a read-only part behind an [Arc], which is shared by all clones, and a local part with the additions of this clone.
+//! [Freezing][SharedVec::freeze] moves the local part into the shared one, so clones, which are taken afterwards, only copy a pointer.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::HashMap, hash::Hash, ops::Index, sync::Arc};
+
+/// A vector, whose prefix may be shared with other clones.
+pub struct SharedVec<T> {
+    shared: Arc<Vec<T>>,
+    local: Vec<T>,
+}
+
+impl<T> SharedVec<T> {
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.shared.len() + self.local.len()
+    }
+
+    /// Returns [true] if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends an element to the local part.
+    pub fn push(&mut self, value: T) {
+        self.local.push(value);
+    }
+
+    /// Returns an iterator over all elements, starting with the shared ones.
+    pub fn iter(&self) -> std::iter::Chain<std::slice::Iter<'_, T>, std::slice::Iter<'_, T>> {
+        self.shared.iter().chain(self.local.iter())
+    }
+}
+
+impl<T: Clone> SharedVec<T> {
+    /// Moves the local elements into the shared part.
+    /// The shared part is only copied if another clone still refers to it.
+    pub fn freeze(&mut self) {
+        if !self.local.is_empty() {
+            Arc::make_mut(&mut self.shared).append(&mut self.local);
+        }
+    }
+}
+
+impl<T> Default for SharedVec<T> {
+    fn default() -> Self {
+        Vec::new().into()
+    }
+}
+
+impl<T> From<Vec<T>> for SharedVec<T> {
+    fn from(local: Vec<T>) -> Self {
+        Self {
+            shared: Arc::new(Vec::new()),
+            local,
+        }
+    }
+}
+
+impl<T: Clone> Clone for SharedVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            local: self.local.clone(),
+        }
+    }
+}
+
+impl<T> Index<usize> for SharedVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index.checked_sub(self.shared.len()) {
+            Some(local) => &self.local[local],
+            None => &self.shared[index],
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SharedVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Clone> IntoIterator for SharedVec<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let mut result = Arc::try_unwrap(self.shared).unwrap_or_else(|shared| (*shared).clone());
+        result.append(&mut self.local);
+        result.into_iter()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SharedVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for SharedVec<T> {}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for SharedVec<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for SharedVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Serialize> Serialize for SharedVec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SharedVec<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Into::into)
+    }
+}
+
+/// A map, whose entries may be shared with other clones.
+/// Local entries take precedence over shared ones with the same key.
+pub struct SharedMap<K, V> {
+    shared: Arc<HashMap<K, V>>,
+    local: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V> SharedMap<K, V> {
+    /// Returns the value of the given key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.local.get(key).or_else(|| self.shared.get(key))
+    }
+
+    /// Returns [true] if the map contains the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.local.contains_key(key) || self.shared.contains_key(key)
+    }
+
+    /// Inserts the given entry into the local part.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.local.insert(key, value);
+    }
+
+    /// Returns an iterator over all entries.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.shared
+            .iter()
+            .filter(|(key, _)| !self.local.contains_key(key))
+            .chain(self.local.iter())
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> SharedMap<K, V> {
+    /// Moves the local entries into the shared part.
+    /// The shared part is only copied if another clone still refers to it.
+    pub fn freeze(&mut self) {
+        if !self.local.is_empty() {
+            Arc::make_mut(&mut self.shared).extend(self.local.drain());
+        }
+    }
+}
+
+impl<K, V> Default for SharedMap<K, V> {
+    fn default() -> Self {
+        Self {
+            shared: Arc::new(HashMap::new()),
+            local: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Clone, V: Clone> Clone for SharedMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            local: self.local.clone(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> FromIterator<(K, V)> for SharedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            shared: Arc::new(HashMap::new()),
+            local: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> IntoIterator for &'a SharedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Box<dyn Iterator<Item = (&'a K, &'a V)> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+impl<K: std::fmt::Debug + Eq + Hash, V: std::fmt::Debug> std::fmt::Debug for SharedMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_log::test;
+
+    #[test]
+    fn shared_vec() {
+        let mut vec: SharedVec<usize> = vec![0, 1].into();
+        vec.freeze();
+        let mut clone = vec.clone();
+        assert!(Arc::ptr_eq(&vec.shared, &clone.shared));
+        clone.push(2);
+        vec.push(3);
+        assert_eq!(clone, vec![0, 1, 2]);
+        assert_eq!(vec, vec![0, 1, 3]);
+        assert_eq!((clone[1], clone[2]), (1, 2));
+
+        // the shared part is only copied, as the clone still refers to it
+        vec.freeze();
+        assert!(!Arc::ptr_eq(&vec.shared, &clone.shared));
+        assert_eq!(vec.into_iter().collect::<Vec<_>>(), vec![0, 1, 3]);
+        assert_eq!(clone.into_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn shared_map() {
+        let mut map: SharedMap<usize, usize> = [(0, 0), (1, 1)].into_iter().collect();
+        map.freeze();
+        let mut clone = map.clone();
+        clone.insert(1, 2);
+        clone.insert(3, 3);
+        assert_eq!(map.get(&1), Some(&1));
+        assert_eq!(clone.get(&1), Some(&2));
+        assert!(!map.contains_key(&3));
+        let mut entries = clone.iter().collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, vec![(&0, &0), (&1, &2), (&3, &3)]);
+    }
+}
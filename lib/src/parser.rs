@@ -4,8 +4,8 @@ use lexical_sort::{natural_lexical_cmp, StringSort};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until},
-    character::complete::{alphanumeric1, multispace0},
-    combinator::{all_consuming, value},
+    character::complete::{alphanumeric1, digit1, multispace0},
+    combinator::{all_consuming, map, map_res, value},
     multi::many1,
     sequence::{delimited, preceded, separated_pair, terminated},
     IResult,
@@ -15,11 +15,15 @@ use std::{
     cell::RefCell,
     sync::{Arc, RwLock},
 };
+use strum::{EnumString, EnumVariantNames};
 
-use crate::datatypes::adf::VarContainer;
+use crate::datatypes::{adf::VarContainer, graph::DependencyGraph};
+use crate::obdd::clamp_bound;
+
+pub mod rewriting;
 
 /// A representation of a formula, still using the strings from the input.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(PartialEq, Eq)]
 pub enum Formula<'a> {
     /// `c(f)` in the input format.
     Bot,
@@ -39,107 +43,572 @@ pub enum Formula<'a> {
     Xor(Box<Formula<'a>>, Box<Formula<'a>>),
     /// If and only if connective between two formulae.
     Iff(Box<Formula<'a>>, Box<Formula<'a>>),
+    /// At least the given number of the subformulae hold.
+    AtLeast(usize, Vec<Formula<'a>>),
+    /// At most the given number of the subformulae hold.
+    AtMost(usize, Vec<Formula<'a>>),
+    /// Exactly the given number of the subformulae hold.
+    Exactly(usize, Vec<Formula<'a>>),
 }
 
-impl Formula<'_> {
-    pub(crate) fn to_boolean_expr(
-        &self,
-    ) -> biodivine_lib_bdd::boolean_expression::BooleanExpression {
+impl<'a> Formula<'a> {
+    /// Returns the names of all atoms which occur in the formula.
+    /// Each name is only returned once, in the order of its first occurrence.
+    pub fn atoms(&self) -> Vec<&str> {
+        let mut result = Vec::new();
+        self.fold(|formula, _: Vec<()>| {
+            if let Formula::Atom(name) = formula {
+                if !result.contains(name) {
+                    result.push(*name);
+                }
+            }
+        });
+        result
+    }
+
+    /// Folds the formula bottom-up, where `combine` is applied to each subformula and the results of its direct subformulae.
+    ///
+    /// The traversal uses an explicit stack instead of recursion, so the nesting depth of the formula is only limited by the available memory.
+    pub(crate) fn fold<'n, T>(&'n self, combine: impl FnMut(&'n Formula<'a>, Vec<T>) -> T) -> T {
+        fold_tree(self, Formula::subformulae, combine)
+    }
+
+    /// Returns the direct subformulae.
+    fn subformulae(&self) -> Vec<&Formula<'a>> {
+        match self {
+            Formula::Bot | Formula::Top | Formula::Atom(_) => Vec::new(),
+            Formula::Not(sub) => vec![sub],
+            Formula::And(sub_a, sub_b)
+            | Formula::Or(sub_a, sub_b)
+            | Formula::Imp(sub_a, sub_b)
+            | Formula::Xor(sub_a, sub_b)
+            | Formula::Iff(sub_a, sub_b) => vec![sub_a, sub_b],
+            Formula::AtLeast(_, subs) | Formula::AtMost(_, subs) | Formula::Exactly(_, subs) => {
+                subs.iter().collect()
+            }
+        }
+    }
+
+    fn connective(&self) -> Connective<'a> {
+        match self {
+            Formula::Bot => Connective::Bot,
+            Formula::Top => Connective::Top,
+            Formula::Atom(name) => Connective::Atom(name),
+            Formula::Not(_) => Connective::Not,
+            Formula::And(..) => Connective::And,
+            Formula::Or(..) => Connective::Or,
+            Formula::Imp(..) => Connective::Imp,
+            Formula::Xor(..) => Connective::Xor,
+            Formula::Iff(..) => Connective::Iff,
+            Formula::AtLeast(k, _) => Connective::AtLeast(*k),
+            Formula::AtMost(k, _) => Connective::AtMost(*k),
+            Formula::Exactly(k, _) => Connective::Exactly(*k),
+        }
+    }
+
+    /// Creates the formula with the given connective and direct subformulae.
+    fn assemble(connective: Connective<'a>, subs: Vec<Formula<'a>>) -> Self {
+        match connective {
+            Connective::Bot => Formula::Bot,
+            Connective::Top => Formula::Top,
+            Connective::Atom(name) => Formula::Atom(name),
+            Connective::Not => Formula::Not(single(subs)),
+            Connective::And => pair(subs, Formula::And),
+            Connective::Or => pair(subs, Formula::Or),
+            Connective::Imp => pair(subs, Formula::Imp),
+            Connective::Xor => pair(subs, Formula::Xor),
+            Connective::Iff => pair(subs, Formula::Iff),
+            Connective::AtLeast(k) => Formula::AtLeast(k, subs),
+            Connective::AtMost(k) => Formula::AtMost(k, subs),
+            Connective::Exactly(k) => Formula::Exactly(k, subs),
+        }
+    }
+
+    /// Moves the direct subformulae onto the given stack and leaves constants in their place.
+    fn detach_subformulae(&mut self, stack: &mut Vec<Formula<'a>>) {
         match self {
-            Formula::Top => biodivine_lib_bdd::boolean_expression::BooleanExpression::Const(true),
-            Formula::Bot => biodivine_lib_bdd::boolean_expression::BooleanExpression::Const(false),
-            Formula::Atom(name) => {
-                biodivine_lib_bdd::boolean_expression::BooleanExpression::Variable(name.to_string())
+            Formula::Bot | Formula::Top | Formula::Atom(_) => {}
+            Formula::Not(sub) => stack.push(std::mem::replace(sub, Formula::Bot)),
+            Formula::And(sub_a, sub_b)
+            | Formula::Or(sub_a, sub_b)
+            | Formula::Imp(sub_a, sub_b)
+            | Formula::Xor(sub_a, sub_b)
+            | Formula::Iff(sub_a, sub_b) => {
+                stack.push(std::mem::replace(sub_a, Formula::Bot));
+                stack.push(std::mem::replace(sub_b, Formula::Bot));
             }
-            Formula::Not(subformula) => {
-                biodivine_lib_bdd::boolean_expression::BooleanExpression::Not(Box::new(
-                    subformula.to_boolean_expr(),
-                ))
+            Formula::AtLeast(_, subs) | Formula::AtMost(_, subs) | Formula::Exactly(_, subs) => {
+                stack.append(subs)
             }
-            Formula::And(sub_a, sub_b) => {
-                biodivine_lib_bdd::boolean_expression::BooleanExpression::And(
-                    Box::new(sub_a.to_boolean_expr()),
-                    Box::new(sub_b.to_boolean_expr()),
+        }
+    }
+
+    /// Combines the given formulae into a balanced tree of the given binary connective, so the nesting depth only grows logarithmically.
+    /// Returns `neutral` if no formula is given.
+    pub(crate) fn balanced<F>(
+        mut formulae: Vec<Formula<'a>>,
+        neutral: Formula<'a>,
+        connective: F,
+    ) -> Formula<'a>
+    where
+        F: Fn(Box<Formula<'a>>, Box<Formula<'a>>) -> Formula<'a> + Copy,
+    {
+        match formulae.len() {
+            0 => neutral,
+            1 => formulae.pop().expect("There is exactly one formula"),
+            len => {
+                let second = formulae.split_off(len / 2);
+                connective(
+                    Box::new(Self::balanced(formulae, neutral.clone(), connective)),
+                    Box::new(Self::balanced(second, neutral, connective)),
                 )
             }
-            Formula::Or(sub_a, sub_b) => {
-                biodivine_lib_bdd::boolean_expression::BooleanExpression::Or(
-                    Box::new(sub_a.to_boolean_expr()),
-                    Box::new(sub_b.to_boolean_expr()),
-                )
+        }
+    }
+
+    /// Instantiates the formula as a [biodivine roBDD][biodivine_lib_bdd::Bdd] over the given variables.
+    pub(crate) fn to_biodivine(
+        &self,
+        varset: &biodivine_lib_bdd::BddVariableSet,
+    ) -> biodivine_lib_bdd::Bdd {
+        self.fold(|formula, subs: Vec<biodivine_lib_bdd::Bdd>| match formula {
+            Formula::Top => varset.mk_true(),
+            Formula::Bot => varset.mk_false(),
+            Formula::Atom(name) => varset.mk_var_by_name(name),
+            Formula::Not(_) => subs[0].not(),
+            Formula::And(..) => subs[0].and(&subs[1]),
+            Formula::Or(..) => subs[0].or(&subs[1]),
+            Formula::Iff(..) => subs[0].iff(&subs[1]),
+            Formula::Imp(..) => subs[0].imp(&subs[1]),
+            Formula::Xor(..) => subs[0].xor(&subs[1]),
+            Formula::AtLeast(k, _) => {
+                let k = clamp_bound(*k, subs.len());
+                Self::biodivine_thresholds(varset, &subs, k).swap_remove(k)
             }
-            Formula::Iff(sub_a, sub_b) => {
-                biodivine_lib_bdd::boolean_expression::BooleanExpression::Iff(
-                    Box::new(sub_a.to_boolean_expr()),
-                    Box::new(sub_b.to_boolean_expr()),
-                )
+            Formula::AtMost(k, _) => {
+                let k = clamp_bound(*k, subs.len());
+                Self::biodivine_thresholds(varset, &subs, k + 1)[k + 1].not()
             }
-            Formula::Imp(sub_a, sub_b) => {
-                biodivine_lib_bdd::boolean_expression::BooleanExpression::Imp(
-                    Box::new(sub_a.to_boolean_expr()),
-                    Box::new(sub_b.to_boolean_expr()),
-                )
+            Formula::Exactly(k, _) => {
+                let k = clamp_bound(*k, subs.len());
+                let thresholds = Self::biodivine_thresholds(varset, &subs, k + 1);
+                thresholds[k].and_not(&thresholds[k + 1])
             }
-            Formula::Xor(sub_a, sub_b) => {
-                biodivine_lib_bdd::boolean_expression::BooleanExpression::Xor(
-                    Box::new(sub_a.to_boolean_expr()),
-                    Box::new(sub_b.to_boolean_expr()),
-                )
+        })
+    }
+
+    /// Computes for each `j` in `0..=max` the [biodivine roBDD][biodivine_lib_bdd::Bdd], which holds if at least `j` of the given roBDDs hold.
+    fn biodivine_thresholds(
+        varset: &biodivine_lib_bdd::BddVariableSet,
+        subs: &[biodivine_lib_bdd::Bdd],
+        max: usize,
+    ) -> Vec<biodivine_lib_bdd::Bdd> {
+        let mut thresholds = (0..=max)
+            .map(|j| {
+                if j == 0 {
+                    varset.mk_true()
+                } else {
+                    varset.mk_false()
+                }
+            })
+            .collect::<Vec<_>>();
+        for sub in subs.iter().rev() {
+            for j in (1..=max).rev() {
+                thresholds[j] =
+                    biodivine_lib_bdd::Bdd::if_then_else(sub, &thresholds[j - 1], &thresholds[j]);
             }
         }
+        thresholds
+    }
+}
+
+impl Clone for Formula<'_> {
+    fn clone(&self) -> Self {
+        self.fold(|formula, subs| Formula::assemble(formula.connective(), subs))
+    }
+}
+
+impl Drop for Formula<'_> {
+    fn drop(&mut self) {
+        // the generated drop would recurse into the subformulae
+        let mut stack = Vec::new();
+        self.detach_subformulae(&mut stack);
+        while let Some(mut formula) = stack.pop() {
+            formula.detach_subformulae(&mut stack);
+        }
     }
 }
 
 impl std::fmt::Debug for Formula<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Formula::Atom(a) => {
-                write!(f, "{}", a)?;
-            }
-            Formula::Not(n) => {
-                write!(f, "not({:?})", n)?;
-            }
-            Formula::And(f1, f2) => {
-                write!(f, "and({:?},{:?})", f1, f2)?;
-            }
-            Formula::Or(f1, f2) => {
-                write!(f, "or({:?},{:?})", f1, f2)?;
+        enum Item<'n, 'a> {
+            Formula(&'n Formula<'a>),
+            Text(&'static str),
+        }
+        let mut stack = vec![Item::Formula(self)];
+        while let Some(item) = stack.pop() {
+            let formula = match item {
+                Item::Formula(formula) => formula,
+                Item::Text(text) => {
+                    write!(f, "{text}")?;
+                    continue;
+                }
+            };
+            match formula {
+                Formula::Atom(a) => write!(f, "{}", a)?,
+                Formula::Not(_) => write!(f, "not(")?,
+                Formula::And(..) => write!(f, "and(")?,
+                Formula::Or(..) => write!(f, "or(")?,
+                Formula::Imp(..) => write!(f, "imp(")?,
+                Formula::Xor(..) => write!(f, "xor(")?,
+                Formula::Iff(..) => write!(f, "iff(")?,
+                Formula::Bot => write!(f, "Const(B)")?,
+                Formula::Top => write!(f, "Const(T)")?,
+                Formula::AtLeast(k, _) => write!(f, "atleast({k}")?,
+                Formula::AtMost(k, _) => write!(f, "atmost({k}")?,
+                Formula::Exactly(k, _) => write!(f, "exactly({k}")?,
             }
-            Formula::Imp(f1, f2) => {
-                write!(f, "imp({:?},{:?})", f1, f2)?;
+            let bounded = matches!(
+                formula,
+                Formula::AtLeast(..) | Formula::AtMost(..) | Formula::Exactly(..)
+            );
+            let subs = formula.subformulae();
+            if bounded || !subs.is_empty() {
+                stack.push(Item::Text(")"));
+                for (idx, sub) in subs.into_iter().enumerate().rev() {
+                    stack.push(Item::Formula(sub));
+                    if bounded || idx > 0 {
+                        stack.push(Item::Text(","));
+                    }
+                }
             }
-            Formula::Xor(f1, f2) => {
-                write!(f, "xor({:?},{:?})", f1, f2)?;
+        }
+        Ok(())
+    }
+}
+
+/// The connective at the root of a formula, i.e. the formula without its subformulae.
+#[derive(Clone, Copy)]
+enum Connective<'n> {
+    Bot,
+    Top,
+    Atom(&'n str),
+    Not,
+    And,
+    Or,
+    Imp,
+    Xor,
+    Iff,
+    AtLeast(usize),
+    AtMost(usize),
+    Exactly(usize),
+}
+
+/// Folds a tree bottom-up with an explicit stack, where `subtrees` returns the direct subtrees of a node and `combine` is applied to each node and the results of its direct subtrees.
+fn fold_tree<'n, N, T>(
+    root: &'n N,
+    subtrees: impl Fn(&'n N) -> Vec<&'n N>,
+    mut combine: impl FnMut(&'n N, Vec<T>) -> T,
+) -> T {
+    enum Visit<'n, N> {
+        Enter(&'n N),
+        Leave(&'n N, usize),
+    }
+    let mut stack = vec![Visit::Enter(root)];
+    let mut results = Vec::new();
+    while let Some(visit) = stack.pop() {
+        match visit {
+            Visit::Enter(node) => {
+                let subs = subtrees(node);
+                stack.push(Visit::Leave(node, subs.len()));
+                stack.extend(subs.into_iter().rev().map(Visit::Enter));
             }
-            Formula::Iff(f1, f2) => {
-                write!(f, "iff({:?},{:?})", f1, f2)?;
+            Visit::Leave(node, count) => {
+                let subs = results.split_off(results.len() - count);
+                results.push(combine(node, subs));
             }
-            Formula::Bot => {
-                write!(f, "Const(B)")?;
+        }
+    }
+    results.pop().expect("The root has been folded")
+}
+
+fn single<T>(subs: Vec<T>) -> Box<T> {
+    Box::new(
+        subs.into_iter()
+            .next()
+            .expect("Unary connectives have one subformula"),
+    )
+}
+
+fn pair<T, F>(subs: Vec<T>, connective: F) -> T
+where
+    F: FnOnce(Box<T>, Box<T>) -> T,
+{
+    let mut subs = subs.into_iter();
+    match (subs.next(), subs.next()) {
+        (Some(sub_a), Some(sub_b)) => connective(Box::new(sub_a), Box::new(sub_b)),
+        _ => unreachable!("Binary connectives have two subformulae"),
+    }
+}
+
+/// A representation of a formula, which owns the names of its atoms.
+///
+/// In contrast to [Formula] it does not borrow from an input string, so it can be generated from data (e.g. by an [AdfBuilder][crate::adf::builder::AdfBuilder]), stored independently of any parser, and sent to other threads.
+/// The names are shared by reference counting, so cloning a formula does not copy them.
+/// Use [From] to convert a [Formula] into an [OwnedFormula] and [as_formula][OwnedFormula::as_formula] for the other direction.
+#[derive(PartialEq, Eq, Hash)]
+pub enum OwnedFormula {
+    /// `c(f)` in the input format.
+    Bot,
+    /// `c(v)` in the input format.
+    Top,
+    /// Some atomic variable.
+    Atom(Arc<str>),
+    /// Negation of a subformula.
+    Not(Box<OwnedFormula>),
+    /// Conjunction of two subformulae.
+    And(Box<OwnedFormula>, Box<OwnedFormula>),
+    /// Disjunction of two subformulae.
+    Or(Box<OwnedFormula>, Box<OwnedFormula>),
+    /// Implication of two subformulae.
+    Imp(Box<OwnedFormula>, Box<OwnedFormula>),
+    /// Exclusive-Or of two subformulae.
+    Xor(Box<OwnedFormula>, Box<OwnedFormula>),
+    /// If and only if connective between two formulae.
+    Iff(Box<OwnedFormula>, Box<OwnedFormula>),
+    /// At least the given number of the subformulae hold.
+    AtLeast(usize, Vec<OwnedFormula>),
+    /// At most the given number of the subformulae hold.
+    AtMost(usize, Vec<OwnedFormula>),
+    /// Exactly the given number of the subformulae hold.
+    Exactly(usize, Vec<OwnedFormula>),
+}
+
+impl OwnedFormula {
+    /// Creates an atom, which refers to the statement with the given name.
+    pub fn atom(name: &str) -> Self {
+        OwnedFormula::Atom(Arc::from(name))
+    }
+
+    /// Creates the negation of the given formula.
+    pub fn negation(formula: OwnedFormula) -> Self {
+        OwnedFormula::Not(Box::new(formula))
+    }
+
+    /// Creates the conjunction of the two given formulae.
+    pub fn and(sub_a: OwnedFormula, sub_b: OwnedFormula) -> Self {
+        OwnedFormula::And(Box::new(sub_a), Box::new(sub_b))
+    }
+
+    /// Creates the disjunction of the two given formulae.
+    pub fn or(sub_a: OwnedFormula, sub_b: OwnedFormula) -> Self {
+        OwnedFormula::Or(Box::new(sub_a), Box::new(sub_b))
+    }
+
+    /// Creates the implication of the two given formulae.
+    pub fn imp(sub_a: OwnedFormula, sub_b: OwnedFormula) -> Self {
+        OwnedFormula::Imp(Box::new(sub_a), Box::new(sub_b))
+    }
+
+    /// Creates the exclusive disjunction of the two given formulae.
+    pub fn xor(sub_a: OwnedFormula, sub_b: OwnedFormula) -> Self {
+        OwnedFormula::Xor(Box::new(sub_a), Box::new(sub_b))
+    }
+
+    /// Creates the equivalence of the two given formulae.
+    pub fn iff(sub_a: OwnedFormula, sub_b: OwnedFormula) -> Self {
+        OwnedFormula::Iff(Box::new(sub_a), Box::new(sub_b))
+    }
+
+    /// Creates the formula, which holds if at least `k` of the given formulae hold.
+    pub fn at_least(k: usize, subs: Vec<OwnedFormula>) -> Self {
+        OwnedFormula::AtLeast(k, subs)
+    }
+
+    /// Creates the formula, which holds if at most `k` of the given formulae hold.
+    pub fn at_most(k: usize, subs: Vec<OwnedFormula>) -> Self {
+        OwnedFormula::AtMost(k, subs)
+    }
+
+    /// Creates the formula, which holds if exactly `k` of the given formulae hold.
+    pub fn exactly(k: usize, subs: Vec<OwnedFormula>) -> Self {
+        OwnedFormula::Exactly(k, subs)
+    }
+
+    /// Returns the names of all atoms which occur in the formula.
+    /// Each name is only returned once, in the order of its first occurrence.
+    pub fn atoms(&self) -> Vec<&str> {
+        let mut result = Vec::new();
+        self.fold(|formula, _: Vec<()>| {
+            if let OwnedFormula::Atom(name) = formula {
+                if !result.contains(&name.as_ref()) {
+                    result.push(name.as_ref());
+                }
             }
-            Formula::Top => {
-                write!(f, "Const(T)")?;
+        });
+        result
+    }
+
+    /// Folds the formula bottom-up without recursion (see [Formula::fold]).
+    fn fold<'n, T>(&'n self, combine: impl FnMut(&'n OwnedFormula, Vec<T>) -> T) -> T {
+        fold_tree(self, OwnedFormula::subformulae, combine)
+    }
+
+    /// Returns the direct subformulae.
+    fn subformulae(&self) -> Vec<&OwnedFormula> {
+        match self {
+            OwnedFormula::Bot | OwnedFormula::Top | OwnedFormula::Atom(_) => Vec::new(),
+            OwnedFormula::Not(sub) => vec![sub],
+            OwnedFormula::And(sub_a, sub_b)
+            | OwnedFormula::Or(sub_a, sub_b)
+            | OwnedFormula::Imp(sub_a, sub_b)
+            | OwnedFormula::Xor(sub_a, sub_b)
+            | OwnedFormula::Iff(sub_a, sub_b) => vec![sub_a, sub_b],
+            OwnedFormula::AtLeast(_, subs)
+            | OwnedFormula::AtMost(_, subs)
+            | OwnedFormula::Exactly(_, subs) => subs.iter().collect(),
+        }
+    }
+
+    fn connective(&self) -> Connective<'_> {
+        match self {
+            OwnedFormula::Bot => Connective::Bot,
+            OwnedFormula::Top => Connective::Top,
+            OwnedFormula::Atom(name) => Connective::Atom(name),
+            OwnedFormula::Not(_) => Connective::Not,
+            OwnedFormula::And(..) => Connective::And,
+            OwnedFormula::Or(..) => Connective::Or,
+            OwnedFormula::Imp(..) => Connective::Imp,
+            OwnedFormula::Xor(..) => Connective::Xor,
+            OwnedFormula::Iff(..) => Connective::Iff,
+            OwnedFormula::AtLeast(k, _) => Connective::AtLeast(*k),
+            OwnedFormula::AtMost(k, _) => Connective::AtMost(*k),
+            OwnedFormula::Exactly(k, _) => Connective::Exactly(*k),
+        }
+    }
+
+    /// Creates the formula with the given connective and direct subformulae.
+    fn assemble(connective: Connective, subs: Vec<OwnedFormula>) -> Self {
+        match connective {
+            Connective::Bot => OwnedFormula::Bot,
+            Connective::Top => OwnedFormula::Top,
+            Connective::Atom(name) => OwnedFormula::atom(name),
+            Connective::Not => OwnedFormula::Not(single(subs)),
+            Connective::And => pair(subs, OwnedFormula::And),
+            Connective::Or => pair(subs, OwnedFormula::Or),
+            Connective::Imp => pair(subs, OwnedFormula::Imp),
+            Connective::Xor => pair(subs, OwnedFormula::Xor),
+            Connective::Iff => pair(subs, OwnedFormula::Iff),
+            Connective::AtLeast(k) => OwnedFormula::AtLeast(k, subs),
+            Connective::AtMost(k) => OwnedFormula::AtMost(k, subs),
+            Connective::Exactly(k) => OwnedFormula::Exactly(k, subs),
+        }
+    }
+
+    /// Moves the direct subformulae onto the given stack and leaves constants in their place.
+    fn detach_subformulae(&mut self, stack: &mut Vec<OwnedFormula>) {
+        match self {
+            OwnedFormula::Bot | OwnedFormula::Top | OwnedFormula::Atom(_) => {}
+            OwnedFormula::Not(sub) => stack.push(std::mem::replace(sub, OwnedFormula::Bot)),
+            OwnedFormula::And(sub_a, sub_b)
+            | OwnedFormula::Or(sub_a, sub_b)
+            | OwnedFormula::Imp(sub_a, sub_b)
+            | OwnedFormula::Xor(sub_a, sub_b)
+            | OwnedFormula::Iff(sub_a, sub_b) => {
+                stack.push(std::mem::replace(sub_a, OwnedFormula::Bot));
+                stack.push(std::mem::replace(sub_b, OwnedFormula::Bot));
             }
+            OwnedFormula::AtLeast(_, subs)
+            | OwnedFormula::AtMost(_, subs)
+            | OwnedFormula::Exactly(_, subs) => stack.append(subs),
+        }
+    }
+
+    /// Returns a [Formula], which borrows the names of the atoms from `self`.
+    pub fn as_formula(&self) -> Formula<'_> {
+        self.fold(|formula, subs| Formula::assemble(formula.connective(), subs))
+    }
+}
+
+impl Clone for OwnedFormula {
+    fn clone(&self) -> Self {
+        self.fold(|formula, subs| match formula {
+            OwnedFormula::Atom(name) => OwnedFormula::Atom(Arc::clone(name)),
+            _ => OwnedFormula::assemble(formula.connective(), subs),
+        })
+    }
+}
+
+impl Drop for OwnedFormula {
+    fn drop(&mut self) {
+        // the generated drop would recurse into the subformulae
+        let mut stack = Vec::new();
+        self.detach_subformulae(&mut stack);
+        while let Some(mut formula) = stack.pop() {
+            formula.detach_subformulae(&mut stack);
         }
-        write!(f, "")
     }
 }
 
+impl From<&Formula<'_>> for OwnedFormula {
+    fn from(formula: &Formula<'_>) -> Self {
+        formula.fold(|formula, subs| OwnedFormula::assemble(formula.connective(), subs))
+    }
+}
+
+impl From<Formula<'_>> for OwnedFormula {
+    fn from(formula: Formula<'_>) -> Self {
+        (&formula).into()
+    }
+}
+
+impl<'a> From<&'a OwnedFormula> for Formula<'a> {
+    fn from(formula: &'a OwnedFormula) -> Self {
+        formula.as_formula()
+    }
+}
+
+impl std::fmt::Debug for OwnedFormula {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_formula())
+    }
+}
+
+/// Enumeration of all the available static variable orderings, which can be applied by [AdfParser::varsort].
+#[derive(EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VarOrdering {
+    /// Lexicographical order of the statement names (see [AdfParser::varsort_lexi]).
+    Lexi,
+    /// Alphanumerical order of the statement names (see [AdfParser::varsort_alphanum]).
+    Alphanum,
+    /// Depth-first traversal of the dependency graph (see [AdfParser::varsort_dfs]).
+    Dfs,
+    /// Breadth-first traversal of the dependency graph (see [AdfParser::varsort_bfs]).
+    Bfs,
+    /// FORCE heuristic on the dependency graph (see [AdfParser::varsort_force]).
+    Force,
+    /// Minimum-degree elimination on the dependency graph (see [AdfParser::varsort_min_degree]).
+    MinDegree,
+    /// Topological order of the strongly connected components (see [AdfParser::varsort_scc]).
+    Scc,
+}
+
 /// A parse structure to hold all the information given by the input file in one place.
 ///
 /// Due to an internal representation with [RefCell][std::cell::RefCell] and [Rc][std::rc::Rc] the values can be
 /// handed over to other structures without further memory needs.
+/// The acceptance conditions are stored as [owned formulae][OwnedFormula], so the parser does not borrow from the input and can be kept or moved to other threads after parsing.
 ///
 /// Note that the parser can be utilised by an [ADF][`crate::adf::Adf`] to initialise it with minimal overhead.
 #[derive(Debug)]
-pub struct AdfParser<'a> {
+pub struct AdfParser {
     namelist: Arc<RwLock<Vec<String>>>,
     dict: Arc<RwLock<HashMap<String, usize>>>,
-    formulae: RefCell<Vec<Formula<'a>>>,
+    formulae: RefCell<Vec<OwnedFormula>>,
     formulaname: RefCell<Vec<String>>,
 }
 
-impl Default for AdfParser<'_> {
+impl Default for AdfParser {
     fn default() -> Self {
         AdfParser {
             namelist: Arc::new(RwLock::new(Vec::new())),
@@ -150,12 +619,9 @@ impl Default for AdfParser<'_> {
     }
 }
 
-impl<'a, 'b> AdfParser<'b>
-where
-    'a: 'b,
-{
+impl AdfParser {
     #[allow(dead_code)]
-    fn parse_statements(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+    fn parse_statements<'a>(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
         move |input| {
             let (rem, _) = many1(self.parse_statement())(input)?;
             Ok((rem, ()))
@@ -170,7 +636,7 @@ where
     /// parser.parse()("s(a).ac(a,c(v)).s(b).ac(b,a).s(c).ac(c,neg(b)).");
     /// let adf = adf_bdd::adf::Adf::from_parser(&parser);
     /// ```
-    pub fn parse(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+    pub fn parse<'a>(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
         log::info!("[Start] parsing");
         |input| {
             value(
@@ -180,7 +646,7 @@ where
         }
     }
 
-    fn parse_statement(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+    fn parse_statement<'a>(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
         |input| {
             let mut dict = self
                 .dict
@@ -201,20 +667,20 @@ where
         }
     }
 
-    fn parse_ac(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+    fn parse_ac<'a>(&'a self) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
         |input| {
             let (remain, (name, formula)) =
                 terminated(AdfParser::ac, terminated(tag("."), multispace0))(input)?;
-            self.formulae.borrow_mut().push(formula);
+            self.formulae.borrow_mut().push(formula.into());
             self.formulaname.borrow_mut().push(String::from(name));
             Ok((remain, ()))
         }
     }
 }
 
-impl<'a> AdfParser<'a> {
+impl AdfParser {
     /// Creates a new parser, utilising the already existing [VarContainer]
-    pub fn with_var_container(var_container: VarContainer) -> AdfParser<'a> {
+    pub fn with_var_container(var_container: VarContainer) -> AdfParser {
         AdfParser {
             namelist: var_container.names(),
             dict: var_container.mappings(),
@@ -224,7 +690,7 @@ impl<'a> AdfParser<'a> {
     }
 }
 
-impl AdfParser<'_> {
+impl AdfParser {
     /// after an update to the namelist, all indizes are updated
     fn regenerate_indizes(&self) {
         self.namelist
@@ -264,6 +730,105 @@ impl AdfParser<'_> {
         self
     }
 
+    /// Sort the variables by a depth-first traversal of the [dependency graph][Self::dependency_graph], where the direction of the edges is ignored.
+    /// Results, which got used before might become corrupted.
+    /// Ensure that all used data is physically copied.
+    pub fn varsort_dfs(&self) -> &Self {
+        self.apply_order(&self.dependency_graph().dfs_order())
+    }
+
+    /// Sort the variables by a breadth-first traversal of the [dependency graph][Self::dependency_graph], where the direction of the edges is ignored.
+    /// Results, which got used before might become corrupted.
+    /// Ensure that all used data is physically copied.
+    pub fn varsort_bfs(&self) -> &Self {
+        self.apply_order(&self.dependency_graph().bfs_order())
+    }
+
+    /// Sort the variables with the FORCE heuristic, such that statements which occur together in an acceptance condition are placed close to each other.
+    /// Results, which got used before might become corrupted.
+    /// Ensure that all used data is physically copied.
+    pub fn varsort_force(&self) -> &Self {
+        self.apply_order(&self.dependency_graph().force_order())
+    }
+
+    /// Sort the variables by a greedy minimum-degree elimination on the [dependency graph][Self::dependency_graph].
+    /// Results, which got used before might become corrupted.
+    /// Ensure that all used data is physically copied.
+    pub fn varsort_min_degree(&self) -> &Self {
+        self.apply_order(&self.dependency_graph().min_degree_order())
+    }
+
+    /// Sort the variables such that the strongly connected components of the [dependency graph][Self::dependency_graph] are clustered and placed in topological order.
+    /// Results, which got used before might become corrupted.
+    /// Ensure that all used data is physically copied.
+    pub fn varsort_scc(&self) -> &Self {
+        self.apply_order(&self.dependency_graph().scc_order())
+    }
+
+    /// Sort the variables with respect to the given [VarOrdering].
+    /// Results, which got used before might become corrupted.
+    /// Ensure that all used data is physically copied.
+    pub fn varsort(&self, ordering: VarOrdering) -> &Self {
+        match ordering {
+            VarOrdering::Lexi => self.varsort_lexi(),
+            VarOrdering::Alphanum => self.varsort_alphanum(),
+            VarOrdering::Dfs => self.varsort_dfs(),
+            VarOrdering::Bfs => self.varsort_bfs(),
+            VarOrdering::Force => self.varsort_force(),
+            VarOrdering::MinDegree => self.varsort_min_degree(),
+            VarOrdering::Scc => self.varsort_scc(),
+        }
+    }
+
+    /// Rewrites all parsed acceptance conditions into the given [NormalForm][rewriting::NormalForm] (see [Formula::rewrite]).
+    /// Use it before instantiating an [ADF][crate::adf::Adf] to reduce the intermediate roBDDs.
+    pub fn rewrite(&self, form: rewriting::NormalForm) -> &Self {
+        log::debug!(
+            "[Start] rewriting the acceptance conditions into {:?}",
+            form
+        );
+        self.formulae.borrow_mut().iter_mut().for_each(|formula| {
+            let rewritten = OwnedFormula::from(formula.as_formula().rewrite(form));
+            *formula = rewritten;
+        });
+        self
+    }
+
+    /// Reorders the namelist, such that the statement at position `order[i]` is moved to position `i`.
+    fn apply_order(&self, order: &[usize]) -> &Self {
+        {
+            let mut namelist = self.namelist.write().expect("WriteLock on namelist failed");
+            let reordered = order
+                .iter()
+                .map(|&idx| namelist[idx].clone())
+                .collect::<Vec<String>>();
+            *namelist = reordered;
+        }
+        self.regenerate_indizes();
+        self
+    }
+
+    /// Computes the dependency graph of the parsed statements with respect to the current variable order.
+    /// There is an edge from `s` to `t` whenever `s` occurs in the acceptance condition of `t`.
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        let dict = self.dict.read().expect("ReadLock on dict failed");
+        let mut graph = DependencyGraph::new(dict.len());
+        self.formulaname
+            .borrow()
+            .iter()
+            .zip(self.formulae.borrow().iter())
+            .for_each(|(name, formula)| {
+                if let Some(&target) = dict.get(name) {
+                    formula
+                        .atoms()
+                        .into_iter()
+                        .filter_map(|atom| dict.get(atom))
+                        .for_each(|&source| graph.add_edge(source, target));
+                }
+            });
+        graph
+    }
+
     fn statement(input: &str) -> IResult<&str, &str> {
         preceded(tag("s"), delimited(tag("("), AdfParser::atomic, tag(")")))(input)
     }
@@ -287,21 +852,92 @@ impl AdfParser<'_> {
         AdfParser::atomic(input).map(|(input, result)| (input, Formula::Atom(result)))
     }
 
-    fn formula(input: &str) -> IResult<&str, Formula> {
+    /// Parses a formula in prefix notation.
+    ///
+    /// The connectives, whose operands are not parsed completely yet, are kept on an explicit stack instead of recursive calls, so the nesting depth is only limited by the available memory.
+    /// If the operands of a connective are malformed, the name of the connective is parsed as an atom instead.
+    fn formula(input: &str) -> IResult<&str, Formula<'_>> {
+        let mut operations: Vec<Operation> = Vec::new();
+        let mut rest = input;
+        'operand: loop {
+            if let Ok((remain, operator)) = AdfParser::operator(rest) {
+                operations.push(Operation {
+                    start: rest,
+                    operator,
+                    operands: Vec::new(),
+                });
+                rest = remain;
+                continue;
+            }
+            let mut result = alt((AdfParser::constant, AdfParser::atomic_term))(rest);
+            loop {
+                let (remain, formula) = match result {
+                    Ok(parsed) => parsed,
+                    Err(error) => match operations.pop() {
+                        Some(failed) => AdfParser::atomic_term(failed.start)?,
+                        None => return Err(error),
+                    },
+                };
+                let operation = match operations.last_mut() {
+                    Some(operation) => operation,
+                    None => return Ok((remain, formula)),
+                };
+                operation.operands.push(formula);
+                if operation.operands.len() < operation.operator.max_operands() {
+                    if let Ok((remain, _)) = AdfParser::separator(remain) {
+                        rest = remain;
+                        continue 'operand;
+                    }
+                }
+                result = match tag(")")(remain) {
+                    Ok((remain, _))
+                        if operation.operands.len() >= operation.operator.min_operands() =>
+                    {
+                        let operation = operations.pop().expect("The operation is on the stack");
+                        Ok((remain, operation.operator.apply(operation.operands)))
+                    }
+                    Ok(_) => Err(nom::Err::Error(nom::error::Error::new(
+                        remain,
+                        nom::error::ErrorKind::Verify,
+                    ))),
+                    Err(error) => Err(error),
+                };
+            }
+        }
+    }
+
+    /// Parses the name of a connective with its opening parenthesis, and the bound of a cardinality connective.
+    fn operator(input: &str) -> IResult<&str, Operator> {
         alt((
-            AdfParser::constant,
-            AdfParser::binary_op,
-            AdfParser::unary_op,
-            AdfParser::atomic_term,
+            value(Operator::Neg, tag("neg(")),
+            value(Operator::And, tag("and(")),
+            value(Operator::Or, tag("or(")),
+            value(Operator::Nand, tag("nand(")),
+            value(Operator::Nor, tag("nor(")),
+            value(Operator::Imp, tag("imp(")),
+            value(Operator::Xor, tag("xor(")),
+            value(Operator::Iff, tag("iff(")),
+            map(
+                delimited(tag("atleast("), AdfParser::bound, AdfParser::separator),
+                Operator::AtLeast,
+            ),
+            map(
+                delimited(tag("atmost("), AdfParser::bound, AdfParser::separator),
+                Operator::AtMost,
+            ),
+            map(
+                delimited(tag("exactly("), AdfParser::bound, AdfParser::separator),
+                Operator::Exactly,
+            ),
         ))(input)
     }
 
-    fn unary_op(input: &str) -> IResult<&str, Formula> {
-        preceded(
-            tag("neg"),
-            delimited(tag("("), AdfParser::formula, tag(")")),
-        )(input)
-        .map(|(input, result)| (input, Formula::Not(Box::new(result))))
+    fn bound(input: &str) -> IResult<&str, usize> {
+        map_res(digit1, str::parse::<usize>)(input)
+    }
+
+    fn separator(input: &str) -> IResult<&str, &str> {
+        delimited(multispace0, tag(","), multispace0)(input)
     }
 
     fn constant(input: &str) -> IResult<&str, Formula> {
@@ -321,48 +957,6 @@ impl AdfParser<'_> {
         })
     }
 
-    fn formula_pair(input: &str) -> IResult<&str, (Formula, Formula)> {
-        separated_pair(
-            preceded(tag("("), AdfParser::formula),
-            delimited(multispace0, tag(","), multispace0),
-            terminated(AdfParser::formula, tag(")")),
-        )(input)
-    }
-
-    fn and(input: &str) -> IResult<&str, Formula> {
-        preceded(tag("and"), AdfParser::formula_pair)(input)
-            .map(|(input, (f1, f2))| (input, Formula::And(Box::new(f1), Box::new(f2))))
-    }
-
-    fn or(input: &str) -> IResult<&str, Formula> {
-        preceded(tag("or"), AdfParser::formula_pair)(input)
-            .map(|(input, (f1, f2))| (input, Formula::Or(Box::new(f1), Box::new(f2))))
-    }
-    fn imp(input: &str) -> IResult<&str, Formula> {
-        preceded(tag("imp"), AdfParser::formula_pair)(input)
-            .map(|(input, (f1, f2))| (input, Formula::Imp(Box::new(f1), Box::new(f2))))
-    }
-
-    fn xor(input: &str) -> IResult<&str, Formula> {
-        preceded(tag("xor"), AdfParser::formula_pair)(input)
-            .map(|(input, (f1, f2))| (input, Formula::Xor(Box::new(f1), Box::new(f2))))
-    }
-
-    fn iff(input: &str) -> IResult<&str, Formula> {
-        preceded(tag("iff"), AdfParser::formula_pair)(input)
-            .map(|(input, (f1, f2))| (input, Formula::Iff(Box::new(f1), Box::new(f2))))
-    }
-
-    fn binary_op(input: &str) -> IResult<&str, Formula> {
-        alt((
-            AdfParser::and,
-            AdfParser::or,
-            AdfParser::imp,
-            AdfParser::xor,
-            AdfParser::iff,
-        ))(input)
-    }
-
     fn atomic(input: &str) -> IResult<&str, &str> {
         alt((
             delimited(tag("\""), take_until("\""), tag("\"")),
@@ -390,7 +984,7 @@ impl AdfParser<'_> {
     /// Returns the acceptance condition of a statement at the given position.
     ///
     /// Will return [None] if the position does not map to a formula.
-    pub fn ac_at(&self, idx: usize) -> Option<Formula> {
+    pub fn ac_at(&self, idx: usize) -> Option<OwnedFormula> {
         self.formulae.borrow().get(idx).cloned()
     }
 
@@ -427,6 +1021,72 @@ impl AdfParser<'_> {
     }
 }
 
+/// A connective of the input format, whose operands are parsed by [AdfParser::formula].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Neg,
+    And,
+    Or,
+    Nand,
+    Nor,
+    Imp,
+    Xor,
+    Iff,
+    AtLeast(usize),
+    AtMost(usize),
+    Exactly(usize),
+}
+
+impl Operator {
+    fn min_operands(&self) -> usize {
+        match self {
+            Operator::Neg | Operator::AtLeast(_) | Operator::AtMost(_) | Operator::Exactly(_) => 1,
+            _ => 2,
+        }
+    }
+
+    fn max_operands(&self) -> usize {
+        match self {
+            Operator::Neg => 1,
+            Operator::Imp | Operator::Xor | Operator::Iff => 2,
+            _ => usize::MAX,
+        }
+    }
+
+    /// Creates the formula of the connective, where conjunctions and disjunctions of more than two operands are balanced.
+    fn apply(self, operands: Vec<Formula>) -> Formula {
+        match self {
+            Operator::Neg => Formula::Not(single(operands)),
+            Operator::And => Formula::balanced(operands, Formula::Top, Formula::And),
+            Operator::Or => Formula::balanced(operands, Formula::Bot, Formula::Or),
+            Operator::Nand => Formula::Not(Box::new(Formula::balanced(
+                operands,
+                Formula::Top,
+                Formula::And,
+            ))),
+            Operator::Nor => Formula::Not(Box::new(Formula::balanced(
+                operands,
+                Formula::Bot,
+                Formula::Or,
+            ))),
+            Operator::Imp => pair(operands, Formula::Imp),
+            Operator::Xor => pair(operands, Formula::Xor),
+            Operator::Iff => pair(operands, Formula::Iff),
+            Operator::AtLeast(k) => Formula::AtLeast(k, operands),
+            Operator::AtMost(k) => Formula::AtMost(k, operands),
+            Operator::Exactly(k) => Formula::Exactly(k, operands),
+        }
+    }
+}
+
+/// A connective, whose operands are not parsed completely yet.
+struct Operation<'a> {
+    /// The input at the name of the connective.
+    start: &'a str,
+    operator: Operator,
+    operands: Vec<Formula<'a>>,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -493,6 +1153,55 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_nary_formula() {
+        // n-ary conjunctions and disjunctions are balanced, so two operands are parsed as before
+        assert_eq!(
+            format!(
+                "{:?}",
+                AdfParser::formula("and(a, b,c,or(d,e,f,g))").unwrap().1
+            ),
+            "and(and(a,b),and(c,or(or(d,e),or(f,g))))"
+        );
+        assert_eq!(
+            format!("{:?}", AdfParser::formula("nand(a,b,c)").unwrap().1),
+            "not(and(a,and(b,c)))"
+        );
+        assert_eq!(
+            format!("{:?}", AdfParser::formula("nor(a,neg(b))").unwrap().1),
+            "not(or(a,not(b)))"
+        );
+        assert_eq!(
+            AdfParser::formula("atleast(2,a,c(v),b)").unwrap(),
+            (
+                "",
+                Formula::AtLeast(
+                    2,
+                    vec![Formula::Atom("a"), Formula::Top, Formula::Atom("b")]
+                )
+            )
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                AdfParser::formula("atmost(10, a,exactly(0,b))").unwrap().1
+            ),
+            "atmost(10,a,exactly(0,b))"
+        );
+        // conjunctions and disjunctions need at least two operands, cardinality connectives a bound
+        assert!(all_consuming(AdfParser::formula)("and(a)").is_err());
+        assert!(all_consuming(AdfParser::formula)("atleast(a,b)").is_err());
+        assert!(all_consuming(AdfParser::formula)("exactly(1)").is_err());
+        // keywords are still allowed as statement names
+        assert_eq!(AdfParser::formula("nor").unwrap().1, Formula::Atom("nor"));
+
+        let (_remain, formula) = AdfParser::formula("exactly(1,a,or(b,a),neg(c))").unwrap();
+        assert_eq!(formula.atoms(), vec!["a", "b", "c"]);
+        let owned = OwnedFormula::from(&formula);
+        assert_eq!(owned.atoms(), vec!["a", "b", "c"]);
+        assert_eq!(owned.as_formula(), formula);
+    }
+
     #[test]
     fn parse() {
         let parser = AdfParser::default();
@@ -531,6 +1240,116 @@ mod test {
         assert_eq!(format!("{:?}", Formula::Bot), "Const(B)");
     }
 
+    #[test]
+    fn atoms() {
+        let (_remain, formula) =
+            AdfParser::formula("and(or(neg(a),iff(b,a)),xor(c(v),c))").unwrap();
+        assert_eq!(formula.atoms(), vec!["a", "b", "c"]);
+        assert_eq!(Formula::Top.atoms(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn deep_nesting() {
+        // far deeper than the call stack of a test thread would allow for recursive parsing, instantiation, and dropping
+        let depth = 100_000;
+        let mut input = String::from("s(a).s(b).ac(a,");
+        input.push_str(&"neg(".repeat(depth));
+        input.push('b');
+        input.push_str(&")".repeat(depth));
+        input.push_str(").ac(b,");
+        input.push_str(&"and(a,".repeat(depth));
+        input.push_str("c(v)");
+        input.push_str(&")".repeat(depth));
+        input.push_str(").");
+
+        let parser = AdfParser::default();
+        parser.parse()(&input).unwrap();
+        let formula = parser.ac_at(0).unwrap();
+        assert_eq!(formula.atoms(), vec!["b"]);
+        let debug = format!("{formula:?}");
+        assert_eq!(debug.len(), depth * 5 + 1);
+        assert_eq!(format!("{:?}", formula.clone()), debug);
+
+        // both statements support each other, so they are undecided and only false in a stable model
+        let mut adf = crate::adf::Adf::from_parser(&parser);
+        assert!(adf.grounded().iter().all(|term| !term.is_truth_value()));
+        assert_eq!(
+            adf.stable().collect::<Vec<_>>(),
+            vec![vec![
+                crate::datatypes::Term::BOT,
+                crate::datatypes::Term::BOT
+            ]]
+        );
+        let bio_adf = crate::adfbiodivine::Adf::from_parser(&parser);
+        assert_eq!(bio_adf.stable_count(), 1);
+    }
+
+    #[test]
+    fn owned_formula() {
+        let (_remain, formula) =
+            AdfParser::formula("and(or(neg(a),iff(b,a)),imp(xor(c(v),c),c(f)))").unwrap();
+        let owned = OwnedFormula::from(&formula);
+        assert_eq!(owned.atoms(), vec!["a", "b", "c"]);
+        assert_eq!(owned.as_formula(), formula);
+        assert_eq!(format!("{owned:?}"), format!("{formula:?}"));
+        assert_eq!(
+            OwnedFormula::from(Formula::Not(Box::new(Formula::Atom("a")))),
+            OwnedFormula::negation(OwnedFormula::atom("a"))
+        );
+
+        // the parser neither borrows from its input nor is bound to the parsing thread
+        let parser = AdfParser::default();
+        {
+            let input = String::from("s(a).s(b).ac(a,c(v)).ac(b,neg(a)).");
+            parser.parse()(&input).unwrap();
+        }
+        let mut adf = std::thread::spawn(move || crate::adf::Adf::from_parser(&parser))
+            .join()
+            .unwrap();
+        assert_eq!(
+            adf.grounded(),
+            vec![crate::datatypes::Term::TOP, crate::datatypes::Term::BOT]
+        );
+    }
+
+    #[test]
+    fn structural_sort() {
+        let input = "s(e).s(d).s(c).s(b).s(a).ac(a,c(v)).ac(b,a).ac(c,or(b,d)).ac(d,c).ac(e,e).";
+        let parser = AdfParser::default();
+        parser.parse()(input).unwrap();
+        let graph = parser.dependency_graph();
+        assert_eq!(graph.successors(4), &[3]);
+        assert_eq!(graph.predecessors(2), &[1, 3]);
+        assert_eq!(graph.successors(0), &[0]);
+
+        parser.varsort_scc();
+        assert_eq!(parser.dict_value("a"), Some(0));
+        assert_eq!(parser.dict_value("b"), Some(1));
+        assert_eq!(parser.dict_value("e"), Some(4));
+        assert!(parser.dict_value("c").unwrap() >= 2 && parser.dict_value("d").unwrap() >= 2);
+
+        let parser = AdfParser::default();
+        parser.parse()(input).unwrap();
+        parser.varsort(VarOrdering::Dfs);
+        assert_eq!(
+            *parser.namelist().read().unwrap(),
+            vec!["e", "d", "c", "b", "a"]
+        );
+        parser.varsort(VarOrdering::Lexi).varsort_bfs();
+        assert_eq!(
+            *parser.namelist().read().unwrap(),
+            vec!["a", "b", "c", "d", "e"]
+        );
+        parser.varsort_min_degree();
+        assert_eq!(
+            *parser.namelist().read().unwrap(),
+            vec!["e", "a", "b", "c", "d"]
+        );
+        parser.varsort_force();
+        assert_eq!(parser.dict_size(), 5);
+        assert_eq!(parser.namelist().read().unwrap().len(), 5);
+    }
+
     #[test]
     fn sort_updates() {
         let parser = AdfParser::default();
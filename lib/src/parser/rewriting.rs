@@ -0,0 +1,707 @@
+/*!
+This module contains a rewriting engine for [formulae][Formula].
+
+Each rewrite preserves the semantics of the formula, but might reduce its size and nesting depth, which in turn reduces intermediate blow-ups during the instantiation of the roBDDs.
+The available rewrites are
+ - constant folding, e.g. `and(a,c(v))` to `a` or `atleast(2,a,c(v),b)` to `or(a,b)`,
+ - removal of double negations,
+ - flattening of nested conjunctions and disjunctions into n-ary ones, which are balanced again afterwards,
+ - removal of duplicate and complementary operands,
+ - absorption, e.g. `and(a,or(a,b))` to `a`,
+ - elimination of `imp`, `iff`, `xor`, and the cardinality connectives `atleast`, `atmost`, and `exactly`,
+ - conversion into negation, conjunctive, and disjunctive normal form.
+
+Note that the conjunctive and disjunctive normal forms might be exponentially larger than the original formula.
+The same holds for the elimination of cardinality connectives with many operands and a bound far from both ends.
+ */
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+use strum::{EnumString, EnumVariantNames};
+
+use super::{fold_tree, Formula};
+use crate::obdd::clamp_bound;
+
+/// Enumeration of all the available rewritings of a [Formula] (see [Formula::rewrite]).
+#[derive(EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NormalForm {
+    /// Constant folding, double negation removal, flattening, and absorption, which keeps all other connectives.
+    Simplified,
+    /// Simplification after the elimination of `imp`, `iff`, `xor`, and the cardinality connectives.
+    Eliminated,
+    /// Negation normal form, i.e. negations only occur in front of atoms.
+    Nnf,
+    /// Conjunctive normal form, i.e. a conjunction of disjunctions of literals.
+    Cnf,
+    /// Disjunctive normal form, i.e. a disjunction of conjunctions of literals.
+    Dnf,
+}
+
+impl<'a> Formula<'a> {
+    /// Rewrites the formula into an equivalent one of the given [NormalForm].
+    pub fn rewrite(&self, form: NormalForm) -> Formula<'a> {
+        let expr = Expr::from(self).simplify();
+        let expr = match form {
+            NormalForm::Simplified => expr,
+            NormalForm::Eliminated => expr.eliminate().simplify(),
+            NormalForm::Nnf => expr.eliminate().nnf().simplify(),
+            NormalForm::Cnf => Expr::from_clauses(expr.eliminate().nnf().clauses(true), true),
+            NormalForm::Dnf => Expr::from_clauses(expr.eliminate().nnf().clauses(false), false),
+        };
+        expr.to_formula()
+    }
+
+    /// Simplifies the formula (see [NormalForm::Simplified]).
+    pub fn simplify(&self) -> Formula<'a> {
+        self.rewrite(NormalForm::Simplified)
+    }
+}
+
+/// Internal representation with n-ary conjunctions and disjunctions.
+///
+/// All traversals use an explicit stack (see [fold][Expr::fold]), so the nesting depth is not limited by the call stack.
+enum Expr<'a> {
+    Const(bool),
+    Atom(&'a str),
+    Not(Box<Expr<'a>>),
+    And(Vec<Expr<'a>>),
+    Or(Vec<Expr<'a>>),
+    Imp(Box<Expr<'a>>, Box<Expr<'a>>),
+    Xor(Box<Expr<'a>>, Box<Expr<'a>>),
+    Iff(Box<Expr<'a>>, Box<Expr<'a>>),
+    /// `atmost` and `exactly` are expressed by negations and conjunctions of `atleast`.
+    AtLeast(usize, Vec<Expr<'a>>),
+}
+
+impl<'a> From<&Formula<'a>> for Expr<'a> {
+    fn from(formula: &Formula<'a>) -> Self {
+        formula.fold(|formula, subs| match formula {
+            Formula::Bot => Expr::Const(false),
+            Formula::Top => Expr::Const(true),
+            Formula::Atom(name) => Expr::Atom(name),
+            Formula::Not(_) => Expr::Not(Box::new(Expr::single(subs))),
+            Formula::And(..) => Expr::And(subs),
+            Formula::Or(..) => Expr::Or(subs),
+            Formula::Imp(..) => {
+                let (sub_a, sub_b) = Expr::pair(subs);
+                Expr::Imp(Box::new(sub_a), Box::new(sub_b))
+            }
+            Formula::Xor(..) => {
+                let (sub_a, sub_b) = Expr::pair(subs);
+                Expr::Xor(Box::new(sub_a), Box::new(sub_b))
+            }
+            Formula::Iff(..) => {
+                let (sub_a, sub_b) = Expr::pair(subs);
+                Expr::Iff(Box::new(sub_a), Box::new(sub_b))
+            }
+            Formula::AtLeast(k, _) => Expr::AtLeast(clamp_bound(*k, subs.len()), subs),
+            Formula::AtMost(k, _) => Expr::AtLeast(clamp_bound(*k, subs.len()) + 1, subs).negate(),
+            Formula::Exactly(k, _) => {
+                let k = clamp_bound(*k, subs.len());
+                let at_least = Expr::AtLeast(k, subs.clone());
+                Expr::And(vec![at_least, Expr::AtLeast(k + 1, subs).negate()])
+            }
+        })
+    }
+}
+
+impl Clone for Expr<'_> {
+    fn clone(&self) -> Self {
+        self.fold(Expr::with_subexprs)
+    }
+}
+
+impl Drop for Expr<'_> {
+    fn drop(&mut self) {
+        // the generated drop would recurse into the subexpressions
+        let mut stack = Vec::new();
+        self.detach_subexprs(&mut stack);
+        while let Some(mut expr) = stack.pop() {
+            expr.detach_subexprs(&mut stack);
+        }
+    }
+}
+
+impl PartialEq for Expr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        let mut stack = vec![(self, other)];
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (Expr::Const(val_a), Expr::Const(val_b)) if val_a == val_b => {}
+                (Expr::Atom(name_a), Expr::Atom(name_b)) if name_a == name_b => {}
+                (Expr::Not(sub_a), Expr::Not(sub_b)) => stack.push((sub_a, sub_b)),
+                (Expr::Imp(sub_a, sub_b), Expr::Imp(other_a, other_b))
+                | (Expr::Xor(sub_a, sub_b), Expr::Xor(other_a, other_b))
+                | (Expr::Iff(sub_a, sub_b), Expr::Iff(other_a, other_b)) => {
+                    stack.push((sub_a, other_a));
+                    stack.push((sub_b, other_b));
+                }
+                (Expr::And(subs), Expr::And(others))
+                | (Expr::Or(subs), Expr::Or(others))
+                | (Expr::AtLeast(_, subs), Expr::AtLeast(_, others))
+                    if subs.len() == others.len() && pair.0.bound() == pair.1.bound() =>
+                {
+                    stack.extend(subs.iter().zip(others.iter()))
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Eq for Expr<'_> {}
+
+impl Hash for Expr<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // pre-order traversal, where the number of operands separates the levels
+        let mut stack = vec![self];
+        while let Some(expr) = stack.pop() {
+            std::mem::discriminant(expr).hash(state);
+            match expr {
+                Expr::Const(val) => val.hash(state),
+                Expr::Atom(name) => name.hash(state),
+                Expr::AtLeast(k, _) => k.hash(state),
+                _ => {}
+            }
+            let subs = expr.subexprs();
+            subs.len().hash(state);
+            stack.extend(subs.into_iter().rev());
+        }
+    }
+}
+
+impl<'a> Expr<'a> {
+    /// Folds the expression bottom-up, i.e. `combine` is called for each subexpression with the results of its direct subexpressions.
+    fn fold<'n, T>(&'n self, combine: impl FnMut(&'n Expr<'a>, Vec<T>) -> T) -> T {
+        fold_tree(self, Expr::subexprs, combine)
+    }
+
+    /// Returns the direct subexpressions.
+    fn subexprs(&self) -> Vec<&Expr<'a>> {
+        match self {
+            Expr::Const(_) | Expr::Atom(_) => Vec::new(),
+            Expr::Not(sub) => vec![sub],
+            Expr::Imp(sub_a, sub_b) | Expr::Xor(sub_a, sub_b) | Expr::Iff(sub_a, sub_b) => {
+                vec![sub_a, sub_b]
+            }
+            Expr::And(subs) | Expr::Or(subs) | Expr::AtLeast(_, subs) => subs.iter().collect(),
+        }
+    }
+
+    /// Moves the direct subexpressions onto the given stack, leaving constants in their place.
+    fn detach_subexprs(&mut self, stack: &mut Vec<Expr<'a>>) {
+        match self {
+            Expr::Const(_) | Expr::Atom(_) => {}
+            Expr::Not(sub) => stack.push(std::mem::replace(sub, Expr::Const(false))),
+            Expr::Imp(sub_a, sub_b) | Expr::Xor(sub_a, sub_b) | Expr::Iff(sub_a, sub_b) => {
+                stack.push(std::mem::replace(sub_a, Expr::Const(false)));
+                stack.push(std::mem::replace(sub_b, Expr::Const(false)));
+            }
+            Expr::And(subs) | Expr::Or(subs) | Expr::AtLeast(_, subs) => stack.append(subs),
+        }
+    }
+
+    /// Builds an expression with the connective of `self` and the given direct subexpressions.
+    fn with_subexprs(&self, subs: Vec<Expr<'a>>) -> Self {
+        match self {
+            Expr::Const(val) => Expr::Const(*val),
+            Expr::Atom(name) => Expr::Atom(name),
+            Expr::Not(_) => Expr::Not(Box::new(Self::single(subs))),
+            Expr::And(_) => Expr::And(subs),
+            Expr::Or(_) => Expr::Or(subs),
+            Expr::Imp(..) => {
+                let (sub_a, sub_b) = Self::pair(subs);
+                Expr::Imp(Box::new(sub_a), Box::new(sub_b))
+            }
+            Expr::Xor(..) => {
+                let (sub_a, sub_b) = Self::pair(subs);
+                Expr::Xor(Box::new(sub_a), Box::new(sub_b))
+            }
+            Expr::Iff(..) => {
+                let (sub_a, sub_b) = Self::pair(subs);
+                Expr::Iff(Box::new(sub_a), Box::new(sub_b))
+            }
+            Expr::AtLeast(k, _) => Expr::AtLeast(*k, subs),
+        }
+    }
+
+    fn single(mut subs: Vec<Expr<'a>>) -> Self {
+        subs.pop().expect("Unary connectives have one operand")
+    }
+
+    fn pair(subs: Vec<Expr<'a>>) -> (Self, Self) {
+        let mut subs = subs.into_iter();
+        match (subs.next(), subs.next()) {
+            (Some(sub_a), Some(sub_b)) => (sub_a, sub_b),
+            _ => unreachable!("Binary connectives have two operands"),
+        }
+    }
+
+    /// The bound of an `atleast`, and zero for all other connectives.
+    fn bound(&self) -> usize {
+        match self {
+            Expr::AtLeast(k, _) => *k,
+            _ => 0,
+        }
+    }
+
+    fn negate(mut self) -> Self {
+        match &mut self {
+            Expr::Const(val) => Expr::Const(!*val),
+            Expr::Not(sub) => std::mem::replace(sub.as_mut(), Expr::Const(false)),
+            _ => Expr::Not(Box::new(self)),
+        }
+    }
+
+    /// Simplifies the expression bottom-up.
+    fn simplify(&self) -> Self {
+        self.fold(|expr, subs| match expr {
+            Expr::Const(_) | Expr::Atom(_) => expr.with_subexprs(subs),
+            Expr::Not(_) => Self::single(subs).negate(),
+            Expr::And(_) => Self::junction(subs, true),
+            Expr::Or(_) => Self::junction(subs, false),
+            Expr::Imp(..) => match Self::pair(subs) {
+                (Expr::Const(true), sub) => sub,
+                (Expr::Const(false), _) | (_, Expr::Const(true)) => Expr::Const(true),
+                (sub, Expr::Const(false)) => sub.negate(),
+                (sub_a, sub_b) if sub_a == sub_b => Expr::Const(true),
+                (sub_a, sub_b) => Expr::Imp(Box::new(sub_a), Box::new(sub_b)),
+            },
+            Expr::Iff(..) => match Self::pair(subs) {
+                (Expr::Const(val), sub) | (sub, Expr::Const(val)) => {
+                    if val {
+                        sub
+                    } else {
+                        sub.negate()
+                    }
+                }
+                (sub_a, sub_b) if sub_a == sub_b => Expr::Const(true),
+                (sub_a, sub_b) if sub_a == sub_b.clone().negate() => Expr::Const(false),
+                (sub_a, sub_b) => Expr::Iff(Box::new(sub_a), Box::new(sub_b)),
+            },
+            Expr::Xor(..) => match Self::pair(subs) {
+                (Expr::Const(val), sub) | (sub, Expr::Const(val)) => {
+                    if val {
+                        sub.negate()
+                    } else {
+                        sub
+                    }
+                }
+                (sub_a, sub_b) if sub_a == sub_b => Expr::Const(false),
+                (sub_a, sub_b) if sub_a == sub_b.clone().negate() => Expr::Const(true),
+                (sub_a, sub_b) => Expr::Xor(Box::new(sub_a), Box::new(sub_b)),
+            },
+            Expr::AtLeast(k, _) => Self::threshold(*k, subs),
+        })
+    }
+
+    /// Simplifies the expression, which holds if at least `k` of the given simplified operands hold.
+    fn threshold(mut k: usize, subs: Vec<Expr<'a>>) -> Self {
+        let mut operands = Vec::new();
+        for sub in subs {
+            match sub {
+                Expr::Const(true) => k = k.saturating_sub(1),
+                Expr::Const(false) => {}
+                sub => operands.push(sub),
+            }
+        }
+        if k == 0 {
+            Expr::Const(true)
+        } else if k > operands.len() {
+            Expr::Const(false)
+        } else if k == 1 {
+            Self::junction(operands, false)
+        } else if k == operands.len() {
+            Self::junction(operands, true)
+        } else {
+            Expr::AtLeast(k, operands)
+        }
+    }
+
+    /// Simplifies a conjunction (`conjunctive`) or a disjunction of the given simplified operands.
+    fn junction(subs: Vec<Expr<'a>>, conjunctive: bool) -> Self {
+        // flatten and fold the constants, the operands of a simplified junction are flat already
+        let mut operands = Vec::new();
+        for sub in subs {
+            match (sub, conjunctive) {
+                (Expr::Const(val), _) if val == conjunctive => {}
+                (Expr::Const(_), _) => return Expr::Const(!conjunctive),
+                (mut sub @ Expr::And(_), true) | (mut sub @ Expr::Or(_), false) => {
+                    sub.detach_subexprs(&mut operands)
+                }
+                (sub, _) => operands.push(sub),
+            }
+        }
+        // remove duplicates and detect complementary operands
+        let mut seen = HashSet::new();
+        operands.retain(|sub| seen.insert(sub.clone()));
+        if operands
+            .iter()
+            .any(|sub| seen.contains(&sub.clone().negate()))
+        {
+            return Expr::Const(!conjunctive);
+        }
+        // absorption: an operand of the dual kind, which contains another operand, is redundant
+        operands.retain(|sub| match (sub, conjunctive) {
+            (Expr::Or(inner), true) | (Expr::And(inner), false) => {
+                !inner.iter().any(|op| seen.contains(op))
+            }
+            _ => true,
+        });
+        match operands.len() {
+            0 => Expr::Const(conjunctive),
+            1 => operands.pop().expect("There is exactly one operand"),
+            _ if conjunctive => Expr::And(operands),
+            _ => Expr::Or(operands),
+        }
+    }
+
+    /// Eliminates `imp`, `iff`, `xor`, and `atleast`.
+    fn eliminate(&self) -> Self {
+        self.fold(|expr, subs| match expr {
+            Expr::Imp(..) => {
+                let (sub_a, sub_b) = Self::pair(subs);
+                Expr::Or(vec![sub_a.negate(), sub_b])
+            }
+            Expr::Iff(..) => {
+                let (sub_a, sub_b) = Self::pair(subs);
+                Expr::And(vec![
+                    Expr::Or(vec![sub_a.clone().negate(), sub_b.clone()]),
+                    Expr::Or(vec![sub_a, sub_b.negate()]),
+                ])
+            }
+            Expr::Xor(..) => {
+                let (sub_a, sub_b) = Self::pair(subs);
+                Expr::And(vec![
+                    Expr::Or(vec![sub_a.clone(), sub_b.clone()]),
+                    Expr::Or(vec![sub_a.negate(), sub_b.negate()]),
+                ])
+            }
+            Expr::AtLeast(k, _) => {
+                let k = *k;
+                // row[j] holds if at least j of the operands processed so far hold,
+                // following atleast(j, x, rest...) = or(and(x, atleast(j-1, rest...)), atleast(j, rest...))
+                let mut row = vec![Expr::Const(false); k + 1];
+                row[0] = Expr::Const(true);
+                for sub in subs.into_iter().rev() {
+                    for j in (1..=k).rev() {
+                        let with = match &row[j - 1] {
+                            Expr::Const(true) => sub.clone(),
+                            Expr::Const(false) => Expr::Const(false),
+                            prev => Expr::And(vec![sub.clone(), prev.clone()]),
+                        };
+                        row[j] = match (with, &row[j]) {
+                            (Expr::Const(false), without) => without.clone(),
+                            (with, Expr::Const(false)) => with,
+                            (with, without) => Expr::Or(vec![with, without.clone()]),
+                        };
+                    }
+                }
+                row.swap_remove(k)
+            }
+            _ => expr.with_subexprs(subs),
+        })
+    }
+
+    /// Pushes the negations of an expression without `imp`, `iff`, and `xor` in front of the atoms.
+    fn nnf(&self) -> Self {
+        // each subexpression is rewritten both as it is and as its negation
+        self.fold(|expr, subs: Vec<(Expr<'a>, Expr<'a>)>| match expr {
+            Expr::Const(val) => (Expr::Const(*val), Expr::Const(!val)),
+            Expr::Atom(name) => (Expr::Atom(name), Expr::Atom(name).negate()),
+            Expr::Not(_) => {
+                let (sub, negated) = Self::single_pair(subs);
+                (negated, sub)
+            }
+            Expr::And(_) => {
+                let (subs, negated): (Vec<_>, Vec<_>) = subs.into_iter().unzip();
+                (Expr::And(subs), Expr::Or(negated))
+            }
+            Expr::Or(_) => {
+                let (subs, negated): (Vec<_>, Vec<_>) = subs.into_iter().unzip();
+                (Expr::Or(subs), Expr::And(negated))
+            }
+            Expr::Imp(..) | Expr::Xor(..) | Expr::Iff(..) | Expr::AtLeast(..) => {
+                unreachable!("Connectives have been eliminated before")
+            }
+        })
+        .0
+    }
+
+    fn single_pair(mut subs: Vec<(Expr<'a>, Expr<'a>)>) -> (Self, Self) {
+        subs.pop().expect("Unary connectives have one operand")
+    }
+
+    /// Computes the clauses of an expression in negation normal form.
+    /// If `conjunctive` is set, the expression is the conjunction of the disjunctive clauses, otherwise the disjunction of the conjunctive clauses.
+    /// Tautological (resp. contradictory) and subsumed clauses are removed.
+    fn clauses(&self, conjunctive: bool) -> Vec<Vec<Expr<'a>>> {
+        self.fold(|expr, subs: Vec<Vec<Vec<Expr<'a>>>>| {
+            let clauses = match (expr, conjunctive) {
+                (Expr::Const(val), _) if *val == conjunctive => Vec::new(),
+                (Expr::Const(_), _) => vec![Vec::new()],
+                (Expr::And(_), true) | (Expr::Or(_), false) => subs.into_iter().flatten().collect(),
+                (Expr::Or(_), true) | (Expr::And(_), false) => {
+                    subs.into_iter()
+                        .fold(vec![Vec::new()], |product, sub_clauses| {
+                            product
+                                .iter()
+                                .flat_map(|clause| {
+                                    sub_clauses.iter().map(move |sub_clause| {
+                                        clause.iter().chain(sub_clause.iter()).cloned().collect()
+                                    })
+                                })
+                                .collect()
+                        })
+                }
+                // the atom of a negative literal has been folded already, but the literal is kept as a whole
+                (literal, _) => vec![vec![literal.clone()]],
+            };
+            Self::reduce_clauses(clauses)
+        })
+    }
+
+    fn reduce_clauses(clauses: Vec<Vec<Expr<'a>>>) -> Vec<Vec<Expr<'a>>> {
+        let mut clauses = clauses
+            .into_iter()
+            .filter_map(|clause| {
+                let literals = clause.into_iter().collect::<HashSet<_>>();
+                (!literals
+                    .iter()
+                    .any(|literal| literals.contains(&literal.clone().negate())))
+                .then_some(literals)
+            })
+            .collect::<Vec<_>>();
+        clauses.sort_by_key(|clause| clause.len());
+        let mut result: Vec<HashSet<Expr<'a>>> = Vec::new();
+        for clause in clauses {
+            if !result.iter().any(|kept| kept.is_subset(&clause)) {
+                result.push(clause);
+            }
+        }
+        result
+            .into_iter()
+            .map(|clause| {
+                let mut clause = clause.into_iter().collect::<Vec<_>>();
+                clause.sort_by(|lit_a, lit_b| lit_a.literal_key().cmp(&lit_b.literal_key()));
+                clause
+            })
+            .collect()
+    }
+
+    fn literal_key(&self) -> (&'a str, bool) {
+        match self {
+            Expr::Atom(name) => (name, true),
+            Expr::Not(sub) => match sub.as_ref() {
+                Expr::Atom(name) => (name, false),
+                _ => unreachable!("Clauses only contain literals"),
+            },
+            _ => unreachable!("Clauses only contain literals"),
+        }
+    }
+
+    fn from_clauses(clauses: Vec<Vec<Expr<'a>>>, conjunctive: bool) -> Self {
+        let clauses = clauses
+            .into_iter()
+            .map(|clause| match (clause.len(), conjunctive) {
+                (1, _) => clause
+                    .into_iter()
+                    .next()
+                    .expect("There is exactly one literal"),
+                (_, true) => Expr::Or(clause),
+                (_, false) => Expr::And(clause),
+            })
+            .collect::<Vec<_>>();
+        match (clauses.len(), conjunctive) {
+            (1, _) => clauses
+                .into_iter()
+                .next()
+                .expect("There is exactly one clause"),
+            (_, true) => Expr::And(clauses),
+            (_, false) => Expr::Or(clauses),
+        }
+    }
+
+    fn to_formula(&self) -> Formula<'a> {
+        self.fold(|expr, subs| match expr {
+            Expr::Const(true) => Formula::Top,
+            Expr::Const(false) => Formula::Bot,
+            Expr::Atom(name) => Formula::Atom(name),
+            Expr::Not(_) => Formula::Not(Box::new(
+                subs.into_iter()
+                    .next()
+                    .expect("Unary connectives have one operand"),
+            )),
+            Expr::And(_) => Formula::balanced(subs, Formula::Top, Formula::And),
+            Expr::Or(_) => Formula::balanced(subs, Formula::Bot, Formula::Or),
+            Expr::Imp(..) => Self::formula_pair(subs, Formula::Imp),
+            Expr::Xor(..) => Self::formula_pair(subs, Formula::Xor),
+            Expr::Iff(..) => Self::formula_pair(subs, Formula::Iff),
+            Expr::AtLeast(k, _) => Formula::AtLeast(*k, subs),
+        })
+    }
+
+    fn formula_pair<F>(subs: Vec<Formula<'a>>, connective: F) -> Formula<'a>
+    where
+        F: FnOnce(Box<Formula<'a>>, Box<Formula<'a>>) -> Formula<'a>,
+    {
+        let mut subs = subs.into_iter();
+        match (subs.next(), subs.next()) {
+            (Some(sub_a), Some(sub_b)) => connective(Box::new(sub_a), Box::new(sub_b)),
+            _ => unreachable!("Binary connectives have two operands"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parser::AdfParser, test::normalised};
+    use test_log::test;
+
+    fn formula(input: &str) -> Formula<'_> {
+        AdfParser::formula(input).unwrap().1
+    }
+
+    #[test]
+    fn simplify() {
+        assert_eq!(
+            formula("and(a,or(c(f),neg(neg(b))))").simplify(),
+            formula("and(a,b)")
+        );
+        assert_eq!(formula("and(a,or(a,b))").simplify(), formula("a"));
+        assert_eq!(
+            formula("or(neg(a),and(b,a))").simplify(),
+            formula("or(neg(a),and(b,a))")
+        );
+        assert_eq!(formula("or(b,or(a,neg(b)))").simplify(), Formula::Top);
+        assert_eq!(
+            formula("imp(c(v),xor(a,c(v)))").simplify(),
+            formula("neg(a)")
+        );
+        assert_eq!(formula("iff(a,a)").simplify(), Formula::Top);
+        assert_eq!(
+            formula("atleast(2,a,c(v),neg(neg(b)),c(f))").simplify(),
+            formula("or(a,b)")
+        );
+        assert_eq!(
+            formula("atmost(1,a,b,c(v))").simplify(),
+            formula("neg(or(a,b))")
+        );
+        assert_eq!(formula("atleast(3,a,b)").simplify(), Formula::Bot);
+        assert_eq!(formula("atleast(0,a,b)").simplify(), Formula::Top);
+        assert_eq!(
+            formula("atmost(18446744073709551615,a)").simplify(),
+            Formula::Top
+        );
+        assert_eq!(
+            formula("exactly(18446744073709551615,a,b)").simplify(),
+            Formula::Bot
+        );
+        assert_eq!(formula("atleast(5000000000000,b)").simplify(), Formula::Bot);
+        assert_eq!(
+            formula("exactly(1,a,b,c)").simplify(),
+            formula("and(atleast(1,a,b,c),neg(atleast(2,a,b,c)))").simplify()
+        );
+        // flattening balances the nested conjunctions
+        assert_eq!(
+            formula("and(a,and(b,and(c,and(d,a))))").simplify(),
+            formula("and(and(a,b),and(c,d))")
+        );
+    }
+
+    #[test]
+    fn normal_forms() {
+        let input = formula("iff(a,imp(b,c))");
+        assert_eq!(
+            input.rewrite(NormalForm::Eliminated),
+            formula("and(or(neg(a),or(neg(b),c)),or(a,neg(or(neg(b),c))))")
+        );
+        assert_eq!(
+            input.rewrite(NormalForm::Nnf),
+            formula("and(or(neg(a),or(neg(b),c)),or(a,and(b,neg(c))))")
+        );
+        assert_eq!(
+            input.rewrite(NormalForm::Cnf),
+            formula("and(or(a,b),and(or(a,neg(c)),or(neg(a),or(neg(b),c))))")
+        );
+        assert_eq!(
+            input.rewrite(NormalForm::Dnf),
+            formula("or(and(a,neg(b)),or(and(a,c),and(neg(a),and(b,neg(c)))))")
+        );
+        assert_eq!(
+            formula("and(a,neg(a))").rewrite(NormalForm::Cnf),
+            Formula::Bot
+        );
+        assert_eq!(
+            formula("or(a,neg(a))").rewrite(NormalForm::Dnf),
+            Formula::Top
+        );
+        assert_eq!(
+            formula("xor(a,b)").rewrite(NormalForm::Dnf),
+            formula("or(and(a,neg(b)),and(neg(a),b))")
+        );
+        assert_eq!(
+            formula("atleast(2,a,b,c)").rewrite(NormalForm::Dnf),
+            formula("or(and(a,b),and(a,c),and(b,c))")
+        );
+        assert_eq!(
+            formula("atleast(2,a,b,c)").rewrite(NormalForm::Cnf),
+            formula("and(or(a,b),or(a,c),or(b,c))")
+        );
+    }
+
+    #[test]
+    fn deep_nesting() {
+        // neither the rewriting nor dropping the expressions is limited by the call stack
+        let negations = (0..100_000).fold(Formula::Atom("a"), |acc, _| Formula::Not(Box::new(acc)));
+        for form in [
+            NormalForm::Simplified,
+            NormalForm::Eliminated,
+            NormalForm::Nnf,
+            NormalForm::Cnf,
+            NormalForm::Dnf,
+        ] {
+            assert_eq!(negations.rewrite(form), Formula::Atom("a"));
+        }
+
+        let implications = (0..100_000).fold(Formula::Atom("a"), |acc, _| {
+            Formula::Imp(Box::new(Formula::Atom("b")), Box::new(acc))
+        });
+        assert!(matches!(
+            &implications.simplify(),
+            Formula::Imp(sub_a, _) if **sub_a == Formula::Atom("b")
+        ));
+    }
+
+    #[test]
+    fn equivalence() {
+        let input = "s(a).s(b).s(c).s(d).ac(a,xor(b,imp(c,neg(d)))).ac(b,iff(a,or(c,and(d,c(v))))).ac(c,and(neg(neg(a)),or(b,c))).ac(d,imp(or(a,c(f)),iff(d,b))).";
+        let cardinality = "s(a).s(b).s(c).s(d).ac(a,atmost(1,b,c,d)).ac(b,exactly(2,a,c,neg(d))).ac(c,atleast(2,a,b,c,d)).ac(d,nor(a,atleast(1,b,c))).";
+        for input in [input, cardinality] {
+            let parser = AdfParser::default();
+            parser.parse()(input).unwrap();
+            let mut expected = crate::adf::Adf::from_parser(&parser);
+            let complete = normalised(expected.complete());
+            let stable = expected.stable().collect::<Vec<_>>();
+
+            for form in [
+                NormalForm::Simplified,
+                NormalForm::Eliminated,
+                NormalForm::Nnf,
+                NormalForm::Cnf,
+                NormalForm::Dnf,
+            ] {
+                let parser = AdfParser::default();
+                parser.parse()(input).unwrap();
+                parser.rewrite(form);
+                let mut adf = crate::adf::Adf::from_parser(&parser);
+                assert_eq!(normalised(adf.complete()), complete);
+                assert_eq!(adf.stable().collect::<Vec<_>>(), stable);
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Incremental SAT solving for the [Cnf][crate::adf::cnf::Cnf] encodings of an ADF.
+//!
+//! The [Solver] wraps the CDCL solver of the [varisat] crate behind a small interface, which takes the literals as in the DIMACS format.
+//! Clauses can be added between calls of [solve][Solver::solve], which allows the enumeration of models with blocking clauses.
+
+use varisat::{ExtendFormula, Lit};
+
+use crate::adf::cnf::Cnf;
+
+/// An incremental SAT solver, which is backed by [varisat].
+pub struct Solver {
+    solver: varisat::Solver<'static>,
+    variables: usize,
+}
+
+impl std::fmt::Debug for Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Solver")
+            .field("variables", &self.variables)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Solver {
+    /// Creates a solver without clauses over the variables `1..=variables`.
+    pub fn new(variables: usize) -> Self {
+        Self {
+            solver: varisat::Solver::new(),
+            variables,
+        }
+    }
+
+    /// Creates a solver, which contains all clauses of the given [Cnf].
+    pub fn from_cnf(cnf: &Cnf) -> Self {
+        let mut result = Self::new(cnf.variables);
+        for clause in cnf.clauses.iter() {
+            result.add_clause(clause);
+        }
+        result
+    }
+
+    /// Returns the number of variables.
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    /// Adds the clause, which is a disjunction of the given literals.
+    /// Variables, which have not been known yet, are added to the solver.
+    pub fn add_clause(&mut self, literals: &[i64]) {
+        if let Some(max) = literals.iter().map(|literal| literal.unsigned_abs()).max() {
+            self.variables = self.variables.max(max as usize);
+        }
+        let clause = literals
+            .iter()
+            .map(|&literal| Lit::from_dimacs(literal as isize))
+            .collect::<Vec<_>>();
+        self.solver.add_clause(&clause);
+    }
+
+    /// Searches for a model of all clauses.
+    ///
+    /// Returns the model, where the `i`-th value is the value of the variable `i + 1`, or [None] if the clauses are unsatisfiable.
+    /// Variables, which do not occur in any clause, are false in the model.
+    pub fn solve(&mut self) -> Option<Vec<bool>> {
+        let satisfiable = self
+            .solver
+            .solve()
+            .expect("The solver is neither interrupted nor writes a proof");
+        if !satisfiable {
+            return None;
+        }
+        let mut model = vec![false; self.variables];
+        for lit in self.solver.model()? {
+            if let Some(value) = model.get_mut(lit.index()) {
+                *value = lit.is_positive();
+            }
+        }
+        Some(model)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use test_log::test;
+
+    fn satisfies(clauses: &[Vec<i64>], model: &[bool]) -> bool {
+        clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|&literal| model[literal.unsigned_abs() as usize - 1] == (literal > 0))
+        })
+    }
+
+    #[test]
+    fn pigeonhole() {
+        // four pigeons do not fit into three holes, where the variable 3p + h + 1 places pigeon p into hole h
+        let mut solver = Solver::new(12);
+        let var = |pigeon: i64, hole: i64| 3 * pigeon + hole + 1;
+        for pigeon in 0..4 {
+            solver.add_clause(&[var(pigeon, 0), var(pigeon, 1), var(pigeon, 2)]);
+        }
+        for hole in 0..3 {
+            for first in 0..4 {
+                for second in first + 1..4 {
+                    solver.add_clause(&[-var(first, hole), -var(second, hole)]);
+                }
+            }
+        }
+        assert_eq!(solver.solve(), None);
+        solver.add_clause(&[1]);
+        assert_eq!(solver.solve(), None);
+    }
+
+    #[test]
+    fn random_formulae() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let variables = rng.gen_range(1..=10);
+            let clauses = (0..rng.gen_range(0..=45))
+                .map(|_| {
+                    (0..rng.gen_range(1..=3))
+                        .map(|_| {
+                            let var = rng.gen_range(1..=variables) as i64;
+                            if rng.gen() {
+                                var
+                            } else {
+                                -var
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let models = (0..1usize << variables)
+                .map(|bits| {
+                    (0..variables)
+                        .map(|idx| bits & (1 << idx) != 0)
+                        .collect::<Vec<_>>()
+                })
+                .filter(|model| satisfies(&clauses, model))
+                .count();
+
+            let mut solver = Solver::new(variables);
+            for clause in clauses.iter() {
+                solver.add_clause(clause);
+            }
+            let mut found = 0;
+            while let Some(model) = solver.solve() {
+                assert!(satisfies(&clauses, &model));
+                found += 1;
+                let blocking = model
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, &value)| {
+                        if value {
+                            -(idx as i64 + 1)
+                        } else {
+                            idx as i64 + 1
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                solver.add_clause(&blocking);
+            }
+            assert_eq!(found, models);
+        }
+    }
+}
@@ -4,6 +4,66 @@ use super::datatypes::*;
 use super::parser::*;
 use test_log::test;
 
+/// Maps the undecided values to [Term::UND] and sorts the models, as undecided values are represented by the roBDDs of the acceptance conditions.
+pub(crate) fn normalised(models: impl IntoIterator<Item = Vec<Term>>) -> Vec<Vec<Term>> {
+    let mut result = models
+        .into_iter()
+        .map(|model| {
+            model
+                .into_iter()
+                .map(|term| {
+                    if term.is_truth_value() {
+                        term
+                    } else {
+                        Term::UND
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// Propagates the unit clauses of the [Cnf][crate::adf::cnf::Cnf] on the given partial assignment.
+/// Returns the total assignment or [None] on a conflict, where the fixed variables are expected to determine all the other ones.
+pub(crate) fn unit_propagation(
+    cnf: &crate::adf::cnf::Cnf,
+    mut assignment: Vec<Option<bool>>,
+) -> Option<Vec<bool>> {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for clause in cnf.clauses.iter() {
+            let value = |literal: &i64| {
+                assignment[literal.unsigned_abs() as usize - 1].map(|value| value == (*literal > 0))
+            };
+            if clause.iter().any(|literal| value(literal) == Some(true)) {
+                continue;
+            }
+            let open = clause
+                .iter()
+                .filter(|literal| value(literal).is_none())
+                .copied()
+                .collect::<Vec<_>>();
+            match open.len() {
+                0 => return None,
+                1 => {
+                    assignment[open[0].unsigned_abs() as usize - 1] = Some(open[0] > 0);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+    }
+    Some(
+        assignment
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .expect("The fixed variables determine all the other ones"),
+    )
+}
+
 #[test]
 fn adf_biodivine_cmp_1() {
     let parser = AdfParser::default();
@@ -10,6 +10,7 @@ use actix_web::rt::task::spawn_blocking;
 use actix_web::rt::time::timeout;
 use actix_web::{delete, get, post, put, web, HttpMessage, HttpRequest, HttpResponse, Responder};
 use adf_bdd::datatypes::adf::VarContainer;
+use adf_bdd::datatypes::budget::{Budget, Interrupted};
 use adf_bdd::datatypes::{BddNode, Term, Var};
 use futures_util::{FutureExt, TryStreamExt};
 use mongodb::bson::doc;
@@ -18,6 +19,7 @@ use mongodb::results::DeleteResult;
 use names::{Generator, Name};
 use serde::{Deserialize, Serialize};
 
+use adf_bdd::adf::navigation::{Facet, NavigationSemantics};
 use adf_bdd::adf::Adf;
 use adf_bdd::adfbiodivine::Adf as BdAdf;
 use adf_bdd::obdd::Bdd;
@@ -165,12 +167,12 @@ pub(crate) struct SimplifiedAdf {
     pub(crate) ac: AcDb,
 }
 
-impl From<Adf> for SimplifiedAdf {
-    fn from(source: Adf) -> Self {
+impl From<&Adf> for SimplifiedAdf {
+    fn from(source: &Adf) -> Self {
         Self {
-            ordering: source.ordering.into(),
-            bdd: source.bdd.nodes.into_iter().map(Into::into).collect(),
-            ac: source.ac.into_iter().map(|t| t.0.to_string()).collect(),
+            ordering: source.ordering.clone().into(),
+            bdd: source.bdd.nodes.iter().map(|&node| node.into()).collect(),
+            ac: source.ac.iter().map(|t| t.0.to_string()).collect(),
         }
     }
 }
@@ -394,6 +396,7 @@ async fn add_adf_problem(
 
     let username_clone = username.clone();
     let problem_name_clone = problem_name.clone();
+    let app_state_clone = app_state.clone();
 
     let adf_fut = timeout(
         COMPUTE_TIME,
@@ -418,7 +421,7 @@ async fn add_adf_problem(
                 .map_err(|_| "ADF could not be parsed, double check your input!");
 
             let result = parse_result.map(|_| {
-                let lib_adf = match adf_problem_input.parsing {
+                let mut lib_adf = match adf_problem_input.parsing {
                     Parsing::Naive => Adf::from_parser(&parser),
                     Parsing::Hybrid => {
                         let bd_adf = BdAdf::from_parser(&parser);
@@ -431,7 +434,10 @@ async fn add_adf_problem(
                     graph: DoubleLabeledGraph::from_adf_and_ac(&lib_adf, None),
                 };
 
-                (SimplifiedAdf::from(lib_adf), ac_and_graph)
+                lib_adf.freeze();
+                let simp_adf = SimplifiedAdf::from(&lib_adf);
+
+                (simp_adf, ac_and_graph, lib_adf)
             });
 
             app_state
@@ -445,6 +451,7 @@ async fn add_adf_problem(
     );
 
     spawn(adf_fut.then(move |adf_res| async move {
+        let mut instantiated = None;
         let (adf, ac_and_graph): (SimplifiedAdfOpt, AcsAndGraphsOpt) = match adf_res {
             Err(err) => (
                 SimplifiedAdfOpt::Error(err.to_string()),
@@ -458,28 +465,67 @@ async fn add_adf_problem(
                 SimplifiedAdfOpt::Error(err.to_string()),
                 AcsAndGraphsOpt::Error(err.to_string()),
             ),
-            Ok(Ok(Ok((adf, ac_and_graph)))) => (
-                SimplifiedAdfOpt::Some(adf),
-                AcsAndGraphsOpt::Some(vec![ac_and_graph]),
-            ),
+            Ok(Ok(Ok((adf, ac_and_graph, lib_adf)))) => {
+                instantiated = Some(lib_adf);
+                (
+                    SimplifiedAdfOpt::Some(adf),
+                    AcsAndGraphsOpt::Some(vec![ac_and_graph]),
+                )
+            }
         };
 
         let result = adf_coll
             .update_one(
-                doc! { "name": problem_name, "username": username },
+                doc! { "name": &problem_name, "username": &username },
                 doc! { "$set": { "adf": &adf, "acs_per_strategy.parse_only": &ac_and_graph } },
                 None,
             )
             .await;
 
-        if let Err(err) = result {
-            log::error!("{err}");
+        match result {
+            Err(err) => log::error!("{err}"),
+            Ok(_) => {
+                // only cache the ADF once the database holds the matching problem
+                if let Some(lib_adf) = instantiated {
+                    app_state_clone.instantiated_adfs.lock().unwrap().replace(
+                        &username,
+                        &problem_name,
+                        Arc::new(lib_adf),
+                    );
+                }
+            }
         }
     }));
 
     HttpResponse::Ok().body("Parsing started...")
 }
 
+/// Returns the frozen ADF of the given problem and instantiates it from the database representation, if it is not cached yet.
+fn instantiated_adf(
+    app_state: &AppState,
+    username: &str,
+    problem_name: &str,
+    simp_adf: SimplifiedAdf,
+) -> Arc<Adf> {
+    if let Some(adf) = app_state
+        .instantiated_adfs
+        .lock()
+        .unwrap()
+        .get(username, problem_name)
+    {
+        return adf;
+    }
+
+    // the instantiation happens outside of the lock, so other problems are not blocked
+    let mut adf: Adf = simp_adf.into();
+    adf.freeze();
+    app_state
+        .instantiated_adfs
+        .lock()
+        .unwrap()
+        .insert(username, problem_name, Arc::new(adf))
+}
+
 #[derive(Deserialize)]
 struct SolveAdfProblemBody {
     strategy: Strategy,
@@ -573,29 +619,41 @@ async fn solve_adf_problem(
             #[cfg(feature = "mock_long_computations")]
             std::thread::sleep(Duration::from_secs(20));
 
-            let mut adf: Adf = simp_adf.into();
-
-            let acs: Vec<Ac> = match adf_problem_input.strategy {
-                Strategy::Complete => adf.complete().collect(),
-                Strategy::Ground => vec![adf.grounded()],
-                Strategy::Stable => adf.stable().collect(),
+            let mut adf = Adf::clone(&instantiated_adf(
+                &app_state,
+                &running_info.username,
+                &running_info.adf_name,
+                simp_adf,
+            ));
+
+            // the budget stops the computation itself, as the timeout can not abort a blocking task
+            let budget = Budget::new().with_timeout(COMPUTE_TIME);
+            let acs: Result<Vec<Ac>, Interrupted> = match adf_problem_input.strategy {
+                Strategy::Complete => adf.complete_budgeted(budget).collect(),
+                Strategy::Ground => adf.grounded_budgeted(&budget).map(|ac| vec![ac]),
+                Strategy::Stable => adf.stable_budgeted(budget).collect(),
                 // TODO: INPUT VALIDATION: only allow this for hybrid parsing
-                Strategy::StableCountingA => adf.stable_count_optimisation_heu_a().collect(),
+                Strategy::StableCountingA => adf
+                    .stable_count_optimisation_heu_a_budgeted(budget)
+                    .collect(),
                 // TODO: INPUT VALIDATION: only allow this for hybrid parsing
-                Strategy::StableCountingB => adf.stable_count_optimisation_heu_b().collect(),
+                Strategy::StableCountingB => adf
+                    .stable_count_optimisation_heu_b_budgeted(budget)
+                    .collect(),
                 // TODO: support more than just default heuristics
                 Strategy::StableNogood => adf
-                    .stable_nogood(adf_bdd::adf::heuristics::Heuristic::default())
+                    .stable_nogood_budgeted(adf_bdd::adf::heuristics::Heuristic::default(), budget)
                     .collect(),
             };
 
-            let acs_and_graphs: Vec<AcAndGraph> = acs
-                .iter()
-                .map(|ac| AcAndGraph {
-                    ac: ac.iter().map(|t| t.0.to_string()).collect(),
-                    graph: DoubleLabeledGraph::from_adf_and_ac(&adf, Some(ac)),
-                })
-                .collect();
+            let acs_and_graphs: Result<Vec<AcAndGraph>, Interrupted> = acs.map(|acs| {
+                acs.iter()
+                    .map(|ac| AcAndGraph {
+                        ac: ac.iter().map(|t| t.0.to_string()).collect(),
+                        graph: DoubleLabeledGraph::from_adf_and_ac(&adf, Some(ac)),
+                    })
+                    .collect()
+            });
 
             app_state
                 .currently_running
@@ -611,7 +669,8 @@ async fn solve_adf_problem(
         let acs_and_graphs_enum: AcsAndGraphsOpt = match acs_and_graphs_res {
             Err(err) => AcsAndGraphsOpt::Error(err.to_string()),
             Ok(Err(err)) => AcsAndGraphsOpt::Error(err.to_string()),
-            Ok(Ok(acs_and_graphs)) => AcsAndGraphsOpt::Some(acs_and_graphs),
+            Ok(Ok(Err(err))) => AcsAndGraphsOpt::Error(err.to_string()),
+            Ok(Ok(Ok(acs_and_graphs))) => AcsAndGraphsOpt::Some(acs_and_graphs),
         };
 
         let result = adf_coll.update_one(doc! { "name": problem_name, "username": username }, match adf_problem_input.strategy {
@@ -631,6 +690,132 @@ async fn solve_adf_problem(
     HttpResponse::Ok().body("Solving started...")
 }
 
+#[derive(Deserialize)]
+struct FacetBody {
+    statement: String,
+    value: Term,
+}
+
+#[derive(Deserialize)]
+struct NavigateAdfProblemBody {
+    semantics: NavigationSemantics,
+    #[serde(default)]
+    fixed: Vec<FacetBody>,
+}
+
+#[derive(Serialize)]
+struct FacetInfo {
+    statement: String,
+    value: Term,
+    model_count: u128,
+}
+
+#[derive(Serialize)]
+struct NavigationInfo {
+    model_count: u128,
+    facets: Vec<FacetInfo>,
+}
+
+#[post("/{problem_name}/navigate")]
+async fn navigate_adf_problem(
+    app_state: web::Data<AppState>,
+    identity: Option<Identity>,
+    path: web::Path<String>,
+    req_body: web::Json<NavigateAdfProblemBody>,
+) -> impl Responder {
+    let problem_name = path.into_inner();
+    let navigate_input: NavigateAdfProblemBody = req_body.into_inner();
+    let adf_coll: mongodb::Collection<AdfProblem> = app_state
+        .mongodb_client
+        .database(DB_NAME)
+        .collection(ADF_COLL);
+
+    let username = match identity.map(|id| id.id()) {
+        None => {
+            return HttpResponse::Unauthorized()
+                .body("You need to login to navigate an ADF problem.")
+        }
+        Some(Err(err)) => return HttpResponse::InternalServerError().body(err.to_string()),
+        Some(Ok(username)) => username,
+    };
+
+    let adf_problem = match adf_coll
+        .find_one(doc! { "name": &problem_name, "username": &username }, None)
+        .await
+    {
+        Err(err) => return HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(None) => {
+            return HttpResponse::NotFound()
+                .body(format!("ADF problem with name {problem_name} not found."))
+        }
+        Ok(Some(prob)) => prob,
+    };
+
+    let simp_adf: SimplifiedAdf = match adf_problem.adf {
+        SimplifiedAdfOpt::None => {
+            return HttpResponse::BadRequest().body("The ADF problem has not been parsed yet.")
+        }
+        SimplifiedAdfOpt::Error(err) => {
+            return HttpResponse::BadRequest().body(format!(
+                "The ADF problem could not be parsed. Update it and try again. Error: {err}"
+            ))
+        }
+        SimplifiedAdfOpt::Some(adf) => adf,
+    };
+
+    let navigation_res = spawn_blocking(move || {
+        let mut adf = Adf::clone(&instantiated_adf(
+            &app_state,
+            &username,
+            &problem_name,
+            simp_adf,
+        ));
+
+        let fixed = navigate_input
+            .fixed
+            .into_iter()
+            .map(|facet| {
+                adf.ordering
+                    .variable(&facet.statement)
+                    .map(|var| Facet::new(var, facet.value))
+                    .ok_or(facet.statement)
+            })
+            .collect::<Result<Vec<Facet>, String>>()
+            .map_err(|statement| format!("Statement {statement} does not exist."))?;
+
+        let navigation = adf
+            .navigate_budgeted(
+                navigate_input.semantics,
+                &fixed,
+                &Budget::new().with_timeout(COMPUTE_TIME),
+            )
+            .map_err(|err| err.to_string())?;
+
+        Ok::<_, String>(NavigationInfo {
+            model_count: navigation.model_count,
+            facets: navigation
+                .facets
+                .into_iter()
+                .map(|count| FacetInfo {
+                    statement: adf
+                        .ordering
+                        .name(count.facet.statement)
+                        .expect("Each statement has a name"),
+                    value: count.facet.value,
+                    model_count: count.model_count,
+                })
+                .collect(),
+        })
+    })
+    .await;
+
+    match navigation_res {
+        Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
+        Ok(Err(err)) => HttpResponse::BadRequest().body(err),
+        Ok(Ok(navigation)) => HttpResponse::Ok().json(navigation),
+    }
+}
+
 #[get("/{problem_name}")]
 async fn get_adf_problem(
     app_state: web::Data<AppState>,
@@ -698,7 +883,14 @@ async fn delete_adf_problem(
         }) => HttpResponse::InternalServerError().body("Adf Problem could not be deleted."),
         Ok(DeleteResult {
             deleted_count: 1, ..
-        }) => HttpResponse::Ok().body("Adf Problem deleted."),
+        }) => {
+            app_state
+                .instantiated_adfs
+                .lock()
+                .unwrap()
+                .remove(&username, &problem_name);
+            HttpResponse::Ok().body("Adf Problem deleted.")
+        }
         Ok(_) => {
             unreachable!("delete_one removes at most one entry so all cases are covered already")
         }
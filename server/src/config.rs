@@ -1,7 +1,8 @@
-use std::collections::HashSet;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use adf_bdd::adf::Adf;
 use mongodb::Client;
 use serde::Serialize;
 
@@ -10,6 +11,8 @@ use crate::adf::Strategy;
 pub(crate) const COOKIE_DURATION: actix_web::cookie::time::Duration =
     actix_web::cookie::time::Duration::minutes(30);
 pub(crate) const COMPUTE_TIME: Duration = Duration::from_secs(120);
+/// Number of frozen ADFs, which are kept in memory at most.
+pub(crate) const ADF_CACHE_CAPACITY: usize = 64;
 
 pub(crate) const ASSET_DIRECTORY: &str = "./assets";
 
@@ -34,4 +37,62 @@ pub(crate) struct RunningInfo {
 pub(crate) struct AppState {
     pub(crate) mongodb_client: Client,
     pub(crate) currently_running: Mutex<HashSet<RunningInfo>>,
+    /// Frozen ADFs by username and problem name; requests only clone them instead of rebuilding them from the database.
+    pub(crate) instantiated_adfs: Mutex<AdfCache>,
+}
+
+/// Least recently used cache of frozen ADFs, which holds at most [ADF_CACHE_CAPACITY] entries.
+#[derive(Default)]
+pub(crate) struct AdfCache {
+    entries: HashMap<(String, String), (Arc<Adf>, u64)>,
+    clock: u64,
+}
+
+impl AdfCache {
+    pub(crate) fn get(&mut self, username: &str, problem_name: &str) -> Option<Arc<Adf>> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries
+            .get_mut(&(username.to_string(), problem_name.to_string()))
+            .map(|(adf, last_used)| {
+                *last_used = clock;
+                Arc::clone(adf)
+            })
+    }
+
+    /// Inserts the ADF unless the problem is cached already and returns the cached one.
+    /// The least recently used entry is evicted if the cache is full.
+    pub(crate) fn insert(&mut self, username: &str, problem_name: &str, adf: Arc<Adf>) -> Arc<Adf> {
+        self.clock += 1;
+        let key = (username.to_string(), problem_name.to_string());
+        if !self.entries.contains_key(&key) && self.entries.len() >= ADF_CACHE_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        let (adf, last_used) = self.entries.entry(key).or_insert((adf, self.clock));
+        *last_used = self.clock;
+        Arc::clone(adf)
+    }
+
+    /// Replaces the cached ADF of the problem, e.g. after it has been parsed again.
+    pub(crate) fn replace(&mut self, username: &str, problem_name: &str, adf: Arc<Adf>) {
+        self.remove(username, problem_name);
+        self.insert(username, problem_name, adf);
+    }
+
+    pub(crate) fn remove(&mut self, username: &str, problem_name: &str) {
+        self.entries
+            .remove(&(username.to_string(), problem_name.to_string()));
+    }
+
+    /// Removes all ADFs of the given user.
+    pub(crate) fn remove_user(&mut self, username: &str) {
+        self.entries.retain(|(user, _), _| user != username);
+    }
 }
@@ -22,9 +22,9 @@ mod user;
 
 use adf::{
     add_adf_problem, delete_adf_problem, get_adf_problem, get_adf_problems_for_user,
-    solve_adf_problem,
+    navigate_adf_problem, solve_adf_problem,
 };
-use config::{AppState, ASSET_DIRECTORY, COOKIE_DURATION};
+use config::{AdfCache, AppState, ASSET_DIRECTORY, COOKIE_DURATION};
 use user::{
     create_username_index, delete_account, login, logout, register, update_user, user_info,
 };
@@ -50,6 +50,7 @@ async fn main() -> std::io::Result<()> {
     let app_data = web::Data::new(AppState {
         mongodb_client: client.clone(),
         currently_running: Mutex::new(HashSet::new()),
+        instantiated_adfs: Mutex::new(AdfCache::default()),
     });
 
     HttpServer::new(move || {
@@ -93,6 +94,7 @@ async fn main() -> std::io::Result<()> {
                 web::scope("/adf")
                     .service(add_adf_problem)
                     .service(solve_adf_problem)
+                    .service(navigate_adf_problem)
                     .service(get_adf_problem)
                     .service(delete_adf_problem)
                     .service(get_adf_problems_for_user),
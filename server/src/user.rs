@@ -124,6 +124,12 @@ async fn delete_account(
                     Ok(DeleteResult {
                         deleted_count: _, ..
                     }) => {
+                        app_state
+                            .instantiated_adfs
+                            .lock()
+                            .unwrap()
+                            .remove_user(&username);
+
                         // Delete actual user
                         match user_coll
                             .delete_one(doc! { "username": &username }, None)
@@ -348,10 +354,17 @@ async fn update_user(
                             Err(err) => HttpResponse::InternalServerError().body(err.to_string()),
                             Ok(UpdateResult {
                                 modified_count: _, ..
-                            }) => HttpResponse::Ok().json(UserInfo {
-                                username: user.username,
-                                temp: false,
-                            }),
+                            }) => {
+                                app_state
+                                    .instantiated_adfs
+                                    .lock()
+                                    .unwrap()
+                                    .remove_user(&username);
+                                HttpResponse::Ok().json(UserInfo {
+                                    username: user.username,
+                                    temp: false,
+                                })
+                            }
                         }
                     }
                     Ok(_) => unreachable!(